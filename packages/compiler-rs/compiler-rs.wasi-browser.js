@@ -58,4 +58,11 @@ const {
 export default __napiModule.exports
 export const compile = __napiModule.exports.compile
 export const ErrorCodes = __napiModule.exports.ErrorCodes
+export const generateTemplateModule = __napiModule.exports.generateTemplateModule
+export const generateVirtualCode = __napiModule.exports.generateVirtualCode
+export const ImportStyle = __napiModule.exports.ImportStyle
 export const transform = __napiModule.exports.transform
+export const transformAsync = __napiModule.exports.transformAsync
+export const transformMany = __napiModule.exports.transformMany
+export const transformSfc = __napiModule.exports.transformSfc
+export const transformTemplate = __napiModule.exports.transformTemplate
@@ -110,4 +110,11 @@ const { instance: __napiInstance, module: __wasiModule, napiModule: __napiModule
 module.exports = __napiModule.exports
 module.exports.compile = __napiModule.exports.compile
 module.exports.ErrorCodes = __napiModule.exports.ErrorCodes
+module.exports.generateTemplateModule = __napiModule.exports.generateTemplateModule
+module.exports.generateVirtualCode = __napiModule.exports.generateVirtualCode
+module.exports.ImportStyle = __napiModule.exports.ImportStyle
 module.exports.transform = __napiModule.exports.transform
+module.exports.transformAsync = __napiModule.exports.transformAsync
+module.exports.transformMany = __napiModule.exports.transformMany
+module.exports.transformSfc = __napiModule.exports.transformSfc
+module.exports.transformTemplate = __napiModule.exports.transformTemplate
@@ -2,16 +2,24 @@
 /* eslint-disable */
 export declare function compile(source: string, options?: CompilerOptions | undefined | null): CompileCodegenResult
 
+export interface CodeMapping {
+  sourceOffset: number
+  generatedOffset: number
+  length: number
+}
+
 export interface CompileCodegenResult {
   helpers: Set<string>
   templates: Array<Template>
   delegates: Set<string>
   code: string
+  ir?: string
 }
 
 export interface CompilerError extends SyntaxError {
   code: number
   loc?: [number, number]
+  filename?: string
 }
 
 export interface CompilerOptions {
@@ -23,10 +31,18 @@ export interface CompilerOptions {
    */
   isCustomElement?: (arg: string) => boolean
   onError?: (arg: object) => void
+  /** * Compile-time transforms for custom directives, keyed by the directive name
+   * without the `v-` prefix. The returned props and events are bound to the element.
+   */
+  directiveTransforms?: Record<string, (arg: CustomDirective) => CustomDirectiveResult | undefined | null>
   /** * Generate source map?
    * @default false
    */
   sourceMap?: boolean
+  /** * Source map of the source when it was generated from another file,
+   * the output map is chained through it to point at the original source.
+   */
+  inputSourceMap?: string
   /** * Filename for source map generation.
    * Also used for self-recursive reference in templates
    * @default 'index.jsx'
@@ -34,8 +50,17 @@ export interface CompilerOptions {
   filename?: string
   /** * When enabled, JSX within `defineVaporComponent` is transformed to Vapor DOM,
    * while all other JSX is transformed to Virtual DOM.
+   * `@vapor`, `@vdom` or `@jsxMode vapor|vdom` comments before the first statement,
+   * a statement or a function override the mode of the JSX within them. A comment
+   * only applies to the whole file if a blank line or a directive follows it.
    */
   interop?: boolean
+  /**
+   * Compiles the JSX in vDOM mode, see `interop`, to `createVNode` calls with
+   * patch flags, block roots and hoisted static vnodes, instead of leaving it to another JSX plugin.
+   * @default false
+   */
+  vdom?: boolean
   /** * Enabled HMR support.
    * @default false
    */
@@ -44,30 +69,194 @@ export interface CompilerOptions {
    * @default false
    */
   ssr?: boolean
+  /** * Return a JSON dump of the IR of each JSX root, for debugging and tooling.
+   * @default false
+   */
+  dumpIr?: boolean
+  /** * Module each runtime helper is imported from, keyed by the helper name.
+   * Defaults to `vue-jsx-vapor` for `setNodes`, `createNodes` and `createComponent(WithFallback)`,
+   * `/__vue-jsx-ssr-register-helper` for `ssrRegisterHelper` and `vue` for the rest.
+   */
+  runtimeModules?: Record<string, string>
+  /** * Global variable of each runtime module for the `global` import style,
+   * defaults to the PascalCased module name, e.g. `Vue` for `vue`.
+   */
+  runtimeGlobals?: Record<string, string>
+  /** * How runtime helpers are imported, `require` is used instead of `import` for `.js`/`.cjs` scripts.
+   * @default 'named'
+   */
+  importStyle?: ImportStyle
+  /** * Declare templates right before the top-level statement that uses them instead of at the top of the module,
+   * so that unused components are tree-shaken together with their templates.
+   * @default false
+   */
+  perComponentTemplates?: boolean
+  /** * Import templates from this module instead of declaring them in every file, e.g. `virtual:vapor-templates`.
+   * Its content is generated by `generateTemplateModule` from the templates of all compiled files.
+   */
+  templateModule?: string
+  /** * Functions that return the component they wrap, e.g. `memoComponent` in `memoComponent(defineVaporComponent(...))`,
+   * so that wrapped components are still registered for HMR and SSR.
+   */
+  componentWrappers?: Array<string>
+  /**
+   * Functions that define Vapor components.
+   * @default ['defineVaporComponent']
+   */
+  vaporFactories?: Array<string>
+  /**
+   * Functions that define vDOM components.
+   * @default ['defineComponent']
+   */
+  vdomFactories?: Array<string>
+  /**
+   * Modules the component factories are imported from, including aliased and
+   * namespace imports. Factories imported from other modules are ignored.
+   * @default ['vue', 'vue-jsx-vapor']
+   */
+  factoryModules?: Array<string>
+}
+
+/** The directive passed to a user-provided directive transform. */
+export interface ComponentUsage {
+  /** Tag name as written, e.g. `Foo.Bar` or `my-comp`. */
+  name: string
+  /** Span of the tag name in the opening tag. */
+  loc: [number, number]
+}
+
+export interface CustomDirective {
+  /** The directive name without the `v-` prefix, e.g. `focus` for `v-focus`. */
+  name: string
+  arg?: string
+  modifiers: Array<string>
+  /** The source text of the directive value. */
+  exp?: string
+}
+
+export interface CustomDirectiveEvent {
+  /** The event name, e.g. `click`. */
+  name: string
+  /** * JavaScript expression of the event handler.
+   * Defaults to the directive expression.
+   */
+  value?: string
+  modifiers?: Array<string>
+}
+
+export interface CustomDirectiveProp {
+  key: string
+  /** * JavaScript expression of the prop value.
+   * Defaults to the directive expression.
+   */
+  value?: string
+  /** * Whether `value` is a plain string instead of an expression.
+   * @default false
+   */
+  isStatic?: boolean
+}
+
+export interface CustomDirectiveResult {
+  props?: Array<CustomDirectiveProp>
+  events?: Array<CustomDirectiveEvent>
+  /** * Whether the directive should still be applied at runtime.
+   * @default false
+   */
+  needRuntime?: boolean
 }
 
+/**
+ * Errors reported through `onError`. Compiling goes on after each of them,
+ * so that every error of a file is reported in one pass, and the broken
+ * parts recover as documented on each variant.
+ */
 export declare const enum ErrorCodes {
+  /** The unterminated tag is closed at the end of the template. */
+  EofInTag = 9,
+  /** The end tag is ignored. */
+  XInvalidEndTag = 23,
+  /** The element is closed at the end of its parent. */
+  XMissingEndTag = 24,
+  /** The rest of the template is rendered as text. */
+  XMissingInterpolationEnd = 25,
+  /** The condition throws when it's evaluated, the other branches still compile. */
   VIfNoExpression = 28,
+  /** The element is replaced with a placeholder that throws when it's rendered. */
   VElseNoAdjacentIf = 30,
+  /** The element is replaced with a placeholder that throws when it's rendered. */
   VForNoExpression = 31,
+  /** The element is replaced with a placeholder that throws when it's rendered. */
   VForMalformedExpression = 32,
+  /** The handler is a no-op. */
   VOnNoExpression = 35,
+  /** The component is replaced with a placeholder that throws when it's rendered. */
   VSlotMixedSlotUsage = 37,
+  /** The duplicate slot is dropped. */
   VSlotDuplicateSlotNames = 38,
+  /** The extraneous children are dropped. */
   VSlotExtraneousDefaultSlotChildren = 39,
+  /** The directive is ignored. */
   VSlotMisplaced = 40,
+  /** The element is rendered without two-way binding. */
   VModelNoExpression = 41,
+  /** The element is rendered without two-way binding. */
   VModelMalformedExpression = 42,
+  /** The element content is cleared. */
   VHtmlNoExpression = 53,
+  /** The children are dropped, the directive replaces them as in Vue. */
   VHtmlWithChildren = 54,
+  /** The element content is cleared. */
   VTextNoExpression = 55,
+  /** The children are dropped, the directive replaces them as in Vue. */
   VTextWithChildren = 56,
+  /** Warning, the binding still compiles. */
   VModelOnInvalidElement = 57,
+  /** Warning, the argument is ignored. */
   VModelArgOnElement = 58,
+  /** Warning, the binding still compiles. */
   VModelOnFileInputElement = 59,
+  /** Warning, both bindings still compile. */
   VModelUnnecessaryValue = 60,
+  /** The directive is ignored. */
   VShowNoExpression = 61,
-  VSlotsNoExpression = 62
+  /** The directive is ignored. */
+  VSlotsNoExpression = 62,
+  /** The directive is ignored. */
+  VDomUnsupportedDirective = 63,
+  /** The directive is applied without its argument, e.g. to the default slot. */
+  XUnsupportedDynamicArgument = 64,
+  /** The root is left out of the IR dump. */
+  XIrSerializationFailed = 65,
+  /** The file is transformed as far as it could be parsed. */
+  XSyntaxError = 66,
+  /** The source map maps to the source the compiler received. */
+  XInvalidInputSourceMap = 67,
+  /** Only the first of the templates is exported from the template module. */
+  XTemplateExportCollision = 68
+}
+
+/** Generates the template module from the templates of many compilations. */
+export declare function generateTemplateModule(templates: Array<Template>, options?: CompilerOptions | undefined | null): string
+
+/**
+ * Generates TypeScript that type-checks the directives of the JSX in `source`:
+ * `v-for` is lowered to a `for...of` loop over `__iter(source)`, `v-if` to a
+ * conditional, the props of `v-slot` are typed from the component's slots,
+ * `v-model` of inputs, selects and textareas is checked as their `value` or
+ * `checked`, and the values of the other directives are checked as
+ * expressions. The rest of the source is copied as is. The filename
+ * defaults to `index.tsx`.
+ */
+export declare function generateVirtualCode(source: string, options?: CompilerOptions | undefined | null): VirtualCode
+
+/** How runtime helpers are imported. */
+export declare const enum ImportStyle {
+  /** `import { template as _template } from "vue"` */
+  Named = 'named',
+  /** `import * as _vue from "vue"`, helpers are called as `_vue.template`. */
+  Namespace = 'namespace',
+  /** No imports, helpers are called on a global variable, e.g. `Vue.template`. */
+  Global = 'global'
 }
 
 export type Template =
@@ -75,7 +264,74 @@ export type Template =
 
 export declare function transform(source: string, options?: CompilerOptions | undefined | null): TransformReturn
 
+/** Same as `transform`, but compiles on the libuv thread pool. */
+export declare function transformAsync(source: string, options?: CompilerOptions | undefined | null): Promise<TransformReturn>
+
+export interface TransformFile {
+  source: string
+  /** Filename of the source, defaults to `options.filename`. */
+  filename?: string
+  /** Source map of the source, defaults to `options.inputSourceMap`. */
+  inputSourceMap?: string
+}
+
+/**
+ * Compiles many sources in parallel on Rust threads, results are in the same order as `files`.
+ * A file that fails to compile gets an `error` instead of a `result`.
+ */
+export declare function transformMany(files: Array<TransformFile>, options?: CompilerOptions | undefined | null): Promise<Array<TransformManyResult>>
+
+/** The result of one file of `transformMany`, either `result` or `error` is set. */
+export interface TransformManyResult {
+  filename: string
+  result?: TransformReturn
+  /** Why the file failed to compile, the other files are compiled regardless. */
+  error?: string
+}
+
+/**
+ * Compiles the JSX in the `<script>` and `<script setup>` blocks of a Vue SFC
+ * with `lang="jsx"` or `lang="tsx"`, and returns the SFC with the compiled
+ * blocks. Errors and source maps point into the SFC.
+ */
+export declare function transformSfc(source: string, options?: CompilerOptions | undefined | null): TransformReturn
+
+/**
+ * Compiles a Vue template, e.g. the `<template>` of an SFC, to a `render`
+ * function with the same Vapor backend as JSX.
+ */
+export declare function transformTemplate(source: string, options?: CompilerOptions | undefined | null): TransformReturn
+
+/** What a file uses, e.g. for auto-import plugins and bundle analyzers. */
+export interface TransformMetadata {
+  /** Component tags in source order, including the ones of dynamic components. */
+  components: Array<ComponentUsage>
+  /** Custom directives without the `v-` prefix, e.g. `focus` for `v-focus`. */
+  directives: Array<string>
+  /** Runtime helpers imported by the output, e.g. `template`. */
+  helpers: Array<string>
+  /** Events delegated with `delegateEvents`. */
+  delegates: Array<string>
+  templateCount: number
+  /** Whether an element or component has a `ref`. */
+  hasTemplateRef: boolean
+  /** `__hmrId`s of the components registered for HMR. */
+  hmrIds: Array<string>
+}
+
 export interface TransformReturn {
   code: string
   map?: string
+  /** JSON array of the IR of each JSX root, available when `dumpIr` is enabled. */
+  ir?: string
+  metadata: TransformMetadata
+}
+
+export interface VirtualCode {
+  code: string
+  /**
+   * Parts of `code` copied from the source, e.g. for a language server to
+   * map diagnostics and completions back to the source.
+   */
+  mappings: Array<CodeMapping>
 }
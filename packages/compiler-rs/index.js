@@ -559,4 +559,11 @@ if (!nativeBinding) {
 module.exports = nativeBinding
 module.exports.compile = nativeBinding.compile
 module.exports.ErrorCodes = nativeBinding.ErrorCodes
+module.exports.generateTemplateModule = nativeBinding.generateTemplateModule
+module.exports.generateVirtualCode = nativeBinding.generateVirtualCode
+module.exports.ImportStyle = nativeBinding.ImportStyle
 module.exports.transform = nativeBinding.transform
+module.exports.transformAsync = nativeBinding.transformAsync
+module.exports.transformMany = nativeBinding.transformMany
+module.exports.transformSfc = nativeBinding.transformSfc
+module.exports.transformTemplate = nativeBinding.transformTemplate
@@ -0,0 +1,305 @@
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  fs,
+  io::{self, Write},
+  path::{Path, PathBuf},
+  process::ExitCode,
+};
+
+use compiler_rs::{
+  transform::{ImportStyle, TransformConfig, TransformOptions, transform_file},
+  utils::error::{ERROR_MESSAGES, ErrorCodes},
+};
+use oxc_span::Span;
+
+const HELP: &str = "Compile JSX/TSX files to Vue Vapor.
+
+Usage: compiler-rs [OPTIONS] <INPUT>...
+
+Arguments:
+  <INPUT>...  Files or directories, directories are searched for .jsx/.tsx files
+
+Options:
+  -o, --out-dir <DIR>  Write output files into DIR instead of printing to stdout
+      --source-map     Write a .map file next to each output (requires --out-dir)
+      --emit <KIND>    What to emit: js (default), ir or diagnostics
+      --ssr            Enable SSR support
+      --hmr            Enable HMR support
+      --interop        Only transform JSX within defineVaporComponent to Vapor DOM
+      --vdom           Compile the other JSX to createVNode calls
+      --with-fallback  Compile components to createComponentWithFallback
+      --import-style <STYLE>
+                       How runtime helpers are imported: named (default), namespace or global
+      --per-component-templates
+                       Declare templates next to the components that use them
+      --component-wrapper <NAME>
+                       Function that returns the component it wraps, can be repeated
+      --vapor-factory <NAME>
+                       Function that defines Vapor components, in addition to
+                       defineVaporComponent, can be repeated
+      --vdom-factory <NAME>
+                       Function that defines vDOM components, in addition to
+                       defineComponent, can be repeated
+      --factory-module <MODULE>
+                       Module the factories are imported from, in addition to
+                       vue and vue-jsx-vapor, can be repeated
+  -h, --help           Print help";
+
+#[derive(PartialEq)]
+enum Emit {
+  Js,
+  Ir,
+  Diagnostics,
+}
+
+struct Args {
+  inputs: Vec<PathBuf>,
+  out_dir: Option<PathBuf>,
+  emit: Emit,
+  config: TransformConfig,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+  let mut result = Args {
+    inputs: vec![],
+    out_dir: None,
+    emit: Emit::Js,
+    config: TransformConfig::default(),
+  };
+  while let Some(arg) = args.next() {
+    let (name, inline_value) = match arg.split_once('=') {
+      Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+      _ => (arg, None),
+    };
+    let mut value = |name: &str| {
+      inline_value
+        .clone()
+        .or_else(|| args.next())
+        .ok_or(format!("missing value for `{name}`"))
+    };
+    match name.as_str() {
+      "-h" | "--help" => return Ok(None),
+      "-o" | "--out-dir" => result.out_dir = Some(PathBuf::from(value(&name)?)),
+      "--emit" => {
+        result.emit = match value(&name)?.as_str() {
+          "js" => Emit::Js,
+          "ir" => Emit::Ir,
+          "diagnostics" => Emit::Diagnostics,
+          kind => return Err(format!("unknown emit kind `{kind}`")),
+        }
+      }
+      "--import-style" => {
+        result.config.import_style = match value(&name)?.as_str() {
+          "named" => ImportStyle::Named,
+          "namespace" => ImportStyle::Namespace,
+          "global" => ImportStyle::Global,
+          style => return Err(format!("unknown import style `{style}`")),
+        }
+      }
+      "--source-map" => result.config.source_map = true,
+      "--ssr" => result.config.ssr = true,
+      "--hmr" => result.config.hmr = true,
+      "--interop" => result.config.interop = true,
+      "--vdom" => result.config.vdom = true,
+      "--with-fallback" => result.config.with_fallback = true,
+      "--per-component-templates" => result.config.per_component_templates = true,
+      "--component-wrapper" => result.config.component_wrappers.push(value(&name)?),
+      "--vapor-factory" => result.config.vapor_factories.push(value(&name)?),
+      "--vdom-factory" => result.config.vdom_factories.push(value(&name)?),
+      "--factory-module" => result.config.factory_modules.push(value(&name)?),
+      _ if name.starts_with('-') && name.len() > 1 => {
+        return Err(format!("unknown option `{name}`"));
+      }
+      _ => result.inputs.push(PathBuf::from(name)),
+    }
+  }
+
+  if result.inputs.is_empty() {
+    return Err("no input files".to_string());
+  }
+  if result.out_dir.is_none() {
+    if result.config.source_map {
+      return Err("`--source-map` requires `--out-dir`".to_string());
+    }
+    if result.emit != Emit::Diagnostics
+      && (result.inputs.len() > 1 || result.inputs.iter().any(|input| input.is_dir()))
+    {
+      return Err("compiling multiple files requires `--out-dir`".to_string());
+    }
+  }
+  result.config.dump_ir = result.emit == Emit::Ir;
+  Ok(Some(result))
+}
+
+fn is_jsx_file(path: &Path) -> bool {
+  matches!(
+    path.extension().and_then(|ext| ext.to_str()),
+    Some("jsx" | "tsx")
+  )
+}
+
+/// Collects `(file, path relative to the output directory)` pairs.
+fn collect_files(input: &Path, base: &Path, files: &mut Vec<(PathBuf, PathBuf)>) -> io::Result<()> {
+  if input.is_dir() {
+    let mut entries = fs::read_dir(input)?
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+      if entry.is_dir() || is_jsx_file(&entry) {
+        collect_files(&entry, base, files)?;
+      }
+    }
+  } else {
+    let relative = input.strip_prefix(base).unwrap_or(input);
+    let relative = if relative.as_os_str().is_empty() {
+      PathBuf::from(input.file_name().unwrap_or_default())
+    } else {
+      relative.to_path_buf()
+    };
+    files.push((input.to_path_buf(), relative));
+  }
+  Ok(())
+}
+
+/// Finds the first pair of files that would be written to the same output.
+fn find_duplicate_output(files: &[(PathBuf, PathBuf)]) -> Option<(&Path, &Path, PathBuf)> {
+  let mut outputs = HashMap::new();
+  for (path, relative) in files {
+    let output = relative.with_extension("");
+    if let Some(other) = outputs.insert(output.clone(), path) {
+      return Some((other, path, output));
+    }
+  }
+  None
+}
+
+/// Returns the 1-based line and UTF-16 column of a byte offset.
+fn line_column(source: &str, offset: u32) -> (usize, usize) {
+  let mut offset = (offset as usize).min(source.len());
+  while !source.is_char_boundary(offset) {
+    offset -= 1;
+  }
+  let before = &source[..offset];
+  let line = before.matches('\n').count() + 1;
+  let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+  let column = before[line_start..].encode_utf16().count() + 1;
+  (line, column)
+}
+
+fn report(path: &Path, source: &str, span: Span, message: &str) {
+  let (line, column) = line_column(source, span.start);
+  eprintln!("{}:{line}:{column}: error: {message}", path.display());
+}
+
+/// Compiles a single file, returns whether it had errors.
+fn compile_file(path: &Path, relative: &Path, args: &Args) -> io::Result<bool> {
+  let source = fs::read_to_string(path)?;
+  let filename = path.to_string_lossy();
+  let compiler_errors: RefCell<Vec<(ErrorCodes, Span)>> = RefCell::new(vec![]);
+  let result = transform_file(
+    &source,
+    Some(TransformOptions {
+      on_error: Box::new(|code, span| compiler_errors.borrow_mut().push((code, span))),
+      ..args.config.options(&filename)
+    }),
+  );
+  let compiler_errors = compiler_errors.take();
+  for (code, span) in &compiler_errors {
+    report(
+      path,
+      &source,
+      *span,
+      ERROR_MESSAGES.get(code).copied().unwrap_or_default(),
+    );
+  }
+  // Nothing worth emitting for a file that doesn't parse.
+  if compiler_errors
+    .iter()
+    .any(|(code, _)| *code == ErrorCodes::XSyntaxError)
+  {
+    return Ok(true);
+  }
+
+  let (content, extension) = match args.emit {
+    Emit::Diagnostics => return Ok(!compiler_errors.is_empty()),
+    Emit::Ir => (result.ir.unwrap_or_default(), "ir.json"),
+    Emit::Js => (result.code, "js"),
+  };
+  let Some(out_dir) = &args.out_dir else {
+    io::stdout().write_all(content.as_bytes())?;
+    return Ok(!compiler_errors.is_empty());
+  };
+
+  let output = out_dir.join(relative).with_extension(extension);
+  if let Some(parent) = output.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  if let Some(map) = result.map.filter(|_| args.emit == Emit::Js) {
+    let map_path = output.with_extension("js.map");
+    fs::write(&map_path, map)?;
+    let map_name = map_path.file_name().unwrap_or_default().to_string_lossy();
+    fs::write(
+      &output,
+      format!("{content}//# sourceMappingURL={map_name}\n"),
+    )?;
+  } else {
+    fs::write(&output, content)?;
+  }
+  Ok(!compiler_errors.is_empty())
+}
+
+fn main() -> ExitCode {
+  let args = match parse_args(std::env::args().skip(1)) {
+    Ok(Some(args)) => args,
+    Ok(None) => {
+      println!("{HELP}");
+      return ExitCode::SUCCESS;
+    }
+    Err(error) => {
+      eprintln!("error: {error}\n\n{HELP}");
+      return ExitCode::from(2);
+    }
+  };
+
+  let mut files = vec![];
+  for input in &args.inputs {
+    if !input.exists() {
+      eprintln!("error: `{}` does not exist", input.display());
+      return ExitCode::from(2);
+    }
+    if let Err(error) = collect_files(input, input, &mut files) {
+      eprintln!("error: {}: {error}", input.display());
+      return ExitCode::FAILURE;
+    }
+  }
+
+  if args.out_dir.is_some()
+    && let Some((a, b, output)) = find_duplicate_output(&files)
+  {
+    eprintln!(
+      "error: `{}` and `{}` would both be written to `{}`",
+      a.display(),
+      b.display(),
+      output.display()
+    );
+    return ExitCode::from(2);
+  }
+
+  let mut has_errors = false;
+  for (path, relative) in files {
+    match compile_file(&path, &relative, &args) {
+      Ok(errors) => has_errors |= errors,
+      Err(error) => {
+        eprintln!("error: {}: {error}", path.display());
+        has_errors = true;
+      }
+    }
+  }
+  if has_errors {
+    ExitCode::FAILURE
+  } else {
+    ExitCode::SUCCESS
+  }
+}
@@ -1,23 +1,33 @@
-use std::cell::RefCell;
 use std::{collections::BTreeSet, path::PathBuf};
 
+#[cfg(feature = "napi")]
+use crate::transform::{
+  ImportStyle, TransformConfig,
+  custom_directive::{CustomDirective, CustomDirectiveResult},
+  napi_transform_options,
+};
 use crate::transform::{TransformContext, TransformOptions};
+#[cfg(feature = "napi")]
+use std::collections::HashMap;
 
+#[cfg(feature = "napi")]
 use napi::{
   Env,
   bindgen_prelude::{Function, Object},
 };
+#[cfg(feature = "napi")]
 use napi_derive::napi;
 use oxc_allocator::{Allocator, TakeIn};
 use oxc_ast::ast::{ExpressionStatement, Program, Statement};
 use oxc_codegen::{Codegen, CodegenOptions, CodegenReturn, IndentChar};
 use oxc_parser::{ParseOptions, Parser};
-use oxc_span::{SPAN, SourceType};
+use oxc_span::SPAN;
 
 #[cfg_attr(feature = "napi", napi)]
 pub type Template = (String, bool);
 
-#[cfg_attr(feature = "napi", napi(object))]
+#[cfg(feature = "napi")]
+#[napi(object)]
 #[derive(Default)]
 pub struct CompilerOptions {
   /**
@@ -30,11 +40,22 @@ pub struct CompilerOptions {
    */
   pub is_custom_element: Option<Function<'static, String, bool>>,
   pub on_error: Option<Function<'static, Object<'static>, ()>>,
+  /**
+   * Compile-time transforms for custom directives, keyed by the directive name
+   * without the `v-` prefix. The returned props and events are bound to the element.
+   */
+  pub directive_transforms:
+    Option<HashMap<String, Function<'static, CustomDirective, Option<CustomDirectiveResult>>>>,
   /**
    * Generate source map?
    * @default false
    */
   pub source_map: Option<bool>,
+  /**
+   * Source map of the source when it was generated from another file,
+   * the output map is chained through it to point at the original source.
+   */
+  pub input_source_map: Option<String>,
   /**
    * Filename for source map generation.
    * Also used for self-recursive reference in templates
@@ -44,8 +65,17 @@ pub struct CompilerOptions {
   /**
    * When enabled, JSX within `defineVaporComponent` is transformed to Vapor DOM,
    * while all other JSX is transformed to Virtual DOM.
+   * `@vapor`, `@vdom` or `@jsxMode vapor|vdom` comments before the first statement,
+   * a statement or a function override the mode of the JSX within them. A comment
+   * only applies to the whole file if a blank line or a directive follows it.
    */
   pub interop: Option<bool>,
+  /**
+   * Compiles the JSX in vDOM mode, see `interop`, to `createVNode` calls with
+   * patch flags, block roots and hoisted static vnodes, instead of leaving it to another JSX plugin.
+   * @default false
+   */
+  pub vdom: Option<bool>,
   /**
    * Enabled HMR support.
    * @default false
@@ -56,6 +86,59 @@ pub struct CompilerOptions {
    * @default false
    */
   pub ssr: Option<bool>,
+  /**
+   * Return a JSON dump of the IR of each JSX root, for debugging and tooling.
+   * @default false
+   */
+  pub dump_ir: Option<bool>,
+  /**
+   * Module each runtime helper is imported from, keyed by the helper name.
+   * Defaults to `vue-jsx-vapor` for `setNodes`, `createNodes` and `createComponent(WithFallback)`,
+   * `/__vue-jsx-ssr-register-helper` for `ssrRegisterHelper` and `vue` for the rest.
+   */
+  pub runtime_modules: Option<HashMap<String, String>>,
+  /**
+   * Global variable of each runtime module for the `global` import style,
+   * defaults to the PascalCased module name, e.g. `Vue` for `vue`.
+   */
+  pub runtime_globals: Option<HashMap<String, String>>,
+  /**
+   * How runtime helpers are imported, `require` is used instead of `import` for `.js`/`.cjs` scripts.
+   * @default 'named'
+   */
+  pub import_style: Option<ImportStyle>,
+  /**
+   * Declare templates right before the top-level statement that uses them instead of at the top of the module,
+   * so that unused components are tree-shaken together with their templates.
+   * @default false
+   */
+  pub per_component_templates: Option<bool>,
+  /**
+   * Import templates from this module instead of declaring them in every file, e.g. `virtual:vapor-templates`.
+   * Its content is generated by `generateTemplateModule` from the templates of all compiled files.
+   */
+  pub template_module: Option<String>,
+  /**
+   * Functions that return the component they wrap, e.g. `memoComponent` in `memoComponent(defineVaporComponent(...))`,
+   * so that wrapped components are still registered for HMR and SSR.
+   */
+  pub component_wrappers: Option<Vec<String>>,
+  /**
+   * Functions that define Vapor components.
+   * @default ['defineVaporComponent']
+   */
+  pub vapor_factories: Option<Vec<String>>,
+  /**
+   * Functions that define vDOM components.
+   * @default ['defineComponent']
+   */
+  pub vdom_factories: Option<Vec<String>>,
+  /**
+   * Modules the component factories are imported from, including aliased and
+   * namespace imports. Factories imported from other modules are ignored.
+   * @default ['vue', 'vue-jsx-vapor']
+   */
+  pub factory_modules: Option<Vec<String>>,
 }
 
 #[cfg_attr(feature = "napi", napi(object))]
@@ -65,6 +148,7 @@ pub struct CompileCodegenResult {
   pub templates: Vec<Template>,
   pub delegates: BTreeSet<String>,
   pub code: String,
+  pub ir: Option<String>,
 }
 
 #[cfg(feature = "napi")]
@@ -74,43 +158,12 @@ pub fn _compile(
   source: String,
   options: Option<CompilerOptions>,
 ) -> CompileCodegenResult {
-  use crate::utils::error::ErrorCodes;
-  let options = options.unwrap_or_default();
-  let filename = &options.filename.unwrap_or("index.jsx".to_string());
+  let mut options = options.unwrap_or_default();
+  let filename = options.filename.take().unwrap_or("index.jsx".to_string());
+  let config = TransformConfig::from(&mut options);
   compile(
     &source,
-    Some(TransformOptions {
-      filename,
-      source_type: SourceType::from_path(filename).unwrap(),
-      templates: RefCell::new(vec![]),
-      helpers: RefCell::new(BTreeSet::new()),
-      delegates: RefCell::new(BTreeSet::new()),
-      source_map: options.source_map.unwrap_or(false),
-      with_fallback: options.with_fallback.unwrap_or(false),
-      interop: options.interop.unwrap_or(false),
-      hmr: options.hmr.unwrap_or(false),
-      ssr: options.ssr.unwrap_or(false),
-      is_custom_element: if let Some(is_custom_element) = options.is_custom_element {
-        Box::new(move |tag: String| is_custom_element.call(tag).unwrap())
-          as Box<dyn Fn(String) -> bool>
-      } else {
-        Box::new(|_: String| false) as Box<dyn Fn(String) -> bool>
-      },
-      on_error: if let Some(on_error) = options.on_error {
-        use oxc_span::Span;
-
-        use crate::utils::error::create_compiler_error;
-
-        Box::new(move |code: ErrorCodes, span: Span| {
-          let compiler_error = create_compiler_error(&env, code, span).unwrap();
-          on_error.call(compiler_error).unwrap();
-        }) as Box<dyn Fn(ErrorCodes, Span)>
-      } else {
-        use oxc_span::Span;
-
-        Box::new(|_: ErrorCodes, _: Span| {}) as Box<dyn Fn(ErrorCodes, Span)>
-      },
-    }),
+    Some(napi_transform_options(env, &config, options, &filename)),
   )
 }
 
@@ -123,8 +176,15 @@ pub fn compile(source: &str, options: Option<TransformOptions>) -> CompileCodege
       ..ParseOptions::default()
     })
     .parse();
-  let Statement::ExpressionStatement(stmt) = root.program.body.get_mut(0).unwrap() else {
-    panic!("Expected ExpressionStatement");
+  let Some(Statement::ExpressionStatement(stmt)) = root.program.body.get_mut(0) else {
+    // Nothing to compile without a leading JSX expression.
+    return CompileCodegenResult {
+      helpers: BTreeSet::new(),
+      templates: vec![],
+      delegates: BTreeSet::new(),
+      code: String::new(),
+      ir: options.take_ir(),
+    };
   };
 
   let filename = options.filename;
@@ -169,5 +229,6 @@ pub fn compile(source: &str, options: Option<TransformOptions>) -> CompileCodege
     delegates: context.options.delegates.take(),
     helpers: context.options.helpers.take(),
     templates: context.options.templates.take(),
+    ir: context.options.take_ir(),
   }
 }
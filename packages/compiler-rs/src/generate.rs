@@ -19,6 +19,7 @@ pub mod v_show;
 use std::{
   cell::RefCell,
   collections::{HashMap, HashSet},
+  fmt::Display,
   mem,
 };
 
@@ -63,7 +64,23 @@ impl<'a> CodegenContext<'a> {
 
   pub fn helper(&self, name: &str) -> String {
     self.options.helpers.borrow_mut().insert(name.to_string());
-    format!("_{name}")
+    self.unique_name(&format!("_{name}"))
+  }
+
+  /// Returns the collision-free name of a generated module-level binding.
+  pub fn unique_name(&self, name: &str) -> String {
+    self.transform_cotext.names.borrow_mut().get(name)
+  }
+
+  /// Returns the collision-free name of a numbered local, e.g. `n0` for `("n", 0)`.
+  pub fn local(&self, prefix: &str, id: impl Display) -> String {
+    let prefix = self.transform_cotext.locals.borrow_mut().prefix(prefix);
+    format!("{prefix}{id}")
+  }
+
+  /// Returns the collision-free name of a local, e.g. `_dir`.
+  pub fn local_name(&self, name: &str) -> String {
+    self.transform_cotext.locals.borrow_mut().get(name)
   }
 
   pub fn with_id(
@@ -128,7 +145,10 @@ impl<'a> CodegenContext<'a> {
             SPAN,
             VariableDeclarationKind::Const,
             ast.binding_pattern(
-              ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom("_setTemplateRef")),
+              ast.binding_pattern_kind_binding_identifier(
+                SPAN,
+                ast.atom(&self.unique_name("_setTemplateRef")),
+              ),
               NONE,
               false,
             ),
@@ -157,7 +177,7 @@ impl<'a> CodegenContext<'a> {
     if !self.options.delegates.borrow().is_empty() {
       self.helper("delegateEvents");
     }
-    if !&self.options.templates.borrow().is_empty() {
+    if self.options.template_module.is_none() && !&self.options.templates.borrow().is_empty() {
       self.helper("template");
     }
 
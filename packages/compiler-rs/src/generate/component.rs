@@ -1,6 +1,5 @@
 use std::mem;
 
-use napi::bindgen_prelude::Either3;
 use oxc_allocator::CloneIn;
 use oxc_ast::NONE;
 use oxc_ast::ast::BinaryOperator;
@@ -142,7 +141,7 @@ pub fn gen_create_component<'a>(
         VariableDeclarationKind::Const,
         ast.binding_pattern(
           BindingPatternKind::BindingIdentifier(
-            ast.alloc_binding_identifier(SPAN, ast.atom(&format!("n{id}"))),
+            ast.alloc_binding_identifier(SPAN, ast.atom(&context.local("n", id))),
           ),
           NONE,
           false,
@@ -168,9 +167,7 @@ pub fn gen_create_component<'a>(
       false,
     ),
   ));
-  if let Some(directive_statement) = gen_directives_for_element(id, context, context_block) {
-    statements.push(directive_statement);
-  }
+  statements.extend(gen_directives_for_element(id, context, context_block));
 }
 
 fn gen_raw_props<'a>(
@@ -178,12 +175,12 @@ fn gen_raw_props<'a>(
   context: &'a CodegenContext<'a>,
 ) -> Option<Expression<'a>> {
   let props_len = props.len();
-  if let Either3::A(static_props) = &props[0] {
+  if let IRProps::Static(static_props) = &props[0] {
     if static_props.is_empty() && props_len == 1 {
       return None;
     }
     let static_props = props.remove(0);
-    if let Either3::A(static_props) = static_props {
+    if let IRProps::Static(static_props) = static_props {
       Some(gen_static_props(
         static_props,
         context,
@@ -237,16 +234,16 @@ fn gen_dynamic_props<'a>(
   let mut frags = ast.vec();
   for p in props {
     let mut expr = None;
-    if let Either3::A(p) = p {
+    if let IRProps::Static(p) = p {
       if !p.is_empty() {
         frags.push(gen_static_props(p, context, None))
       }
       continue;
-    } else if let Either3::B(p) = p {
+    } else if let IRProps::Dynamic(p) = p {
       let mut properties = ast.vec();
       gen_prop(&mut properties, p, context, false);
       expr = Some(ast.expression_object(SPAN, properties));
-    } else if let Either3::C(p) = p {
+    } else if let IRProps::Expression(p) = p {
       let expression = gen_expression(p.value, context, None, None);
       expr = if p.handler.unwrap_or_default() {
         Some(ast.expression_call(
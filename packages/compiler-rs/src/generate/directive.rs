@@ -1,11 +1,9 @@
-use napi::bindgen_prelude::Either16;
 use oxc_ast::AstBuilder;
 use oxc_ast::NONE;
 use oxc_ast::ast::Statement;
 use oxc_ast::ast::{
-  Argument, ArrayExpressionElement, FormalParameterKind, ObjectExpression, PropertyKind,
+  ArrayExpressionElement, Expression, FormalParameterKind, ObjectExpression, PropertyKind,
 };
-use oxc_span::GetSpan;
 use oxc_span::SPAN;
 
 use crate::generate::CodegenContext;
@@ -14,6 +12,7 @@ use crate::generate::v_model::gen_v_model;
 use crate::generate::v_show::gen_v_show;
 use crate::ir::index::BlockIRNode;
 use crate::ir::index::DirectiveIRNode;
+use crate::ir::index::OperationNode;
 use crate::utils::check::is_simple_identifier;
 use crate::utils::text::to_valid_asset_id;
 
@@ -22,16 +21,18 @@ pub fn gen_builtin_directive<'a>(
   context: &'a CodegenContext<'a>,
 ) -> Option<Statement<'a>> {
   match oper.name.as_str() {
-    "show" => Some(gen_v_show(oper, context)),
-    "model" => Some(gen_v_model(oper, context)),
+    "show" => gen_v_show(oper, context),
+    "model" => gen_v_model(oper, context),
     _ => None,
   }
 }
 
 /**
- * user directives via `withVaporDirectives`
- * TODO the compiler side is implemented but no runtime support yet
- * it was removed due to perf issues
+ * user directives via `withVaporDirectives`:
+ * `_withVaporDirectives(n0, [[vFoo, () => value, arg, modifiers]])`
+ * each directive is called with the element (or the root element of a
+ * component, resolved by the runtime), a getter of its value, the argument and
+ * the modifiers, and may return a cleanup that runs when the scope is disposed.
  */
 pub fn gen_directives_for_element<'a>(
   id: i32,
@@ -39,29 +40,24 @@ pub fn gen_directives_for_element<'a>(
   context_block: &mut BlockIRNode<'a>,
 ) -> Option<Statement<'a>> {
   let ast = &context.ast;
-  let mut element = String::new();
   let mut directive_items = ast.vec();
   for item in &mut context_block.operation {
-    if let Either16::M(item) = item
+    if let OperationNode::Directive(item) = item
       && item.element == id
       && !item.builtin.unwrap_or(false)
     {
-      if element.is_empty() {
-        element = item.element.to_string();
-      }
       let name = &item.name;
-      let asset = item.asset;
-      let directive_var = ast.alloc_identifier_reference(
+      let directive = ast.expression_identifier(
         SPAN,
-        if asset.unwrap_or(false) {
+        if item.asset.unwrap_or(false) {
           ast.atom(&to_valid_asset_id(name, "directive"))
         } else {
           ast.atom(name)
         },
       );
-      let value = if let Some(exp) = item.dir.exp.take() {
+      let value = item.dir.exp.take().map(|exp| {
         let expression = gen_expression(exp, context, None, None);
-        Some(ast.alloc_arrow_function_expression(
+        ast.expression_arrow_function(
           SPAN,
           true,
           false,
@@ -76,54 +72,35 @@ pub fn gen_directives_for_element<'a>(
           ast.function_body(
             SPAN,
             ast.vec(),
-            ast.vec1(ast.statement_expression(expression.span(), expression)),
+            ast.vec1(ast.statement_expression(SPAN, expression)),
           ),
-        ))
-      } else {
-        None
-      };
+        )
+      });
       let argument = item
         .dir
         .arg
         .take()
         .map(|arg| gen_expression(arg, context, None, None));
       let modifiers = if !item.dir.modifiers.is_empty() {
-        Some(gen_directive_modifiers(
+        Some(Expression::ObjectExpression(gen_directive_modifiers(
           item.dir.modifiers.drain(..).map(|m| m.content).collect(),
           ast,
-        ))
+        )))
       } else {
         None
       };
 
-      directive_items.push(ArrayExpressionElement::ArrayExpression(
-        ast.alloc_array_expression(
+      let mut elements = vec![Some(directive), value, argument, modifiers];
+      while elements.last().is_some_and(Option::is_none) {
+        elements.pop();
+      }
+      directive_items.push(ArrayExpressionElement::from(
+        ast.expression_array(
           SPAN,
           ast.vec_from_iter(
-            [
-              Some(ArrayExpressionElement::Identifier(directive_var)),
-              if let Some(value) = value {
-                Some(ArrayExpressionElement::ArrowFunctionExpression(value))
-              } else if argument.is_some() || modifiers.is_some() {
-                Some(ArrayExpressionElement::Identifier(
-                  ast.alloc_identifier_reference(SPAN, "void 0"),
-                ))
-              } else {
-                None
-              },
-              if let Some(argument) = argument {
-                Some(argument.into())
-              } else if modifiers.is_some() {
-                Some(ArrayExpressionElement::Identifier(
-                  ast.alloc_identifier_reference(SPAN, "void 0"),
-                ))
-              } else {
-                None
-              },
-              modifiers.map(ArrayExpressionElement::ObjectExpression),
-            ]
-            .into_iter()
-            .flatten(),
+            elements
+              .into_iter()
+              .map(|element| element.unwrap_or_else(|| ast.void_0(SPAN)).into()),
           ),
         ),
       ));
@@ -132,22 +109,23 @@ pub fn gen_directives_for_element<'a>(
   if directive_items.is_empty() {
     return None;
   }
-  let directives = ast.alloc_array_expression(SPAN, directive_items);
-  Some(ast.statement_expression(
-    SPAN,
-    ast.expression_call(
+  Some(
+    ast.statement_expression(
       SPAN,
-      ast.expression_identifier(SPAN, ast.atom(&context.helper("withVaporDirectives"))),
-      NONE,
-      ast.vec_from_array([
-        Argument::Identifier(
-          ast.alloc_identifier_reference(SPAN, ast.atom(&format!("n{}", element))),
-        ),
-        Argument::ArrayExpression(directives),
-      ]),
-      false,
+      ast.expression_call(
+        SPAN,
+        ast.expression_identifier(SPAN, ast.atom(&context.helper("withVaporDirectives"))),
+        NONE,
+        ast.vec_from_array([
+          ast
+            .expression_identifier(SPAN, ast.atom(&context.local("n", id)))
+            .into(),
+          ast.expression_array(SPAN, directive_items).into(),
+        ]),
+        false,
+      ),
     ),
-  ))
+  )
 }
 
 pub fn gen_directive_modifiers<'a>(
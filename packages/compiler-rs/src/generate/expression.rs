@@ -7,7 +7,9 @@ use oxc_span::{GetSpan, SPAN, Span};
 use oxc_traverse::Ancestor;
 
 use crate::{
-  generate::CodegenContext, ir::index::SimpleExpressionNode, utils::walk::WalkIdentifiers,
+  generate::CodegenContext,
+  ir::index::SimpleExpressionNode,
+  utils::{error::ErrorCodes, expression::into_assignment_target, walk::WalkIdentifiers},
 };
 
 pub fn gen_expression<'a>(
@@ -26,6 +28,11 @@ pub fn gen_expression<'a>(
     return ast.expression_string_literal(loc, ast.atom(content), None);
   }
 
+  // Keep the output valid when the expression is missing, e.g. `v-html={}`.
+  if node.is_empty() {
+    return ast.void_0(loc);
+  }
+
   if node.is_constant_expression() {
     return if let Some(assignment) = assignment {
       ast.expression_assignment(
@@ -67,18 +74,18 @@ pub fn gen_expression<'a>(
   };
   if let Some(assignment) = assignment {
     let span = expression.span();
-    expression = context.ast.expression_assignment(
-      span,
-      AssignmentOperator::Assign,
-      match expression {
-        Expression::Identifier(id) => AssignmentTarget::AssignmentTargetIdentifier(id),
-        Expression::StaticMemberExpression(id) => AssignmentTarget::StaticMemberExpression(id),
-        Expression::ComputedMemberExpression(id) => AssignmentTarget::ComputedMemberExpression(id),
-        Expression::PrivateFieldExpression(id) => AssignmentTarget::PrivateFieldExpression(id),
-        _ => unimplemented!(),
-      },
-      assignment,
-    );
+    expression = match into_assignment_target(expression) {
+      Ok(target) => context.ast.expression_assignment(
+        span,
+        AssignmentOperator::Assign,
+        target.into(),
+        assignment,
+      ),
+      Err(expression) => {
+        context.options.on_error.as_ref()(ErrorCodes::VModelMalformedExpression, span);
+        expression
+      }
+    };
   }
 
   if need_wrap {
@@ -1,4 +1,3 @@
-use napi::bindgen_prelude::Either16;
 use oxc_ast::NONE;
 use oxc_ast::ast::{Argument, NumberBase, Statement};
 use oxc_span::SPAN;
@@ -33,7 +32,7 @@ pub fn gen_operations<'a>(
   let event_opers = opers
     .iter()
     .filter_map(|op| {
-      if let Either16::H(op) = op {
+      if let OperationNode::SetEvent(op) = op {
         Some(op.clone())
       } else {
         None
@@ -60,17 +59,17 @@ pub fn gen_operation_with_insertion_state<'a>(
   event_opers: &Vec<SetEventIRNode>,
 ) {
   match &oper {
-    Either16::A(if_ir_node) => {
+    OperationNode::If(if_ir_node) => {
       if let Some(parent) = if_ir_node.parent {
         statements.push(gen_insertion_state(parent, if_ir_node.anchor, context))
       }
     }
-    Either16::B(for_ir_node) => {
+    OperationNode::For(for_ir_node) => {
       if let Some(parent) = for_ir_node.parent {
         statements.push(gen_insertion_state(parent, for_ir_node.anchor, context))
       }
     }
-    Either16::N(create_component_ir_node) => {
+    OperationNode::CreateComponent(create_component_ir_node) => {
       if let Some(parent) = create_component_ir_node.parent {
         statements.push(gen_insertion_state(
           parent,
@@ -93,26 +92,28 @@ pub fn gen_operation<'a>(
   event_opers: &Vec<SetEventIRNode>,
 ) {
   match oper {
-    Either16::A(oper) => statements.push(gen_if(oper, context, context_block, false)),
-    Either16::B(oper) => gen_for(statements, oper, context, context_block),
-    Either16::C(oper) => statements.push(gen_set_text(oper, context)),
-    Either16::D(oper) => statements.push(gen_set_prop(oper, context)),
-    Either16::E(oper) => statements.push(gen_dynamic_props(oper, context)),
-    Either16::F(oper) => statements.push(gen_set_dynamic_events(oper, context)),
-    Either16::G(oper) => statements.push(gen_set_nodes(oper, context)),
-    Either16::H(oper) => statements.push(gen_set_event(oper, context, event_opers)),
-    Either16::I(oper) => statements.push(gen_set_html(oper, context)),
-    Either16::J(oper) => statements.push(gen_set_template_ref(oper, context)),
-    Either16::K(oper) => statements.push(gen_create_nodes(oper, context)),
-    Either16::L(oper) => statements.push(gen_insert_node(oper, context)),
-    Either16::M(oper) => {
+    OperationNode::If(oper) => statements.push(gen_if(oper, context, context_block, false)),
+    OperationNode::For(oper) => gen_for(statements, oper, context, context_block),
+    OperationNode::SetText(oper) => statements.push(gen_set_text(oper, context)),
+    OperationNode::SetProp(oper) => statements.push(gen_set_prop(oper, context)),
+    OperationNode::SetDynamicProps(oper) => statements.push(gen_dynamic_props(oper, context)),
+    OperationNode::SetDynamicEvents(oper) => statements.push(gen_set_dynamic_events(oper, context)),
+    OperationNode::SetNodes(oper) => statements.push(gen_set_nodes(oper, context)),
+    OperationNode::SetEvent(oper) => statements.push(gen_set_event(oper, context, event_opers)),
+    OperationNode::SetHtml(oper) => statements.push(gen_set_html(oper, context)),
+    OperationNode::SetTemplateRef(oper) => statements.push(gen_set_template_ref(oper, context)),
+    OperationNode::CreateNodes(oper) => statements.push(gen_create_nodes(oper, context)),
+    OperationNode::InsertNode(oper) => statements.push(gen_insert_node(oper, context)),
+    OperationNode::Directive(oper) => {
       if let Some(statement) = gen_builtin_directive(oper, context) {
         statements.push(statement)
       }
     }
-    Either16::N(oper) => gen_create_component(statements, oper, context, context_block),
-    Either16::O(oper) => statements.push(gen_declare_old_ref(oper, context)),
-    Either16::P(oper) => statements.push(gen_get_text_child(oper, context)),
+    OperationNode::CreateComponent(oper) => {
+      gen_create_component(statements, oper, context, context_block)
+    }
+    OperationNode::DeclareOldRef(oper) => statements.push(gen_declare_old_ref(oper, context)),
+    OperationNode::GetTextChild(oper) => statements.push(gen_get_text_child(oper, context)),
   }
 }
 
@@ -132,7 +133,7 @@ pub fn gen_insertion_state<'a>(
         [
           Some(Argument::Identifier(ast.alloc_identifier_reference(
             SPAN,
-            ast.atom(&format!("n{}", parent)),
+            ast.atom(&context.local("n", parent)),
           ))),
           if let Some(anchor) = anchor {
             if anchor == -1 {
@@ -146,7 +147,7 @@ pub fn gen_insertion_state<'a>(
             } else {
               Some(Argument::Identifier(ast.alloc_identifier_reference(
                 SPAN,
-                ast.atom(&format!("n{anchor}")),
+                ast.atom(&context.local("n", anchor)),
               )))
             }
           } else {
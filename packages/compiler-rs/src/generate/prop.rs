@@ -1,4 +1,3 @@
-use napi::bindgen_prelude::Either3;
 use oxc_ast::NONE;
 use oxc_ast::ast::BinaryOperator;
 use oxc_ast::ast::Expression;
@@ -10,6 +9,7 @@ use oxc_span::SPAN;
 use crate::generate::CodegenContext;
 use crate::generate::expression::gen_expression;
 use crate::ir::component::IRProp;
+use crate::ir::component::IRProps;
 use crate::ir::index::SetDynamicPropsIRNode;
 use crate::ir::index::SetPropIRNode;
 use crate::ir::index::SimpleExpressionNode;
@@ -17,49 +17,17 @@ use crate::utils::check::is_simple_identifier;
 use crate::utils::check::is_svg_tag;
 
 pub struct HelperConfig {
-  name: String,
+  name: &'static str,
   need_key: bool,
 }
 
-fn helpers(name: &str) -> HelperConfig {
-  match name {
-    "setText" => HelperConfig {
-      name: "setText".to_string(),
-      need_key: false,
-    },
-    "setHtml" => HelperConfig {
-      name: "setHtml".to_string(),
-      need_key: false,
-    },
-    "setClass" => HelperConfig {
-      name: "setClass".to_string(),
-      need_key: false,
-    },
-    "setStyle" => HelperConfig {
-      name: "setStyle".to_string(),
-      need_key: false,
-    },
-    "setValue" => HelperConfig {
-      name: "setValue".to_string(),
-      need_key: false,
-    },
-    "setAttr" => HelperConfig {
-      name: "setAttr".to_string(),
-      need_key: true,
-    },
-    "setProp" => HelperConfig {
-      name: "setProp".to_string(),
-      need_key: true,
-    },
-    "setDOMProp" => HelperConfig {
-      name: "setDOMProp".to_string(),
-      need_key: true,
-    },
-    "setDynamicProps" => HelperConfig {
-      name: "setDynamicProps".to_string(),
-      need_key: true,
-    },
-    _ => panic!("Unsupported helper name"),
+fn helpers(name: &'static str) -> HelperConfig {
+  HelperConfig {
+    name,
+    need_key: matches!(
+      name,
+      "setAttr" | "setProp" | "setDOMProp" | "setDynamicProps"
+    ),
   }
 }
 
@@ -79,7 +47,7 @@ pub fn gen_set_prop<'a>(oper: SetPropIRNode<'a>, context: &'a CodegenContext<'a>
   let mut arguments = ast.vec();
   arguments.push(
     ast
-      .expression_identifier(SPAN, ast.atom(&format!("n{}", oper.element)))
+      .expression_identifier(SPAN, ast.atom(&context.local("n", oper.element)))
       .into(),
   );
   let resolved_helper = get_runtime_helper(&tag, &key.content, modifier);
@@ -92,10 +60,7 @@ pub fn gen_set_prop<'a>(oper: SetPropIRNode<'a>, context: &'a CodegenContext<'a>
     SPAN,
     ast.expression_call(
       SPAN,
-      ast.expression_identifier(
-        SPAN,
-        ast.atom(&context.helper(resolved_helper.name.as_str())),
-      ),
+      ast.expression_identifier(SPAN, ast.atom(&context.helper(resolved_helper.name))),
       NONE,
       arguments,
       false,
@@ -209,16 +174,16 @@ pub fn gen_dynamic_props<'a>(
   let ast = &context.ast;
   let values = oper.props.into_iter().map(|props| {
     match props {
-      Either3::A(props) => gen_literal_object_props(props, context).into(),
-      Either3::B(prop) => gen_literal_object_props(vec![prop], context).into(),
-      Either3::C(props) => gen_expression(props.value, context, None, None).into(), // {...obj}
+      IRProps::Static(props) => gen_literal_object_props(props, context).into(),
+      IRProps::Dynamic(prop) => gen_literal_object_props(vec![prop], context).into(),
+      IRProps::Expression(props) => gen_expression(props.value, context, None, None).into(), // {...obj}
     }
   });
 
   let mut arguments = ast.vec();
   arguments.push(
     ast
-      .expression_identifier(SPAN, ast.atom(&format!("n{}", oper.element)))
+      .expression_identifier(SPAN, ast.atom(&context.local("n", oper.element)))
       .into(),
   );
   arguments.push(ast.expression_array(SPAN, ast.vec_from_iter(values)).into());
@@ -1,7 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
 use indexmap::IndexMap;
-use napi::bindgen_prelude::{Either, Either4};
 use oxc_allocator::TakeIn;
 use oxc_ast::{
   NONE,
@@ -9,6 +8,7 @@ use oxc_ast::{
 };
 use oxc_span::SPAN;
 
+use crate::utils::either::Either;
 use crate::{
   generate::{CodegenContext, block::gen_block, expression::gen_expression},
   ir::{
@@ -26,10 +26,10 @@ pub fn gen_raw_slots<'a>(
   if slots.is_empty() {
     return None;
   }
-  if let Either4::A(_) = &slots[0] {
+  if let IRSlots::Static(_) = &slots[0] {
     // single static slot
     let static_slots = slots.remove(0);
-    if let Either4::A(static_slots) = static_slots {
+    if let IRSlots::Static(static_slots) = static_slots {
       Some(gen_static_slots(
         static_slots.slots,
         context,
@@ -99,16 +99,16 @@ fn gen_dynamic_slots<'a>(
   let context_block = context_block as *mut BlockIRNode;
   for slot in slots {
     elements.push(match slot {
-      Either4::A(slot) => {
+      IRSlots::Static(slot) => {
         gen_static_slots(slot.slots, context, unsafe { &mut *context_block }, None).into()
       }
-      Either4::B(slot) => {
+      IRSlots::Dynamic(slot) => {
         gen_dynamic_slot(slot, context, unsafe { &mut *context_block }, true).into()
       }
-      Either4::C(slot) => {
+      IRSlots::Conditional(slot) => {
         gen_conditional_slot(slot, context, unsafe { &mut *context_block }, true).into()
       }
-      Either4::D(slot) => gen_expression(slot.slots, context, None, None).into(),
+      IRSlots::Expression(slot) => gen_expression(slot.slots, context, None, None).into(),
     })
   }
   ast.expression_array(SPAN, elements)
@@ -263,7 +263,7 @@ fn gen_loop_slot<'a>(
                     ast.vec(),
                     ast.binding_pattern(
                       BindingPatternKind::BindingIdentifier(
-                        ast.alloc_binding_identifier(SPAN, ast.atom("_")),
+                        ast.alloc_binding_identifier(SPAN, ast.atom(&context.local_name("_"))),
                       ),
                       NONE,
                       false,
@@ -296,7 +296,7 @@ fn gen_loop_slot<'a>(
                     ast.vec(),
                     ast.binding_pattern(
                       BindingPatternKind::BindingIdentifier(
-                        ast.alloc_binding_identifier(SPAN, ast.atom("__")),
+                        ast.alloc_binding_identifier(SPAN, ast.atom(&context.local_name("__"))),
                       ),
                       NONE,
                       false,
@@ -419,7 +419,7 @@ fn gen_slot_block_with_props<'a>(
     {
       is_destructure_assignment = true;
       let scope = context.enter_scope();
-      props_name = format!("_slotProps{}", scope.0);
+      props_name = context.local("_slotProps", scope.0);
       if let Some(ast) = props.ast {
         WalkIdentifiers::new(
           context,
@@ -41,14 +41,17 @@ pub fn gen_self<'a>(
           VariableDeclarationKind::Const,
           ast.binding_pattern(
             BindingPatternKind::BindingIdentifier(
-              ast.alloc_binding_identifier(SPAN, ast.atom(&format!("n{id}"))),
+              ast.alloc_binding_identifier(SPAN, ast.atom(&context.local("n", id))),
             ),
             NONE,
             false,
           ),
           Some(ast.expression_call(
             SPAN,
-            ast.expression_identifier(SPAN, ast.atom(&format!("t{template}"))),
+            ast.expression_identifier(
+              SPAN,
+              ast.atom(&context.unique_name(&format!("t{template}"))),
+            ),
             NONE,
             ast.vec(),
             false,
@@ -58,9 +61,7 @@ pub fn gen_self<'a>(
         false,
       ),
     ));
-    if let Some(directives) = gen_directives_for_element(id, context, context_block) {
-      statements.push(directives)
-    }
+    statements.extend(gen_directives_for_element(id, context, context_block));
   }
 
   if let Some(operation) = operation {
@@ -80,7 +81,7 @@ pub fn gen_self<'a>(
     context,
     context_block,
     statements.len(),
-    format!("n{}", id.unwrap_or(0)),
+    context.local("n", id.unwrap_or(0)),
   );
 }
 
@@ -124,11 +125,11 @@ fn gen_children<'a>(
     // p for "placeholder" variables that are meant for possible reuse by
     // other access paths
     let variable = if let Some(id) = id {
-      format!("n{id}")
+      context.local("n", id)
     } else {
       let temp_id = context_block.temp_id;
       context_block.temp_id = temp_id + 1;
-      format!("p{}", temp_id)
+      context.local("p", temp_id)
     };
 
     let expression_call = if let Some(prev) = prev {
@@ -184,7 +185,7 @@ fn gen_children<'a>(
           ))),
           false,
         )
-        // gen_call(Either::A(context.helper("next")), vec![Either4::D(init)])
+        // gen_call(Either::A(context.helper("next")), vec![IRSlots::Expression(init)])
       } else if element_index > 1 {
         ast.expression_call(
           SPAN,
@@ -239,12 +240,11 @@ fn gen_children<'a>(
       gen_self(statements, child, context, unsafe { &mut *_context_block });
     }
 
-    if let Some(id) = id
-      && let Some(directives) =
-        gen_directives_for_element(id, context, unsafe { &mut *_context_block })
-    {
-      statements.push(directives)
-    };
+    if let Some(id) = id {
+      statements.extend(gen_directives_for_element(id, context, unsafe {
+        &mut *_context_block
+      }));
+    }
 
     prev = Some((variable.clone(), element_index));
     gen_children(
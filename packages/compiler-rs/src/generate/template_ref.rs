@@ -25,7 +25,7 @@ pub fn gen_set_template_ref<'a>(
   let mut arguments = ast.vec();
   arguments.push(
     ast
-      .expression_identifier(SPAN, ast.atom(&format!("n{element}")))
+      .expression_identifier(SPAN, ast.atom(&context.local("n", element)))
       .into(),
   );
   arguments.push(gen_expression(value, context, None, None).into());
@@ -33,7 +33,7 @@ pub fn gen_set_template_ref<'a>(
   if effect {
     arguments.push(
       ast
-        .expression_identifier(SPAN, ast.atom(&format!("r{element}")))
+        .expression_identifier(SPAN, ast.atom(&context.local("r", element)))
         .into(),
     );
   } else if ref_for {
@@ -45,7 +45,8 @@ pub fn gen_set_template_ref<'a>(
 
   let right = ast.expression_call(
     SPAN,
-    ast.expression_identifier(SPAN, ast.atom("_setTemplateRef")), // will be generated in root scope
+    // will be generated in root scope
+    ast.expression_identifier(SPAN, ast.atom(&context.unique_name("_setTemplateRef"))),
     NONE,
     arguments,
     false,
@@ -57,7 +58,7 @@ pub fn gen_set_template_ref<'a>(
         SPAN,
         AssignmentOperator::Assign,
         AssignmentTarget::AssignmentTargetIdentifier(
-          ast.alloc_identifier_reference(SPAN, ast.atom(&format!("r{element}"))),
+          ast.alloc_identifier_reference(SPAN, ast.atom(&context.local("r", element))),
         ),
         right,
       ),
@@ -80,7 +81,7 @@ pub fn gen_declare_old_ref<'a>(
       VariableDeclarationKind::Let,
       ast.binding_pattern(
         BindingPatternKind::BindingIdentifier(
-          ast.alloc_binding_identifier(SPAN, ast.atom(&format!("r{}", oper.id))),
+          ast.alloc_binding_identifier(SPAN, ast.atom(&context.local("r", oper.id))),
         ),
         NONE,
         false,
@@ -27,11 +27,7 @@ pub fn gen_set_text<'a>(oper: SetTextIRNode<'a>, context: &'a CodegenContext<'a>
     ast
       .expression_identifier(
         SPAN,
-        ast.atom(&format!(
-          "{}{}",
-          if generated.unwrap_or(false) { "x" } else { "n" },
-          element
-        )),
+        ast.atom(&context.local(if generated.unwrap_or(false) { "x" } else { "n" }, element)),
       )
       .into(),
   );
@@ -64,7 +60,7 @@ pub fn gen_get_text_child<'a>(
           VariableDeclarationKind::Const,
           ast.binding_pattern(
             BindingPatternKind::BindingIdentifier(
-              ast.alloc_binding_identifier(SPAN, ast.atom(&format!("x{}", oper.parent))),
+              ast.alloc_binding_identifier(SPAN, ast.atom(&context.local("x", oper.parent))),
             ),
             NONE,
             false,
@@ -76,7 +72,7 @@ pub fn gen_get_text_child<'a>(
               NONE,
               ast.vec1(
                 ast
-                  .expression_identifier(SPAN, ast.atom(&format!("n{}", oper.parent)))
+                  .expression_identifier(SPAN, ast.atom(&context.local("n", oper.parent)))
                   .into(),
               ),
               false,
@@ -109,15 +105,7 @@ pub fn gen_set_nodes<'a>(
     ast
       .expression_identifier(
         SPAN,
-        ast.atom(&format!(
-          "{}{}",
-          if generated.unwrap_or(false) {
-            "x".to_string()
-          } else {
-            "n".to_string()
-          },
-          element
-        )),
+        ast.atom(&context.local(if generated.unwrap_or(false) { "x" } else { "n" }, element)),
       )
       .into(),
   );
@@ -155,7 +143,7 @@ pub fn gen_create_nodes<'a>(
       SPAN,
       VariableDeclarationKind::Const,
       ast.binding_pattern(
-        ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom(&format!("n{id}"))),
+        ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom(&context.local("n", id))),
         NONE,
         false,
       ),
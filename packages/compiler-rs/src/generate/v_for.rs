@@ -1,6 +1,5 @@
 use std::{collections::HashMap, ops::Deref};
 
-use napi::bindgen_prelude::Either16;
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::{
   NONE,
@@ -17,6 +16,7 @@ use oxc_traverse::{
   ancestor::{ArrayExpressionWithoutElements, ObjectExpressionWithoutProperties},
 };
 
+use crate::ir::index::OperationNode;
 use crate::{
   generate::{
     CodegenContext, block::gen_block_content, expression::gen_expression, operation::gen_operation,
@@ -104,7 +104,7 @@ pub fn gen_for<'a>(
 
   let (depth, exit_scope) = context.enter_scope();
   let mut id_map: HashMap<String, Option<Expression>> = HashMap::new();
-  let item_var = format!("_for_item{depth}");
+  let item_var = context.local("_for_item", depth);
   id_map.insert(item_var.clone(), None);
 
   let _id_map = &mut id_map as *mut HashMap<String, Option<Expression>>;
@@ -159,22 +159,21 @@ pub fn gen_for<'a>(
                   as *const *const ArrayExpression);
                 &(*parent).elements
               };
-              let index = elements
-                .iter()
-                .position(|element| {
-                  if let Some(child) = child {
-                    let span = match child {
-                      Ancestor::SpreadElementArgument(e) => e.span(),
-                      Ancestor::ArrayExpressionElements(e) => e.span(),
-                      Ancestor::ObjectExpressionProperties(e) => e.span(),
-                      _ => unimplemented!(),
-                    };
-                    element.span().eq(span)
-                  } else {
-                    element.span().eq(&id.span())
-                  }
-                })
-                .unwrap();
+              let Some(index) = elements.iter().position(|element| {
+                if let Some(child) = child {
+                  let span = match child {
+                    Ancestor::SpreadElementArgument(e) => e.span(),
+                    Ancestor::ArrayExpressionElements(e) => e.span(),
+                    Ancestor::ObjectExpressionProperties(e) => e.span(),
+                    _ => return false,
+                  };
+                  element.span().eq(span)
+                } else {
+                  element.span().eq(&id.span())
+                }
+              }) else {
+                continue;
+              };
               if child_is_spread {
                 path = ast.expression_call(
                   SPAN,
@@ -219,20 +218,17 @@ pub fn gen_for<'a>(
                     .expression_array(
                       SPAN,
                       ast.vec_from_iter(properties.iter().filter_map(|p| {
-                        if let ObjectPropertyKind::ObjectProperty(p) = p {
-                          Some(if let PropertyKey::StringLiteral(key) = &p.key {
-                            ast.expression_string_literal(SPAN, key.value, None).into()
-                          } else {
+                        let ObjectPropertyKind::ObjectProperty(p) = p else {
+                          return None;
+                        };
+                        if let PropertyKey::StringLiteral(key) = &p.key {
+                          Some(ast.expression_string_literal(SPAN, key.value, None).into())
+                        } else {
+                          p.key.name().map(|name| {
                             ast
-                              .expression_string_literal(
-                                SPAN,
-                                ast.atom(&p.key.name().unwrap()),
-                                None,
-                              )
+                              .expression_string_literal(SPAN, ast.atom(&name), None)
                               .into()
                           })
-                        } else {
-                          None
                         }
                       })),
                     )
@@ -271,7 +267,7 @@ pub fn gen_for<'a>(
   let mut args: Vec<String> = vec![];
   args.push(item_var);
   if let Some(raw_key) = raw_key.clone() {
-    let key_var = format!("_for_key{depth}");
+    let key_var = context.local("_for_key", depth);
     args.push(key_var.clone());
     id_map.insert(
       raw_key,
@@ -289,7 +285,7 @@ pub fn gen_for<'a>(
     id_map.insert(key_var, None);
   }
   if let Some(raw_index) = raw_index.clone() {
-    let index_var = format!("_for_index{depth}");
+    let index_var = context.local("_for_index", depth);
     args.push(index_var.clone());
     id_map.insert(
       raw_index,
@@ -313,7 +309,7 @@ pub fn gen_for<'a>(
   let mut selector_setup = ast.vec();
 
   for (i, selector) in selector_patterns.into_iter().enumerate() {
-    let selector_name = format!("_selector{id}_{i}");
+    let selector_name = context.local("_selector", format!("{id}_{i}"));
     selector_declarations.push(Statement::VariableDeclaration(
       ast.alloc_variable_declaration(
         SPAN,
@@ -344,7 +340,7 @@ pub fn gen_for<'a>(
         ),
         ast.expression_call(
           SPAN,
-          ast.expression_identifier(SPAN, ast.atom("createSelector")),
+          ast.expression_identifier(SPAN, ast.atom(&context.local_name("createSelector"))),
           NONE,
           ast.vec1(Argument::ArrowFunctionExpression(
             ast.alloc_arrow_function_expression(
@@ -429,7 +425,10 @@ pub fn gen_for<'a>(
                     SPAN,
                     ast.expression_call(
                       SPAN,
-                      ast.expression_identifier(SPAN, ast.atom(&format!("_selector{id}_{i}"))),
+                      ast.expression_identifier(
+                        SPAN,
+                        ast.atom(&context.local("_selector", format!("{id}_{i}"))),
+                      ),
                       NONE,
                       ast.vec1(Argument::ArrowFunctionExpression(
                         ast.alloc_arrow_function_expression(
@@ -525,7 +524,7 @@ pub fn gen_for<'a>(
                   ast.vec(),
                   ast.binding_pattern(
                     BindingPatternKind::BindingIdentifier(
-                      ast.alloc_binding_identifier(SPAN, ast.atom("_")),
+                      ast.alloc_binding_identifier(SPAN, ast.atom(&context.local_name("_"))),
                     ),
                     NONE,
                     false,
@@ -558,7 +557,7 @@ pub fn gen_for<'a>(
                   ast.vec(),
                   ast.binding_pattern(
                     BindingPatternKind::BindingIdentifier(
-                      ast.alloc_binding_identifier(SPAN, ast.atom("__")),
+                      ast.alloc_binding_identifier(SPAN, ast.atom(&context.local_name("__"))),
                     ),
                     NONE,
                     false,
@@ -631,13 +630,14 @@ pub fn gen_for<'a>(
                     SPAN,
                     ast.property_key_static_identifier(SPAN, ast.atom("createSelector")),
                     ast.binding_pattern(
-                      BindingPatternKind::BindingIdentifier(
-                        ast.alloc_binding_identifier(SPAN, ast.atom("createSelector")),
-                      ),
+                      BindingPatternKind::BindingIdentifier(ast.alloc_binding_identifier(
+                        SPAN,
+                        ast.atom(&context.local_name("createSelector")),
+                      )),
                       NONE,
                       false,
                     ),
-                    true,
+                    context.local_name("createSelector") == "createSelector",
                     false,
                   )),
                   NONE,
@@ -670,7 +670,7 @@ pub fn gen_for<'a>(
           VariableDeclarationKind::Const,
           ast.binding_pattern(
             BindingPatternKind::BindingIdentifier(
-              ast.alloc_binding_identifier(SPAN, ast.atom(&format!("n{id}"))),
+              ast.alloc_binding_identifier(SPAN, ast.atom(&context.local("n", id))),
             ),
             NONE,
             false,
@@ -855,14 +855,14 @@ fn analyze_variable_scopes(
 fn get_expression<'a>(effect: &'a IREffect<'a>) -> Option<&'a SimpleExpressionNode<'a>> {
   let operation = effect.operations.first();
   match operation.as_ref().unwrap() {
-    Either16::C(operation) => operation.values.first(),
-    Either16::G(operation) => operation.values.first(),
-    Either16::K(operation) => operation.values.first(),
-    Either16::I(operation) => Some(&operation.value),
-    Either16::H(operation) => operation.value.as_ref(),
-    Either16::F(operation) => Some(&operation.value),
-    Either16::J(operation) => Some(&operation.value),
-    Either16::D(operation) => operation.prop.values.first(),
+    OperationNode::SetText(operation) => operation.values.first(),
+    OperationNode::SetNodes(operation) => operation.values.first(),
+    OperationNode::CreateNodes(operation) => operation.values.first(),
+    OperationNode::SetHtml(operation) => Some(&operation.value),
+    OperationNode::SetEvent(operation) => operation.value.as_ref(),
+    OperationNode::SetDynamicEvents(operation) => Some(&operation.value),
+    OperationNode::SetTemplateRef(operation) => Some(&operation.value),
+    OperationNode::SetProp(operation) => operation.prop.values.first(),
     _ => None,
   }
 }
@@ -1,4 +1,3 @@
-use napi::Either;
 use oxc_ast::NONE;
 use oxc_ast::ast::BindingPatternKind;
 use oxc_ast::ast::FormalParameterKind;
@@ -11,6 +10,7 @@ use crate::generate::block::gen_block;
 use crate::generate::expression::gen_expression;
 use crate::ir::index::BlockIRNode;
 use crate::ir::index::IfIRNode;
+use crate::utils::either::Either;
 
 pub fn gen_if<'a>(
   oper: IfIRNode<'a>,
@@ -117,7 +117,7 @@ pub fn gen_if<'a>(
         VariableDeclarationKind::Const,
         ast.binding_pattern(
           BindingPatternKind::BindingIdentifier(
-            ast.alloc_binding_identifier(SPAN, ast.atom(&format!("n{}", oper.id))),
+            ast.alloc_binding_identifier(SPAN, ast.atom(&context.local("n", oper.id))),
           ),
           NONE,
           false,
@@ -12,106 +12,118 @@ use crate::ir::index::DirectiveIRNode;
 use crate::ir::index::DirectiveNode;
 use crate::ir::index::SimpleExpressionNode;
 use crate::utils::check::is_simple_identifier;
+use crate::utils::error::ErrorCodes;
 
 // This is only for built-in v-model on native elements.
 pub fn gen_v_model<'a>(
   oper: DirectiveIRNode<'a>,
   context: &'a CodegenContext<'a>,
-) -> Statement<'a> {
+) -> Option<Statement<'a>> {
   let ast = &context.ast;
   let DirectiveIRNode {
     model_type,
     element,
-    dir: DirectiveNode { exp, modifiers, .. },
+    dir: DirectiveNode {
+      exp,
+      modifiers,
+      loc,
+      ..
+    },
     ..
   } = oper;
-  let exp = exp.unwrap();
+  let helper = match model_type.as_deref() {
+    Some("text") => "applyTextModel",
+    Some("radio") => "applyRadioModel",
+    Some("checkbox") => "applyCheckboxModel",
+    Some("select") => "applySelectModel",
+    Some("dynamic") => "applyDynamicModel",
+    _ => {
+      context.options.on_error.as_ref()(ErrorCodes::VModelOnInvalidElement, loc);
+      return None;
+    }
+  };
+  let Some(exp) = exp else {
+    context.options.on_error.as_ref()(ErrorCodes::VModelNoExpression, loc);
+    return None;
+  };
 
-  ast.statement_expression(
-    SPAN,
-    ast.expression_call(
+  Some(
+    ast.statement_expression(
       SPAN,
-      ast.expression_identifier(
+      ast.expression_call(
         SPAN,
-        ast.atom(&context.helper(match model_type.unwrap().as_str() {
-          "text" => "applyTextModel",
-          "radio" => "applyRadioModel",
-          "checkbox" => "applyCheckboxModel",
-          "select" => "applySelectModel",
-          "dynamic" => "applyDynamicModel",
-          _ => panic!("Unsupported model type"),
-        })),
-      ),
-      NONE,
-      ast.vec_from_iter(
-        [
-          Some(
-            ast
-              .expression_identifier(SPAN, ast.atom(&format!("n{element}")))
-              .into(),
-          ),
-          // getter
-          Some(
-            ast
-              .expression_arrow_function(
-                SPAN,
-                true,
-                false,
-                NONE,
-                ast.formal_parameters(
-                  SPAN,
-                  FormalParameterKind::ArrowFormalParameters,
-                  ast.vec(),
-                  NONE,
-                ),
-                NONE,
-                ast.function_body(
-                  SPAN,
-                  ast.vec(),
-                  ast.vec1(
-                    ast
-                      .statement_expression(SPAN, gen_expression(exp.clone(), context, None, None)),
-                  ),
-                ),
-              )
-              .into(),
-          ),
-          // setter
-          Some(gen_model_handler(exp, context).into()),
-          // modifiers
-          if !modifiers.is_empty() {
+        ast.expression_identifier(SPAN, ast.atom(&context.helper(helper))),
+        NONE,
+        ast.vec_from_iter(
+          [
             Some(
               ast
-                .expression_object(
+                .expression_identifier(SPAN, ast.atom(&context.local("n", element)))
+                .into(),
+            ),
+            // getter
+            Some(
+              ast
+                .expression_arrow_function(
                   SPAN,
-                  ast.vec_from_iter(modifiers.into_iter().map(|modifier| {
-                    let modifier = modifier.content;
-                    let modifier = if is_simple_identifier(&modifier) {
-                      &modifier
-                    } else {
-                      &format!("\"{}\"", modifier)
-                    };
-                    ast.object_property_kind_object_property(
+                  true,
+                  false,
+                  NONE,
+                  ast.formal_parameters(
+                    SPAN,
+                    FormalParameterKind::ArrowFormalParameters,
+                    ast.vec(),
+                    NONE,
+                  ),
+                  NONE,
+                  ast.function_body(
+                    SPAN,
+                    ast.vec(),
+                    ast.vec1(ast.statement_expression(
                       SPAN,
-                      PropertyKind::Init,
-                      ast.property_key_static_identifier(SPAN, ast.atom(modifier)),
-                      ast.expression_boolean_literal(SPAN, true),
-                      false,
-                      false,
-                      false,
-                    )
-                  })),
+                      gen_expression(exp.clone(), context, None, None),
+                    )),
+                  ),
                 )
                 .into(),
-            )
-          } else {
-            None
-          },
-        ]
-        .into_iter()
-        .flatten(),
+            ),
+            // setter
+            Some(gen_model_handler(exp, context).into()),
+            // modifiers
+            if !modifiers.is_empty() {
+              Some(
+                ast
+                  .expression_object(
+                    SPAN,
+                    ast.vec_from_iter(modifiers.into_iter().map(|modifier| {
+                      let modifier = modifier.content;
+                      let modifier = if is_simple_identifier(&modifier) {
+                        &modifier
+                      } else {
+                        &format!("\"{}\"", modifier)
+                      };
+                      ast.object_property_kind_object_property(
+                        SPAN,
+                        PropertyKind::Init,
+                        ast.property_key_static_identifier(SPAN, ast.atom(modifier)),
+                        ast.expression_boolean_literal(SPAN, true),
+                        false,
+                        false,
+                        false,
+                      )
+                    })),
+                  )
+                  .into(),
+              )
+            } else {
+              None
+            },
+          ]
+          .into_iter()
+          .flatten(),
+        ),
+        false,
       ),
-      false,
     ),
   )
 }
@@ -134,7 +146,7 @@ pub fn gen_model_handler<'a>(
         ast.vec(),
         ast.binding_pattern(
           BindingPatternKind::BindingIdentifier(
-            ast.alloc_binding_identifier(SPAN, ast.atom("_value")),
+            ast.alloc_binding_identifier(SPAN, ast.atom(&context.local_name("_value"))),
           ),
           NONE,
           false,
@@ -154,7 +166,7 @@ pub fn gen_model_handler<'a>(
         gen_expression(
           exp,
           context,
-          Some(ast.expression_identifier(SPAN, ast.atom("_value"))),
+          Some(ast.expression_identifier(SPAN, ast.atom(&context.local_name("_value")))),
           None,
         ),
       )),
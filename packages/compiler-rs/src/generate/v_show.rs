@@ -6,48 +6,53 @@ use oxc_span::SPAN;
 use crate::generate::CodegenContext;
 use crate::generate::expression::gen_expression;
 use crate::ir::index::DirectiveIRNode;
+use crate::utils::error::ErrorCodes;
 
-pub fn gen_v_show<'a>(oper: DirectiveIRNode<'a>, context: &'a CodegenContext<'a>) -> Statement<'a> {
+pub fn gen_v_show<'a>(
+  oper: DirectiveIRNode<'a>,
+  context: &'a CodegenContext<'a>,
+) -> Option<Statement<'a>> {
   let ast = &context.ast;
   let DirectiveIRNode { dir, element, .. } = oper;
+  let Some(exp) = dir.exp else {
+    context.options.on_error.as_ref()(ErrorCodes::VShowNoExpression, dir.loc);
+    return None;
+  };
 
-  ast.statement_expression(
-    SPAN,
-    ast.expression_call(
+  Some(
+    ast.statement_expression(
       SPAN,
-      ast.expression_identifier(SPAN, ast.atom(&context.helper("applyVShow"))),
-      NONE,
-      ast.vec_from_array([
-        ast
-          .expression_identifier(SPAN, ast.atom(&format!("n{element}")))
-          .into(),
-        ast
-          .expression_arrow_function(
-            SPAN,
-            true,
-            false,
-            NONE,
-            ast.formal_parameters(
+      ast.expression_call(
+        SPAN,
+        ast.expression_identifier(SPAN, ast.atom(&context.helper("applyVShow"))),
+        NONE,
+        ast.vec_from_array([
+          ast
+            .expression_identifier(SPAN, ast.atom(&context.local("n", element)))
+            .into(),
+          ast
+            .expression_arrow_function(
               SPAN,
-              FormalParameterKind::ArrowFormalParameters,
-              ast.vec(),
+              true,
+              false,
               NONE,
-            ),
-            NONE,
-            ast.function_body(
-              SPAN,
-              ast.vec(),
-              ast.vec1(
-                ast.statement_expression(
-                  SPAN,
-                  gen_expression(dir.exp.unwrap(), context, None, None),
-                ),
+              ast.formal_parameters(
+                SPAN,
+                FormalParameterKind::ArrowFormalParameters,
+                ast.vec(),
+                NONE,
+              ),
+              NONE,
+              ast.function_body(
+                SPAN,
+                ast.vec(),
+                ast.vec1(ast.statement_expression(SPAN, gen_expression(exp, context, None, None))),
               ),
-            ),
-          )
-          .into(),
-      ]),
-      false,
+            )
+            .into(),
+        ]),
+        false,
+      ),
     ),
   )
 }
@@ -1,12 +1,13 @@
 use indexmap::IndexMap;
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either4},
-};
+use serde::Serialize;
 
-use crate::ir::index::{BlockIRNode, IRFor, Modifiers, SimpleExpressionNode};
+use crate::{
+  ir::index::{BlockIRNode, IRFor, Modifiers, SimpleExpressionNode},
+  utils::either::Either,
+};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRProp<'a> {
   pub key: SimpleExpressionNode<'a>,
   pub modifier: Option<String>,
@@ -22,16 +23,25 @@ pub struct IRProp<'a> {
 
 pub type IRPropsStatic<'a> = Vec<IRProp<'a>>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRPropsDynamicExpression<'a> {
   pub value: SimpleExpressionNode<'a>,
   pub handler: Option<bool>,
 }
 
-pub type IRProps<'a> = Either3<IRPropsStatic<'a>, IRProp<'a>, IRPropsDynamicExpression<'a>>;
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IRProps<'a> {
+  Static(IRPropsStatic<'a>),
+  // props with a dynamic key, e.g. v-model:$name$
+  Dynamic(IRProp<'a>),
+  // {...obj} and v-on={obj}
+  Expression(IRPropsDynamicExpression<'a>),
+}
 
 // slots
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum IRSlotType {
   STATIC,
   DYNAMIC,
@@ -39,21 +49,26 @@ pub enum IRSlotType {
   EXPRESSION,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRSlotsStatic<'a> {
   pub slot_type: IRSlotType,
   pub slots: IndexMap<String, BlockIRNode<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRSlotDynamicBasic<'a> {
   pub slot_type: IRSlotType,
   pub name: SimpleExpressionNode<'a>,
+  #[serde(rename = "fn")]
   pub _fn: BlockIRNode<'a>,
+  #[serde(rename = "loop")]
   pub _loop: Option<IRFor<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRSlotDynamicConditional<'a> {
   pub slot_type: IRSlotType,
   pub condition: SimpleExpressionNode<'a>,
@@ -61,15 +76,18 @@ pub struct IRSlotDynamicConditional<'a> {
   pub negative: Option<Box<Either<IRSlotDynamicBasic<'a>, IRSlotDynamicConditional<'a>>>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRSlotsExpression<'a> {
   pub slot_type: IRSlotType,
   pub slots: SimpleExpressionNode<'a>,
 }
 
-pub type IRSlots<'a> = Either4<
-  IRSlotsStatic<'a>,
-  IRSlotDynamicBasic<'a>,
-  IRSlotDynamicConditional<'a>,
-  IRSlotsExpression<'a>,
->;
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum IRSlots<'a> {
+  Static(IRSlotsStatic<'a>),
+  Dynamic(IRSlotDynamicBasic<'a>),
+  Conditional(IRSlotDynamicConditional<'a>),
+  Expression(IRSlotsExpression<'a>),
+}
@@ -1,15 +1,14 @@
-use std::collections::HashSet;
-
-use napi::{Either, bindgen_prelude::Either16};
+use indexmap::IndexSet;
 use oxc_allocator::{Allocator, TakeIn};
 use oxc_ast::ast::{Expression, JSXChild};
 use oxc_span::Span;
+use serde::{Serialize, Serializer, ser::SerializeStruct};
 
 pub use crate::utils::expression::SimpleExpressionNode;
 
 use crate::{
   ir::component::{IRProp, IRProps, IRSlots},
-  utils::text::is_empty_text,
+  utils::{either::Either, text::is_empty_text},
 };
 
 #[derive(Debug)]
@@ -63,7 +62,8 @@ impl<'a> RootNode<'a> {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BlockIRNode<'a> {
   pub dynamic: IRDynamicInfo<'a>,
   pub temp_id: i32,
@@ -90,12 +90,14 @@ impl<'a> Default for BlockIRNode<'a> {
   }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RootIRNode<'a> {
+  #[serde(skip)]
   pub source: &'a str,
   pub root_template_index: Option<usize>,
-  pub component: HashSet<String>,
-  pub directive: HashSet<String>,
+  pub component: IndexSet<String>,
+  pub directive: IndexSet<String>,
   pub block: BlockIRNode<'a>,
   pub has_template_ref: bool,
 }
@@ -103,8 +105,8 @@ impl<'a> RootIRNode<'a> {
   pub fn new(source: &'a str) -> Self {
     RootIRNode {
       source,
-      component: HashSet::new(),
-      directive: HashSet::new(),
+      component: IndexSet::new(),
+      directive: IndexSet::new(),
       block: BlockIRNode::new(),
       has_template_ref: false,
       root_template_index: None,
@@ -112,7 +114,8 @@ impl<'a> RootIRNode<'a> {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IfIRNode<'a> {
   pub id: i32,
   pub condition: SimpleExpressionNode<'a>,
@@ -123,7 +126,8 @@ pub struct IfIRNode<'a> {
   pub anchor: Option<i32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRFor<'a> {
   pub source: Option<SimpleExpressionNode<'a>>,
   pub value: Option<SimpleExpressionNode<'a>>,
@@ -131,7 +135,8 @@ pub struct IRFor<'a> {
   pub index: Option<SimpleExpressionNode<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ForIRNode<'a> {
   pub source: SimpleExpressionNode<'a>,
   pub value: Option<SimpleExpressionNode<'a>>,
@@ -148,7 +153,8 @@ pub struct ForIRNode<'a> {
   pub anchor: Option<i32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetPropIRNode<'a> {
   pub set_prop: bool,
   pub element: i32,
@@ -157,7 +163,8 @@ pub struct SetPropIRNode<'a> {
   pub tag: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetDynamicPropsIRNode<'a> {
   pub set_dynamic_props: bool,
   pub element: i32,
@@ -165,14 +172,16 @@ pub struct SetDynamicPropsIRNode<'a> {
   pub root: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetDynamicEventsIRNode<'a> {
   pub set_dynamic_events: bool,
   pub element: i32,
   pub value: SimpleExpressionNode<'a>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetTextIRNode<'a> {
   pub set_text: bool,
   pub element: i32,
@@ -180,7 +189,8 @@ pub struct SetTextIRNode<'a> {
   pub generated: Option<bool>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetNodesIRNode<'a> {
   pub set_nodes: bool,
   pub element: i32,
@@ -189,7 +199,8 @@ pub struct SetNodesIRNode<'a> {
   pub generated: Option<bool>, // whether this is a generated empty text node by `processTextLikeContainer`
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Modifiers {
   // modifiers for addEventListener() options, e.g. .passive & .capture
   pub options: Vec<String>,
@@ -199,7 +210,8 @@ pub struct Modifiers {
   pub non_keys: Vec<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetEventIRNode<'a> {
   pub set_event: bool,
   pub element: i32,
@@ -211,14 +223,16 @@ pub struct SetEventIRNode<'a> {
   pub effect: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetHtmlIRNode<'a> {
   pub set_html: bool,
   pub element: i32,
   pub value: SimpleExpressionNode<'a>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetTemplateRefIRNode<'a> {
   pub set_template_ref: bool,
   pub element: i32,
@@ -227,7 +241,8 @@ pub struct SetTemplateRefIRNode<'a> {
   pub effect: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateNodesIRNode<'a> {
   pub create_nodes: bool,
   pub id: i32,
@@ -235,7 +250,8 @@ pub struct CreateNodesIRNode<'a> {
   pub values: Vec<SimpleExpressionNode<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InsertNodeIRNode {
   pub insert_node: bool,
   pub elements: Vec<i32>,
@@ -243,7 +259,8 @@ pub struct InsertNodeIRNode {
   pub anchor: Option<i32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DirectiveIRNode<'a> {
   pub directive: bool,
   pub element: i32,
@@ -254,7 +271,8 @@ pub struct DirectiveIRNode<'a> {
   pub model_type: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateComponentIRNode<'a> {
   pub create_component: bool,
   pub id: i32,
@@ -269,36 +287,41 @@ pub struct CreateComponentIRNode<'a> {
   pub anchor: Option<i32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DeclareOldRefIRNode {
   pub declare_older_ref: bool,
   pub id: i32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetTextChildIRNode {
   pub get_text_child: bool,
   pub parent: i32,
 }
 
-pub type OperationNode<'a> = Either16<
-  IfIRNode<'a>,
-  ForIRNode<'a>,
-  SetTextIRNode<'a>,
-  SetPropIRNode<'a>,
-  SetDynamicPropsIRNode<'a>,
-  SetDynamicEventsIRNode<'a>,
-  SetNodesIRNode<'a>,
-  SetEventIRNode<'a>,
-  SetHtmlIRNode<'a>,
-  SetTemplateRefIRNode<'a>,
-  CreateNodesIRNode<'a>,
-  InsertNodeIRNode,
-  DirectiveIRNode<'a>,
-  CreateComponentIRNode<'a>,
-  DeclareOldRefIRNode,
-  GetTextChildIRNode,
->;
+/// Serialized with a `type` field naming the operation, e.g. `"SET_TEXT"`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OperationNode<'a> {
+  If(IfIRNode<'a>),
+  For(ForIRNode<'a>),
+  SetText(SetTextIRNode<'a>),
+  SetProp(SetPropIRNode<'a>),
+  SetDynamicProps(SetDynamicPropsIRNode<'a>),
+  SetDynamicEvents(SetDynamicEventsIRNode<'a>),
+  SetNodes(SetNodesIRNode<'a>),
+  SetEvent(SetEventIRNode<'a>),
+  SetHtml(SetHtmlIRNode<'a>),
+  SetTemplateRef(SetTemplateRefIRNode<'a>),
+  CreateNodes(CreateNodesIRNode<'a>),
+  InsertNode(InsertNodeIRNode),
+  Directive(DirectiveIRNode<'a>),
+  CreateComponent(CreateComponentIRNode<'a>),
+  DeclareOldRef(DeclareOldRefIRNode),
+  GetTextChild(GetTextChildIRNode),
+}
 
 pub enum DynamicFlag {
   None = 0,
@@ -314,7 +337,8 @@ pub enum DynamicFlag {
   // REFERENCED_AND_NON_TEMPLATE_AND_INSERT = 7,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IRDynamicInfo<'a> {
   pub id: Option<i32>,
   pub flags: i32,
@@ -343,18 +367,28 @@ impl<'a> Default for IRDynamicInfo<'a> {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IREffect<'a> {
   pub expressions: Vec<SimpleExpressionNode<'a>>,
   pub operations: Vec<OperationNode<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DirectiveNode<'a> {
   // the normalized name without prefix or shorthands, e.g. "bind", "on"
   pub name: String,
   pub exp: Option<SimpleExpressionNode<'a>>,
   pub arg: Option<SimpleExpressionNode<'a>>,
   pub modifiers: Vec<SimpleExpressionNode<'a>>,
+  #[serde(serialize_with = "serialize_span")]
   pub loc: Span,
 }
+
+pub fn serialize_span<S: Serializer>(span: &Span, serializer: S) -> Result<S::Ok, S::Error> {
+  let mut state = serializer.serialize_struct("Span", 2)?;
+  state.serialize_field("start", &span.start)?;
+  state.serialize_field("end", &span.end)?;
+  state.end()
+}
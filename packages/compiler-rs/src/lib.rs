@@ -1,6 +1,9 @@
 pub mod compile;
 pub mod generate;
 pub mod ir;
+pub mod sfc;
+pub mod template;
 pub mod transform;
 pub mod traverse;
 pub mod utils;
+pub mod virtual_code;
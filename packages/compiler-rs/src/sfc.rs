@@ -0,0 +1,329 @@
+#[cfg(feature = "napi")]
+use napi::Env;
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_sourcemap::SourceMap;
+use oxc_span::{SourceType, Span};
+use std::{cell::RefCell, collections::BTreeSet};
+
+#[cfg(feature = "napi")]
+use crate::compile::CompilerOptions;
+#[cfg(feature = "napi")]
+use crate::transform::{TransformConfig, napi_transform_options};
+use crate::{
+  transform::{
+    TransformMetadata, TransformOptions, TransformReturn, custom_directive::DirectiveTransform,
+    transform_file,
+  },
+  utils::{
+    factory::Factories,
+    offset_map::MappedCode,
+    source_map::{chain_source_map, parse_input_source_map},
+  },
+};
+
+#[cfg(feature = "napi")]
+#[napi]
+pub fn _transform_sfc(
+  env: Env,
+  source: String,
+  options: Option<CompilerOptions>,
+) -> TransformReturn {
+  let mut options = options.unwrap_or_default();
+  let filename = options.filename.take().unwrap_or("index.vue".to_string());
+  let config = TransformConfig::from(&mut options);
+  transform_sfc(
+    &source,
+    Some(napi_transform_options(env, &config, options, &filename)),
+  )
+}
+
+/// A `<script>` block of an SFC.
+struct ScriptBlock<'s> {
+  content: Span,
+  lang: Option<&'s str>,
+  setup: bool,
+}
+
+/// Compiles the JSX in the `<script>` and `<script setup>` blocks of a Vue SFC
+/// with `lang="jsx"` or `lang="tsx"`, and returns the SFC with the compiled
+/// blocks, e.g. for `@vitejs/plugin-vue` to compile afterwards. Errors and
+/// source maps point into the SFC. The blocks end up in the same module, so
+/// the bindings generated for a block avoid the bindings of the others.
+pub fn transform_sfc(source: &str, options: Option<TransformOptions>) -> TransformReturn {
+  let options = options.unwrap_or_default();
+  let blocks = parse_script_blocks(source)
+    .into_iter()
+    .filter_map(|block| {
+      let lang @ ("jsx" | "tsx") = block.lang? else {
+        return None;
+      };
+      let filename = format!(
+        "{}?vue&type=script{}&lang.{lang}",
+        options.filename,
+        if block.setup { "&setup=true" } else { "" },
+      );
+      let source_type = SourceType::from_path(&filename).unwrap_or_default();
+      Some((block, filename, source_type))
+    })
+    .collect::<Vec<_>>();
+  let mut reserved_names = options.reserved_names.clone();
+  reserved_names.extend(blocks.iter().flat_map(|(block, _, source_type)| {
+    module_names(block.content.source_text(source), *source_type)
+  }));
+  let mut out = MappedCode::default();
+  let mut ir = vec![];
+  let mut metadata = TransformMetadata::default();
+  let mut last = 0;
+  for (block, filename, source_type) in blocks {
+    let content = block.content.source_text(source);
+    let result = transform_file(
+      content,
+      Some(block_options(
+        &options,
+        &filename,
+        source_type,
+        block.content.start,
+        reserved_names.clone(),
+      )),
+    );
+    reserved_names.extend(module_names(&result.code, source_type));
+    out.push_mapped(&source[last..block.content.start as usize], last as u32);
+    // Keeps the compiled block on its own lines, like the original content.
+    out.push("\n");
+    match result
+      .map
+      .and_then(|map| SourceMap::from_json_string(&map).ok())
+    {
+      Some(map) => out.push_with_map(&result.code, &map, content, block.content.start),
+      None => out.push(&result.code),
+    }
+    last = block.content.end as usize;
+    ir.extend(result.ir);
+    metadata.extend(offset_metadata(result.metadata, block.content.start));
+  }
+  out.push_mapped(&source[last..], last as u32);
+  let MappedCode { code, map } = out;
+  TransformReturn {
+    map: options.source_map.then(|| {
+      let map = map.to_source_map(&code, source, options.filename);
+      match options
+        .input_source_map
+        .and_then(|input_source_map| parse_input_source_map(input_source_map, &*options.on_error))
+      {
+        Some(input_source_map) => chain_source_map(map, &input_source_map),
+        None => map,
+      }
+      .to_json_string()
+    }),
+    ir: options.dump_ir.then(|| {
+      let roots = ir
+        .iter()
+        .map(|ir| &ir[1..ir.len() - 1])
+        .filter(|ir| !ir.is_empty())
+        .collect::<Vec<_>>();
+      format!("[{}]", roots.join(","))
+    }),
+    metadata,
+    code,
+  }
+}
+
+/// The bindings and global references of `code`.
+fn module_names(code: &str, source_type: SourceType) -> Vec<String> {
+  let allocator = Allocator::default();
+  let program = Parser::new(&allocator, code, source_type).parse().program;
+  let semantic = SemanticBuilder::new().build(&program).semantic;
+  let scoping = semantic.scoping();
+  scoping
+    .symbol_names()
+    .chain(scoping.root_unresolved_references().keys().copied())
+    .map(String::from)
+    .collect()
+}
+
+/// Moves the component spans of a block to their offset in the SFC.
+fn offset_metadata(mut metadata: TransformMetadata, start: u32) -> TransformMetadata {
+  for component in &mut metadata.components {
+    component.loc = (component.loc.0 + start, component.loc.1 + start);
+  }
+  metadata
+}
+
+/// Options of one block, sharing the callbacks of the SFC's options and
+/// reporting errors at their offset in the SFC.
+fn block_options<'o>(
+  options: &'o TransformOptions,
+  filename: &'o str,
+  source_type: SourceType,
+  start: u32,
+  reserved_names: Vec<String>,
+) -> TransformOptions<'o> {
+  TransformOptions {
+    filename,
+    source_type,
+    templates: RefCell::new(vec![]),
+    helpers: RefCell::new(BTreeSet::new()),
+    delegates: RefCell::new(BTreeSet::new()),
+    ir: RefCell::new(vec![]),
+    source_map: options.source_map,
+    input_source_map: None,
+    with_fallback: options.with_fallback,
+    interop: options.interop,
+    vdom: options.vdom,
+    hmr: options.hmr,
+    ssr: options.ssr,
+    dump_ir: options.dump_ir,
+    runtime_modules: options.runtime_modules.clone(),
+    runtime_globals: options.runtime_globals.clone(),
+    import_style: options.import_style,
+    per_component_templates: options.per_component_templates,
+    template_module: options.template_module,
+    component_wrappers: options.component_wrappers.clone(),
+    vapor_factories: options.vapor_factories.clone(),
+    vdom_factories: options.vdom_factories.clone(),
+    factory_modules: options.factory_modules.clone(),
+    factories: RefCell::new(Factories::default()),
+    reserved_names,
+    metadata: RefCell::new(TransformMetadata::default()),
+    is_custom_element: Box::new(|tag| (options.is_custom_element)(tag)),
+    on_error: Box::new(move |code, span| {
+      (options.on_error)(code, Span::new(span.start + start, span.end + start))
+    }),
+    directive_transforms: options
+      .directive_transforms
+      .iter()
+      .map(|(name, directive_transform)| {
+        (
+          name.clone(),
+          Box::new(directive_transform) as DirectiveTransform,
+        )
+      })
+      .collect(),
+  }
+}
+
+/// Finds the top-level `<script>` blocks, skipping comments and the content
+/// of the other blocks.
+fn parse_script_blocks(source: &str) -> Vec<ScriptBlock<'_>> {
+  let mut blocks = vec![];
+  let mut pos = 0;
+  while let Some(index) = source[pos..].find('<') {
+    let start = pos + index;
+    let rest = &source[start..];
+    if rest.starts_with("<!--") {
+      pos = rest.find("-->").map_or(source.len(), |end| start + end + 3);
+      continue;
+    }
+    let name_len = rest[1..]
+      .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+      .unwrap_or(rest.len() - 1);
+    let name = &rest[1..1 + name_len];
+    let Some(tag_end) = find_tag_end(rest).filter(|_| !name.is_empty()) else {
+      pos = start + 1;
+      continue;
+    };
+    let attrs = &rest[1 + name_len..tag_end];
+    let content_start = start + tag_end + 1;
+    if attrs.ends_with('/') {
+      pos = content_start;
+      continue;
+    }
+    let content_end = find_end_tag(source, content_start, name);
+    if name == "script" {
+      blocks.push(ScriptBlock {
+        content: Span::new(content_start as u32, content_end as u32),
+        lang: attr_value(attrs, "lang"),
+        setup: attr_value(attrs, "setup").is_some(),
+      });
+    }
+    pos = source[content_end..]
+      .find('>')
+      .map_or(source.len(), |end| content_end + end + 1);
+  }
+  blocks
+}
+
+/// Returns the index of the `>` that ends the tag at the start of `tag`,
+/// skipping quoted attribute values, e.g. `generic="T extends A<B>"`.
+fn find_tag_end(tag: &str) -> Option<usize> {
+  let mut quote = None;
+  for (index, c) in tag.char_indices() {
+    match (quote, c) {
+      (None, '"' | '\'') => quote = Some(c),
+      (Some(open), _) if c == open => quote = None,
+      (None, '>') => return Some(index),
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Returns the start of the end tag of the block `name`. The content of the
+/// template block is markup, its comments, tags and nested `<template>`s are
+/// skipped, the content of the other blocks is raw text.
+fn find_end_tag(source: &str, start: usize, name: &str) -> usize {
+  let is_tag = |rest: &str| {
+    rest
+      .strip_prefix(name)
+      .is_some_and(|rest| rest.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/'))
+  };
+  let mut depth = 0;
+  let mut pos = start;
+  while let Some(index) = source[pos..].find('<') {
+    let tag = pos + index;
+    let rest = &source[tag + 1..];
+    pos = tag + 1;
+    if let Some(end_tag) = rest.strip_prefix('/') {
+      if is_tag(end_tag) {
+        if depth == 0 {
+          return tag;
+        }
+        depth -= 1;
+      }
+    } else if name == "template" {
+      if rest.starts_with("!--") {
+        pos = rest.find("-->").map_or(source.len(), |end| pos + end + 3);
+      } else if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let Some(end) = find_tag_end(rest) else {
+          break;
+        };
+        if is_tag(rest) && !rest[..end].ends_with('/') {
+          depth += 1;
+        }
+        pos += end + 1;
+      }
+    }
+  }
+  source.len()
+}
+
+/// Returns the value of the attribute `name`, or `""` if it has no value.
+fn attr_value<'s>(attrs: &'s str, name: &str) -> Option<&'s str> {
+  let mut rest = attrs;
+  while let Some(index) = rest.find(name) {
+    let before = &rest[..index];
+    rest = &rest[index + name.len()..];
+    if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+      continue;
+    }
+    let Some(value) = rest.trim_start().strip_prefix('=') else {
+      if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        return Some("");
+      }
+      continue;
+    };
+    let value = value.trim_start();
+    return Some(match value.chars().next() {
+      Some(quote @ ('"' | '\'')) => {
+        let value = &value[1..];
+        &value[..value.find(quote).unwrap_or(value.len())]
+      }
+      _ => &value[..value.find(char::is_whitespace).unwrap_or(value.len())],
+    });
+  }
+  None
+}
@@ -0,0 +1,793 @@
+use std::mem;
+
+#[cfg(feature = "napi")]
+use napi::Env;
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{BindingIdentifier, Expression, ObjectProperty, Statement};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::{GetSpan, SourceType, Span};
+
+#[cfg(feature = "napi")]
+use crate::compile::CompilerOptions;
+#[cfg(feature = "napi")]
+use crate::transform::{TransformConfig, napi_transform_options};
+use crate::{
+  transform::{TransformOptions, TransformReturn, transform_file},
+  utils::{
+    directive::encode_dynamic_argument,
+    error::ErrorCodes,
+    offset_map::MappedCode,
+    source_map::{chain_source_map, parse_input_source_map},
+    text::camelize,
+  },
+};
+
+const VOID_TAGS: [&str; 14] = [
+  "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+  "track", "wbr",
+];
+
+/// Globals that template expressions access directly instead of through `_ctx`.
+const GLOBALS: [&str; 26] = [
+  "Infinity",
+  "undefined",
+  "NaN",
+  "isFinite",
+  "isNaN",
+  "parseFloat",
+  "parseInt",
+  "decodeURI",
+  "decodeURIComponent",
+  "encodeURI",
+  "encodeURIComponent",
+  "Math",
+  "Number",
+  "Date",
+  "Array",
+  "Object",
+  "Boolean",
+  "String",
+  "RegExp",
+  "Map",
+  "Set",
+  "JSON",
+  "Intl",
+  "BigInt",
+  "console",
+  "Error",
+];
+
+#[cfg(feature = "napi")]
+#[napi]
+pub fn _transform_template(
+  env: Env,
+  source: String,
+  options: Option<CompilerOptions>,
+) -> TransformReturn {
+  let mut options = options.unwrap_or_default();
+  let filename = options.filename.take().unwrap_or("index.vue".to_string());
+  let config = TransformConfig::from(&mut options);
+  transform_template(
+    &source,
+    Some(napi_transform_options(env, &config, options, &filename)),
+  )
+}
+
+/// Compiles a Vue template, e.g. the `<template>` of an SFC, to a `render`
+/// function. The template is lowered to JSX and compiled by `transform`, so
+/// templates and JSX share the same IR and Vapor backend. Identifiers that
+/// aren't template variables or globals are accessed on `_ctx`, components and
+/// custom directives are resolved with `resolveComponent` and `resolveDirective`
+/// like in Vue templates, and errors and source maps point at the template.
+pub fn transform_template(source: &str, options: Option<TransformOptions>) -> TransformReturn {
+  let options = options.unwrap_or_default();
+  let MappedCode { code, map } = {
+    let mut parser = TemplateParser {
+      source,
+      pos: 0,
+      open_tags: vec![],
+      on_error: &options.on_error,
+    };
+    let nodes = parser.parse_children();
+    let lowering = Lowering {
+      source,
+      on_error: &options.on_error,
+      source_type: options.source_type.with_jsx(false),
+      locals: vec![],
+      out: MappedCode::default(),
+      to_handler_key: false,
+    };
+    lowering.lower(&nodes)
+  };
+  let input_source_map = options.source_map.then(|| {
+    let map = map.to_source_map(&code, source, options.filename);
+    match options
+      .input_source_map
+      .and_then(|input_source_map| parse_input_source_map(input_source_map, &*options.on_error))
+    {
+      Some(input_source_map) => chain_source_map(map, &input_source_map),
+      None => map,
+    }
+    .to_json_string()
+  });
+  let on_error = options.on_error;
+  let error_map = map.clone();
+  let mut result = transform_file(
+    &code,
+    Some(TransformOptions {
+      source_type: options.source_type.with_jsx(true),
+      with_fallback: true,
+      input_source_map: input_source_map.as_deref(),
+      on_error: Box::new(move |code, span| on_error(code, error_map.original_span(span))),
+      ..options
+    }),
+  );
+  for component in &mut result.metadata.components {
+    let span = map.original_span(Span::new(component.loc.0, component.loc.1));
+    component.loc = (span.start, span.end);
+  }
+  result
+}
+
+enum Node {
+  Element(Element),
+  Text(Span),
+  Interpolation(Span),
+}
+
+struct Element {
+  tag: Span,
+  attrs: Vec<Attr>,
+  children: Vec<Node>,
+}
+
+struct Attr {
+  name: Span,
+  /// Value without its quotes.
+  value: Option<Span>,
+}
+
+struct TemplateParser<'s, 'e> {
+  source: &'s str,
+  pos: usize,
+  open_tags: Vec<&'s str>,
+  on_error: &'e dyn Fn(ErrorCodes, Span),
+}
+
+impl<'s> TemplateParser<'s, '_> {
+  fn rest(&self) -> &'s str {
+    &self.source[self.pos..]
+  }
+
+  fn span(&self, start: usize, end: usize) -> Span {
+    Span::new(start as u32, end as u32)
+  }
+
+  /// Parses nodes until the end of the template or the end tag of an open element.
+  fn parse_children(&mut self) -> Vec<Node> {
+    let mut children = vec![];
+    while self.pos < self.source.len() {
+      let rest = self.rest();
+      if rest.starts_with("<!--") {
+        self.pos = rest
+          .find("-->")
+          .map_or(self.source.len(), |end| self.pos + end + 3);
+      } else if let Some(end_tag) = rest.strip_prefix("</") {
+        let name = &end_tag[..end_tag
+          .find(|c: char| c.is_whitespace() || c == '>')
+          .unwrap_or(end_tag.len())];
+        if self.open_tags.contains(&name) {
+          break;
+        }
+        let start = self.pos;
+        self.skip_end_tag();
+        (self.on_error)(ErrorCodes::XInvalidEndTag, self.span(start, self.pos));
+      } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+        children.push(Node::Element(self.parse_element()));
+      } else if let Some(expression) = rest.strip_prefix("{{") {
+        let start = self.pos + 2;
+        if let Some(end) = expression.find("}}") {
+          children.push(Node::Interpolation(self.span(start, start + end)));
+          self.pos = start + end + 2;
+        } else {
+          (self.on_error)(
+            ErrorCodes::XMissingInterpolationEnd,
+            self.span(self.pos, start),
+          );
+          self.push_text(&mut children, self.source.len());
+        }
+      } else {
+        let first = rest.chars().next().map_or(1, char::len_utf8);
+        let end = [
+          rest[first..].find('<').map(|end| end + first),
+          rest.find("{{"),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .map_or(self.source.len(), |end| self.pos + end);
+        self.push_text(&mut children, end);
+      }
+    }
+    children
+  }
+
+  fn push_text(&mut self, children: &mut Vec<Node>, end: usize) {
+    if let Some(Node::Text(text)) = children.last_mut()
+      && text.end as usize == self.pos
+    {
+      text.end = end as u32;
+    } else {
+      children.push(Node::Text(self.span(self.pos, end)));
+    }
+    self.pos = end;
+  }
+
+  fn skip_end_tag(&mut self) {
+    self.pos = self
+      .rest()
+      .find('>')
+      .map_or(self.source.len(), |end| self.pos + end + 1);
+  }
+
+  fn skip_whitespace(&mut self) {
+    self.pos = self.source.len() - self.rest().trim_start().len();
+  }
+
+  fn parse_element(&mut self) -> Element {
+    let start = self.pos;
+    self.pos += 1;
+    let tag = self.parse_name();
+    let mut attrs = vec![];
+    loop {
+      self.skip_whitespace();
+      let rest = self.rest();
+      if rest.is_empty() {
+        (self.on_error)(ErrorCodes::EofInTag, self.span(start, self.pos));
+        return Element {
+          tag,
+          attrs,
+          children: vec![],
+        };
+      } else if rest.starts_with("/>") {
+        self.pos += 2;
+        return Element {
+          tag,
+          attrs,
+          children: vec![],
+        };
+      } else if rest.starts_with('>') {
+        self.pos += 1;
+        break;
+      }
+      let name = self.parse_name();
+      if name.is_empty() {
+        self.pos += 1;
+        continue;
+      }
+      let end = self.pos;
+      self.skip_whitespace();
+      let value = if let Some(rest) = self.rest().strip_prefix('=') {
+        let quote = rest.trim_start().chars().next();
+        self.pos += 1;
+        self.skip_whitespace();
+        Some(
+          if let Some(quote) = quote.filter(|c| *c == '"' || *c == '\'') {
+            let start = self.pos + 1;
+            let end = self.source[start..]
+              .find(quote)
+              .map_or(self.source.len(), |end| start + end);
+            self.pos = (end + 1).min(self.source.len());
+            self.span(start, end)
+          } else {
+            self.parse_name()
+          },
+        )
+      } else {
+        self.pos = end;
+        None
+      };
+      attrs.push(Attr { name, value });
+    }
+    let name = tag.source_text(self.source);
+    if VOID_TAGS.contains(&name) {
+      return Element {
+        tag,
+        attrs,
+        children: vec![],
+      };
+    }
+    self.open_tags.push(name);
+    let children = self.parse_children();
+    self.open_tags.pop();
+    if self
+      .rest()
+      .strip_prefix("</")
+      .and_then(|end_tag| end_tag.strip_prefix(name))
+      .is_some_and(|rest| rest.starts_with(|c: char| c.is_whitespace() || c == '>'))
+    {
+      self.skip_end_tag();
+    } else {
+      (self.on_error)(ErrorCodes::XMissingEndTag, tag);
+    }
+    Element {
+      tag,
+      attrs,
+      children,
+    }
+  }
+
+  /// Parses a tag name, an attribute name or an unquoted attribute value.
+  fn parse_name(&mut self) -> Span {
+    let start = self.pos;
+    let rest = self.rest();
+    let mut len = rest
+      .find(|c: char| c.is_whitespace() || c == '>' || c == '=')
+      .unwrap_or(rest.len());
+    // `/` ends the name of self-closing tags, e.g. `<br/>` or `<div id=foo/>`.
+    if rest[..len].ends_with('/') && rest[len..].starts_with('>') {
+      len -= 1;
+    }
+    self.pos += len;
+    self.span(start, self.pos)
+  }
+}
+
+/// A parsed `v-` directive or its shorthand.
+struct Directive<'s> {
+  name: &'s str,
+  arg: Option<&'s str>,
+  /// Offset of `arg`.
+  arg_start: u32,
+  is_dynamic_arg: bool,
+  modifiers: Vec<&'s str>,
+}
+
+fn parse_directive(name: &str, start: u32) -> Option<Directive<'_>> {
+  let (directive, rest, arg_start) = if let Some(rest) = name.strip_prefix(':') {
+    ("bind", rest, 1)
+  } else if let Some(rest) = name.strip_prefix('@') {
+    ("on", rest, 1)
+  } else if let Some(rest) = name.strip_prefix('#') {
+    ("slot", rest, 1)
+  } else {
+    let rest = name.strip_prefix("v-")?;
+    let end = rest.find([':', '.']).unwrap_or(rest.len());
+    let arg = rest[end..].strip_prefix(':').unwrap_or(&rest[end..]);
+    (&rest[..end], arg, name.len() - arg.len())
+  };
+  let (arg, is_dynamic_arg, modifiers) = if let Some(dynamic) = rest.strip_prefix('[')
+    && let Some(end) = dynamic.find(']')
+  {
+    (Some(&dynamic[..end]), true, &dynamic[end + 1..])
+  } else {
+    let end = rest.find('.').unwrap_or(rest.len());
+    let arg = &rest[..end];
+    ((!arg.is_empty()).then_some(arg), false, &rest[end..])
+  };
+  Some(Directive {
+    name: directive,
+    arg,
+    arg_start: start + arg_start as u32 + is_dynamic_arg as u32,
+    is_dynamic_arg,
+    modifiers: modifiers.split('.').filter(|m| !m.is_empty()).collect(),
+  })
+}
+
+/// Lowers the parsed template to the JSX of a `render` function.
+struct Lowering<'s, 'e> {
+  source: &'s str,
+  on_error: &'e dyn Fn(ErrorCodes, Span),
+  source_type: SourceType,
+  /// Variables of the enclosing `v-for`s and slots.
+  locals: Vec<String>,
+  out: MappedCode,
+  to_handler_key: bool,
+}
+
+impl Lowering<'_, '_> {
+  fn lower(mut self, nodes: &[Node]) -> MappedCode {
+    let roots = nodes
+      .iter()
+      .filter(
+        |node| !matches!(node, Node::Text(text) if text.source_text(self.source).trim().is_empty()),
+      )
+      .collect::<Vec<_>>();
+    self.out.push("export function render(_ctx) {\n  return ");
+    if let [root @ Node::Element(_)] = roots[..] {
+      self.lower_node(root);
+    } else {
+      self.out.push("<>");
+      for node in roots {
+        self.lower_node(node);
+      }
+      self.out.push("</>");
+    }
+    self.out.push(";\n}\n");
+    if self.to_handler_key {
+      self
+        .out
+        .prepend("import { toHandlerKey as _toHandlerKey } from \"vue\";\n");
+    }
+    self.out
+  }
+
+  fn lower_node(&mut self, node: &Node) {
+    match node {
+      Node::Element(element) => self.lower_element(element),
+      Node::Text(text) => {
+        let content = text.source_text(self.source);
+        if content.contains(['{', '}', '<', '>']) {
+          self.out.push("{");
+          self
+            .out
+            .push_mapped(&serde_json::to_string(content).unwrap(), text.start);
+          self.out.push("}");
+        } else {
+          self.out.push_mapped(content, text.start);
+        }
+      }
+      Node::Interpolation(expression) => {
+        self.out.push("{");
+        self.lower_expression(*expression, true);
+        self.out.push("}");
+      }
+    }
+  }
+
+  fn lower_element(&mut self, element: &Element) {
+    let locals = self.locals.len();
+    let tag = element.tag.source_text(self.source);
+    self.out.push("<");
+    self.out.push_mapped(tag, element.tag.start);
+    // The `v-for` variables are in scope of the other attributes.
+    let (v_for, attrs): (Vec<_>, Vec<_>) = element
+      .attrs
+      .iter()
+      .partition(|attr| attr.name.source_text(self.source) == "v-for");
+    let mut slot_locals = vec![];
+    for attr in v_for.into_iter().chain(attrs) {
+      if attr.name.source_text(self.source) != "v-cloak" {
+        self.out.push(" ");
+        self.lower_attr(attr, &mut slot_locals);
+      }
+    }
+    if element.children.is_empty() {
+      self.out.push(" />");
+    } else {
+      self.locals.append(&mut slot_locals);
+      self.out.push(">");
+      for child in &element.children {
+        self.lower_node(child);
+      }
+      self.out.push("</");
+      self.out.push(tag);
+      self.out.push(">");
+    }
+    self.locals.truncate(locals);
+  }
+
+  fn lower_attr(&mut self, attr: &Attr, slot_locals: &mut Vec<String>) {
+    let name = attr.name.source_text(self.source);
+    let Some(directive) = parse_directive(name, attr.name.start) else {
+      self.out.push_mapped(name, attr.name.start);
+      if let Some(value) = attr.value {
+        let content = value.source_text(self.source);
+        if let Some(quote) = ['"', '\'']
+          .into_iter()
+          .find(|quote| !content.contains(*quote))
+        {
+          self.out.push(&format!("={quote}"));
+          self.out.push_mapped(content, value.start);
+          self.out.push(&quote.to_string());
+        } else {
+          self.out.push("={");
+          self
+            .out
+            .push_mapped(&serde_json::to_string(content).unwrap(), value.start);
+          self.out.push("}");
+        }
+      }
+      return;
+    };
+    let modifiers = directive
+      .modifiers
+      .iter()
+      .map(|modifier| format!("_{modifier}"))
+      .collect::<String>();
+    match directive.name {
+      "bind" => match (directive.arg, attr.value) {
+        (None, Some(value)) => {
+          self.out.push("{...");
+          self.lower_expression(value, true);
+          self.out.push("}");
+        }
+        (Some(_), Some(value)) if directive.is_dynamic_arg => {
+          self.out.push("{...{[");
+          self.lower_arg(&directive);
+          self.out.push("]: ");
+          self.lower_expression(value, true);
+          self.out.push("}}");
+        }
+        (Some(arg), value) => {
+          self.out.push_mapped(arg, directive.arg_start);
+          self.out.push(&modifiers);
+          self.out.push("={");
+          match value {
+            Some(value) => self.lower_expression(value, true),
+            // `:id` is short for `:id="id"`.
+            None => self.lower_prefixed(&camelize(arg), directive.arg_start, true),
+          }
+          self.out.push("}");
+        }
+        (None, None) => {}
+      },
+      "on" => {
+        let Some(arg) = directive.arg else {
+          if let Some(value) = attr.value {
+            self.out.push("{...");
+            self.lower_expression(value, true);
+            self.out.push("}");
+          }
+          return;
+        };
+        if directive.is_dynamic_arg {
+          self.to_handler_key = true;
+          self.out.push("{...{[_toHandlerKey(");
+          self.lower_arg(&directive);
+          self.out.push(")]: ");
+        } else {
+          let event = camelize(arg);
+          let mut chars = event.chars();
+          self.out.push("on");
+          if let Some(first) = chars.next() {
+            self.out.push_mapped(
+              &(first.to_ascii_uppercase().to_string() + chars.as_str()),
+              directive.arg_start,
+            );
+          }
+          self.out.push(&modifiers);
+          self.out.push("={");
+        }
+        match attr.value {
+          Some(value) => self.lower_handler(value),
+          None => self.out.push("() => {}"),
+        }
+        self
+          .out
+          .push(if directive.is_dynamic_arg { "}}" } else { "}" });
+      }
+      "for" => {
+        if let Some(value) = attr.value {
+          self.out.push("v-for={");
+          self.lower_v_for(value);
+          self.out.push("}");
+        }
+      }
+      "slot" => {
+        self.out.push("v-slot");
+        self.lower_directive_arg(&directive, attr);
+        if let Some(value) = attr.value {
+          self.out.push("={");
+          self
+            .out
+            .push_mapped(value.source_text(self.source), value.start);
+          self.out.push("}");
+          slot_locals.extend(self.binding_names(value.source_text(self.source)));
+        }
+      }
+      _ => {
+        self.out.push("v-");
+        self.out.push(directive.name);
+        self.lower_directive_arg(&directive, attr);
+        self.out.push(&modifiers);
+        if let Some(value) = attr.value {
+          self.out.push("={");
+          self.lower_expression(value, true);
+          self.out.push("}");
+        }
+      }
+    }
+  }
+
+  /// Lowers the argument of `v-slot`, `v-model` and custom directives. Dynamic
+  /// arguments are lowered to an encoded expression, see `encode_dynamic_argument`.
+  fn lower_directive_arg(&mut self, directive: &Directive, attr: &Attr) {
+    let Some(arg) = directive.arg else {
+      return;
+    };
+    if !directive.is_dynamic_arg {
+      self.out.push(":");
+      self.out.push_mapped(arg, directive.arg_start);
+      return;
+    }
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, arg, self.source_type).parse_expression();
+    if arg.trim().is_empty() || parsed.is_err() {
+      (self.on_error)(ErrorCodes::XUnsupportedDynamicArgument, attr.name);
+      return;
+    }
+    let out = mem::take(&mut self.out);
+    self.lower_arg(directive);
+    let mut expression = mem::replace(&mut self.out, out).code;
+    // The argument is inserted as it is, e.g. after `"onUpdate:" +`.
+    if !matches!(
+      parsed.unwrap().without_parentheses(),
+      Expression::Identifier(_) | Expression::StaticMemberExpression(_)
+    ) {
+      expression = format!("({expression})");
+    }
+    self.out.push(":");
+    self
+      .out
+      .push_mapped(&encode_dynamic_argument(&expression), directive.arg_start);
+  }
+
+  fn lower_arg(&mut self, directive: &Directive) {
+    let arg = directive.arg.unwrap_or_default();
+    self.lower_prefixed(arg, directive.arg_start, true);
+  }
+
+  fn lower_expression(&mut self, expression: Span, is_expression: bool) {
+    self.lower_prefixed(
+      expression.source_text(self.source),
+      expression.start,
+      is_expression,
+    );
+  }
+
+  /// Lowers an event handler. Member paths and functions are passed as the
+  /// handler, other expressions and statements are wrapped in a function.
+  fn lower_handler(&mut self, value: Span) {
+    let content = value.source_text(self.source);
+    let allocator = Allocator::default();
+    let program = Parser::new(&allocator, content, self.source_type)
+      .parse()
+      .program;
+    let is_handler = matches!(
+      &program.body[..],
+      [Statement::ExpressionStatement(statement)] if matches!(
+        statement.expression.without_parentheses(),
+        Expression::Identifier(_)
+          | Expression::StaticMemberExpression(_)
+          | Expression::ComputedMemberExpression(_)
+          | Expression::ArrowFunctionExpression(_)
+          | Expression::FunctionExpression(_)
+      )
+    );
+    let is_expression = matches!(&program.body[..], [Statement::ExpressionStatement(_)]);
+    self.locals.push("$event".to_string());
+    if is_handler {
+      self.lower_expression(value, true);
+    } else if is_expression {
+      self.out.push("$event => (");
+      self.lower_expression(value, true);
+      self.out.push(")");
+    } else {
+      self.out.push("$event => {");
+      self.lower_expression(value, false);
+      self.out.push("}");
+    }
+    self.locals.pop();
+  }
+
+  /// Lowers `alias in source` to the `v-for` of JSX, and adds the aliases to
+  /// the variables in scope.
+  fn lower_v_for(&mut self, value: Span) {
+    let content = value.source_text(self.source);
+    let Some((alias_end, source_start)) = [" in ", " of "]
+      .into_iter()
+      .filter_map(|separator| content.find(separator))
+      .min()
+      .map(|index| (index, index + 4))
+    else {
+      self.lower_expression(value, true);
+      return;
+    };
+    let source = Span::new(value.start + source_start as u32, value.end);
+    self.out.push_mapped(&content[..alias_end], value.start);
+    self.out.push(" in ");
+    self.lower_expression(source, true);
+    let alias = content[..alias_end].trim();
+    let alias = alias
+      .strip_prefix('(')
+      .and_then(|alias| alias.strip_suffix(')'))
+      .unwrap_or(alias);
+    let names = self.binding_names(alias);
+    self.locals.extend(names);
+  }
+
+  /// Returns the names bound by the parameters `params`, e.g. `item, index`
+  /// or `{ foo, bar }`.
+  fn binding_names(&self, params: &str) -> Vec<String> {
+    let allocator = Allocator::default();
+    let code = format!("({params}) => 0");
+    let program = Parser::new(&allocator, &code, self.source_type)
+      .parse()
+      .program;
+    let mut collector = BindingNames::default();
+    collector.visit_program(&program);
+    collector.names
+  }
+
+  /// Copies `code` from `start` of the template and accesses the identifiers
+  /// that aren't template variables or globals on `_ctx`.
+  fn lower_prefixed(&mut self, code: &str, start: u32, is_expression: bool) {
+    let allocator = Allocator::default();
+    let wrapped = format!("({code})");
+    let (parsed, offset) = if is_expression {
+      (wrapped.as_str(), 1)
+    } else {
+      (code, 0)
+    };
+    let ret = Parser::new(&allocator, parsed, self.source_type).parse();
+    if ret.panicked || !ret.errors.is_empty() {
+      self.out.push_mapped(code, start);
+      return;
+    }
+    let semantic = SemanticBuilder::new().build(&ret.program).semantic;
+    let scoping = semantic.scoping();
+    let mut shorthands = ShorthandProperties::default();
+    shorthands.visit_program(&ret.program);
+    let mut references = scoping
+      .root_unresolved_references()
+      .iter()
+      .filter(|(name, _)| {
+        !GLOBALS.contains(name) && !self.locals.iter().any(|local| local == *name)
+      })
+      .flat_map(|(_, reference_ids)| reference_ids.iter())
+      .map(|reference_id| scoping.get_reference(*reference_id))
+      .filter(|reference| reference.is_value())
+      .map(|reference| semantic.nodes().get_node(reference.node_id()).span())
+      .collect::<Vec<_>>();
+    references.sort_by_key(|span| span.start);
+    let mut last = 0;
+    for span in references {
+      let (reference_start, reference_end) =
+        ((span.start - offset) as usize, (span.end - offset) as usize);
+      self
+        .out
+        .push_mapped(&code[last..reference_start], start + last as u32);
+      if shorthands.starts.contains(&span.start) {
+        self.out.push_mapped(
+          &code[reference_start..reference_end],
+          start + reference_start as u32,
+        );
+        self.out.push(": ");
+      }
+      self.out.push("_ctx.");
+      last = reference_start;
+    }
+    self.out.push_mapped(&code[last..], start + last as u32);
+  }
+}
+
+#[derive(Default)]
+struct BindingNames {
+  names: Vec<String>,
+}
+
+impl<'a> Visit<'a> for BindingNames {
+  fn visit_binding_identifier(&mut self, it: &BindingIdentifier<'a>) {
+    self.names.push(it.name.to_string());
+  }
+}
+
+/// Starts of the shorthand properties, e.g. `foo` in `{ foo }`.
+#[derive(Default)]
+struct ShorthandProperties {
+  starts: Vec<u32>,
+}
+
+impl<'a> Visit<'a> for ShorthandProperties {
+  fn visit_object_property(&mut self, it: &ObjectProperty<'a>) {
+    if it.shorthand {
+      self.starts.push(it.value.span().start);
+    }
+    walk::walk_object_property(self, it);
+  }
+}
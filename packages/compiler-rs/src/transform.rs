@@ -1,15 +1,25 @@
-use napi::{Either, Env};
+#[cfg(feature = "napi")]
+use napi::Env;
+#[cfg(feature = "napi")]
 use napi_derive::napi;
 use oxc_allocator::{Allocator, TakeIn};
-use oxc_ast::ast::{
-  Expression, JSXChild, JSXClosingFragment, JSXExpressionContainer, JSXFragment, JSXOpeningFragment,
+use oxc_ast::{
+  AstBuilder, NONE,
+  ast::{
+    Argument, Expression, FormalParameterKind, JSXChild, JSXClosingFragment, JSXEmptyExpression,
+    JSXExpression, JSXExpressionContainer, JSXFragment, JSXOpeningFragment, Program,
+  },
 };
 use oxc_codegen::{Codegen, CodegenReturn, IndentChar};
-use oxc_parser::Parser;
-use oxc_span::{SPAN, SourceType, Span};
-use std::collections::BTreeSet;
+use oxc_parser::{Parser, ParserReturn};
+use oxc_semantic::{Scoping, SemanticBuilder};
+use oxc_span::{GetSpan, SPAN, SourceType, Span};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
-use std::{cell::RefCell, collections::HashSet, mem, rc::Rc};
+use std::{cell::RefCell, collections::HashSet, mem, rc::Rc, sync::LazyLock};
+pub mod custom_directive;
+#[cfg(feature = "napi")]
+pub mod transform_async;
 pub mod transform_children;
 pub mod transform_element;
 pub mod transform_template_ref;
@@ -26,6 +36,7 @@ pub mod v_slot;
 pub mod v_slots;
 pub mod v_text;
 
+#[cfg(feature = "napi")]
 use crate::compile::CompilerOptions;
 use crate::compile::Template;
 use crate::generate::CodegenContext;
@@ -34,55 +45,234 @@ use crate::{
   ir::{
     component::IRSlots,
     index::{
-      BlockIRNode, DynamicFlag, IRDynamicInfo, IREffect, Modifiers, OperationNode, RootIRNode,
-      RootNode, SimpleExpressionNode,
+      BlockIRNode, DynamicFlag, IRDynamicInfo, IREffect, IfIRNode, Modifiers, OperationNode,
+      RootIRNode, RootNode, SimpleExpressionNode,
     },
   },
   transform::{
-    transform_children::transform_children, transform_element::transform_element,
-    transform_template_ref::transform_template_ref, transform_text::transform_text,
-    v_for::transform_v_for, v_if::transform_v_if, v_once::transform_v_once,
-    v_slot::transform_v_slot, v_slots::transform_v_slots,
+    custom_directive::DirectiveTransform, transform_children::transform_children,
+    transform_element::transform_element, transform_template_ref::transform_template_ref,
+    transform_text::transform_text, v_for::transform_v_for, v_if::transform_v_if,
+    v_once::transform_v_once, v_slot::transform_v_slot, v_slots::transform_v_slots,
   },
   utils::{
     check::{is_constant_node, is_template},
-    error::ErrorCodes,
+    either::Either,
+    error::{ERROR_MESSAGES, ErrorCodes},
+    factory::{
+      Factories, default_factory_modules, default_vapor_factories, default_vdom_factories,
+    },
+    names::{UniqueNames, collect_names},
+    source_map::{chain_source_map, parse_input_source_map},
   },
 };
 
+/// How runtime helpers are imported.
+#[cfg_attr(feature = "napi", napi(string_enum = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportStyle {
+  /// `import { template as _template } from "vue"`
+  #[default]
+  Named,
+  /// `import * as _vue from "vue"`, helpers are called as `_vue.template`.
+  Namespace,
+  /// No imports, helpers are called on a global variable, e.g. `Vue.template`.
+  Global,
+}
+
 pub struct TransformOptions<'a> {
   pub templates: RefCell<Vec<Template>>,
   pub helpers: RefCell<BTreeSet<String>>,
   pub delegates: RefCell<BTreeSet<String>>,
+  /// JSON dumps of the IR, one per JSX root, collected when `dump_ir` is enabled.
+  pub ir: RefCell<Vec<String>>,
   pub with_fallback: bool,
   pub is_custom_element: Box<dyn Fn(String) -> bool + 'a>,
   pub on_error: Box<dyn Fn(ErrorCodes, Span) + 'a>,
+  pub directive_transforms: HashMap<String, DirectiveTransform<'a>>,
   pub source_map: bool,
+  /// Source map of `source` when it was generated from another file, the
+  /// output map is chained through it to point at the original source.
+  pub input_source_map: Option<&'a str>,
   pub filename: &'a str,
   pub source_type: SourceType,
   pub interop: bool,
+  /// Compiles the JSX in vDOM mode to `createVNode` calls instead of leaving
+  /// it to another JSX plugin.
+  pub vdom: bool,
   pub hmr: bool,
   pub ssr: bool,
+  pub dump_ir: bool,
+  /// Module each runtime helper is imported from, keyed by the helper name.
+  pub runtime_modules: HashMap<String, String>,
+  /// Global variable of each runtime module for `ImportStyle::Global`,
+  /// defaults to the PascalCased module name, e.g. `Vue` for `vue`.
+  pub runtime_globals: HashMap<String, String>,
+  pub import_style: ImportStyle,
+  /// Declares templates right before the top-level statement that uses them
+  /// and doesn't share them between statements, so that unused components
+  /// are tree-shaken together with their templates.
+  pub per_component_templates: bool,
+  /// Imports templates from this module instead of declaring them, so that
+  /// identical templates are shared between files. Its content is generated
+  /// by `generate_template_module`.
+  pub template_module: Option<&'a str>,
+  /// Functions that return the component they wrap, e.g. `memoComponent` in
+  /// `memoComponent(defineVaporComponent(...))`, so that wrapped components
+  /// are still registered for HMR and SSR.
+  pub component_wrappers: Vec<String>,
+  /// Functions that define Vapor components, `defineVaporComponent` by default.
+  pub vapor_factories: Vec<String>,
+  /// Functions that define vDOM components, `defineComponent` by default.
+  pub vdom_factories: Vec<String>,
+  /// Modules the factories are imported from, calls of factories imported
+  /// from other modules are ignored.
+  pub factory_modules: Vec<String>,
+  /// Local bindings of the factories, resolved from the imports of the file.
+  pub factories: RefCell<Factories>,
+  /// Names that the generated bindings avoid besides the ones of the file,
+  /// e.g. the bindings of the other `<script>` block of an SFC.
+  pub reserved_names: Vec<String>,
+  /// Components, custom directives, template refs and HMR ids used by the
+  /// file, completed with the helpers, delegates and templates at the end.
+  pub metadata: RefCell<TransformMetadata>,
 }
 impl<'a> Default for TransformOptions<'a> {
   fn default() -> Self {
+    static DEFAULT_CONFIG: LazyLock<TransformConfig> = LazyLock::new(TransformConfig::default);
+    DEFAULT_CONFIG.options("index.jsx")
+  }
+}
+
+/// The settings of `TransformOptions` shared by the files of a build, i.e.
+/// without the per-file state and the callbacks. Every entry point converts
+/// its options to a config and creates the options of each file from it, the
+/// fields are documented on `TransformOptions`.
+#[derive(Debug, Clone)]
+pub struct TransformConfig {
+  pub with_fallback: bool,
+  pub source_map: bool,
+  pub input_source_map: Option<String>,
+  pub interop: bool,
+  pub vdom: bool,
+  pub hmr: bool,
+  pub ssr: bool,
+  pub dump_ir: bool,
+  pub runtime_modules: HashMap<String, String>,
+  pub runtime_globals: HashMap<String, String>,
+  pub import_style: ImportStyle,
+  pub per_component_templates: bool,
+  pub template_module: Option<String>,
+  pub component_wrappers: Vec<String>,
+  pub vapor_factories: Vec<String>,
+  pub vdom_factories: Vec<String>,
+  pub factory_modules: Vec<String>,
+}
+impl Default for TransformConfig {
+  fn default() -> Self {
+    TransformConfig {
+      with_fallback: false,
+      source_map: false,
+      input_source_map: None,
+      interop: false,
+      vdom: false,
+      hmr: false,
+      ssr: false,
+      dump_ir: false,
+      runtime_modules: HashMap::new(),
+      runtime_globals: HashMap::new(),
+      import_style: ImportStyle::Named,
+      per_component_templates: false,
+      template_module: None,
+      component_wrappers: vec![],
+      vapor_factories: default_vapor_factories(),
+      vdom_factories: default_vdom_factories(),
+      factory_modules: default_factory_modules(),
+    }
+  }
+}
+
+impl TransformConfig {
+  /// Options to transform `filename` with this config, without callbacks.
+  pub fn options<'a>(&'a self, filename: &'a str) -> TransformOptions<'a> {
     TransformOptions {
-      filename: "index.jsx",
-      source_type: SourceType::jsx(),
+      filename,
+      source_type: SourceType::from_path(filename).unwrap_or_default(),
       templates: RefCell::new(vec![]),
       helpers: RefCell::new(BTreeSet::new()),
       delegates: RefCell::new(BTreeSet::new()),
-      source_map: false,
-      with_fallback: false,
+      ir: RefCell::new(vec![]),
+      source_map: self.source_map,
+      input_source_map: self.input_source_map.as_deref(),
+      with_fallback: self.with_fallback,
       is_custom_element: Box::new(|_| false),
       on_error: Box::new(|_, _| {}),
-      interop: false,
-      hmr: false,
-      ssr: false,
+      directive_transforms: HashMap::new(),
+      interop: self.interop,
+      vdom: self.vdom,
+      hmr: self.hmr,
+      ssr: self.ssr,
+      dump_ir: self.dump_ir,
+      runtime_modules: self.runtime_modules.clone(),
+      runtime_globals: self.runtime_globals.clone(),
+      import_style: self.import_style,
+      per_component_templates: self.per_component_templates,
+      template_module: self.template_module.as_deref(),
+      component_wrappers: self.component_wrappers.clone(),
+      vapor_factories: self.vapor_factories.clone(),
+      vdom_factories: self.vdom_factories.clone(),
+      factory_modules: self.factory_modules.clone(),
+      factories: RefCell::new(Factories::default()),
+      reserved_names: vec![],
+      metadata: RefCell::new(TransformMetadata::default()),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
+impl From<&mut CompilerOptions> for TransformConfig {
+  /// Takes the settings of the options passed from JS, the filename and the
+  /// callbacks are left to the entry point.
+  fn from(options: &mut CompilerOptions) -> Self {
+    TransformConfig {
+      with_fallback: options.with_fallback.unwrap_or(false),
+      source_map: options.source_map.unwrap_or(false),
+      input_source_map: options.input_source_map.take(),
+      interop: options.interop.unwrap_or(false),
+      vdom: options.vdom.unwrap_or(false),
+      hmr: options.hmr.unwrap_or(false),
+      ssr: options.ssr.unwrap_or(false),
+      dump_ir: options.dump_ir.unwrap_or(false),
+      runtime_modules: options.runtime_modules.take().unwrap_or_default(),
+      runtime_globals: options.runtime_globals.take().unwrap_or_default(),
+      import_style: options.import_style.unwrap_or_default(),
+      per_component_templates: options.per_component_templates.unwrap_or(false),
+      template_module: options.template_module.take(),
+      component_wrappers: options.component_wrappers.take().unwrap_or_default(),
+      vapor_factories: options
+        .vapor_factories
+        .take()
+        .unwrap_or_else(default_vapor_factories),
+      vdom_factories: options
+        .vdom_factories
+        .take()
+        .unwrap_or_else(default_vdom_factories),
+      factory_modules: options
+        .factory_modules
+        .take()
+        .unwrap_or_else(default_factory_modules),
     }
   }
 }
 
+impl<'a> TransformOptions<'a> {
+  pub fn take_ir(&self) -> Option<String> {
+    self
+      .dump_ir
+      .then(|| format!("[{}]", self.ir.take().join(",")))
+  }
+}
+
 pub struct DirectiveTransformResult<'a> {
   pub key: SimpleExpressionNode<'a>,
   pub value: SimpleExpressionNode<'a>,
@@ -130,6 +320,12 @@ pub struct TransformContext<'a> {
   pub seen: Rc<RefCell<HashSet<u32>>>,
 
   global_id: RefCell<i32>,
+  /// Templates before this index are not reused by the current JSX root.
+  pub template_start: RefCell<usize>,
+  /// Collision-free names of the bindings generated for helpers and templates.
+  pub names: RefCell<UniqueNames>,
+  /// Collision-free names of the locals generated for the current JSX root.
+  pub locals: RefCell<UniqueNames>,
 
   pub ir: Rc<RefCell<RootIRNode<'a>>>,
   pub node: RefCell<ContextNode<'a>>,
@@ -149,6 +345,9 @@ impl<'a> TransformContext<'a> {
       slots: RefCell::new(Vec::new()),
       seen: Rc::new(RefCell::new(HashSet::new())),
       global_id: RefCell::new(0),
+      template_start: RefCell::new(0),
+      names: RefCell::new(UniqueNames::default()),
+      locals: RefCell::new(UniqueNames::default()),
       node: RefCell::new(Either::A(RootNode::new(allocator))),
       parent_dynamic: RefCell::new(IRDynamicInfo::new()),
       ir: Rc::new(RefCell::new(RootIRNode::new(""))),
@@ -159,6 +358,8 @@ impl<'a> TransformContext<'a> {
 
   pub fn transform(&'a self, expression: Expression<'a>, source: &'a str) -> Expression<'a> {
     let allocator = self.allocator;
+    let span = expression.span();
+    *self.locals.borrow_mut() = UniqueNames::from_names(collect_names(&expression));
     let mut ir = RootIRNode::new(source);
     *self.node.borrow_mut() = Either::A(RootNode::from(allocator, expression));
     *self.block.borrow_mut() = mem::take(&mut ir.block);
@@ -171,10 +372,23 @@ impl<'a> TransformContext<'a> {
     *self.in_v_for.borrow_mut() = 0;
     *self.parent_dynamic.borrow_mut() = IRDynamicInfo::new();
     self.transform_node(None, None);
+    if self.options.dump_ir {
+      self.dump_ir(span);
+    }
     let generate_context: *const CodegenContext = &CodegenContext::new(self);
     (unsafe { &*generate_context }).generate()
   }
 
+  fn dump_ir(&self, span: Span) {
+    let mut ir = self.ir.borrow_mut();
+    ir.block = self.block.take();
+    match serde_json::to_string(&*ir) {
+      Ok(json) => self.options.ir.borrow_mut().push(json),
+      Err(_) => (self.options.on_error)(ErrorCodes::XIrSerializationFailed, span),
+    }
+    *self.block.borrow_mut() = mem::take(&mut ir.block);
+  }
+
   pub fn increase_id(&self) -> i32 {
     let current = *self.global_id.borrow();
     *self.global_id.borrow_mut() += 1;
@@ -252,14 +466,16 @@ impl<'a> TransformContext<'a> {
     let root_template_index = ir.root_template_index;
     let len = self.options.templates.borrow().len();
     let root = root_template_index.map(|i| i.eq(&len)).unwrap_or(false);
+    let start = *self.template_start.borrow();
     let existing = self
       .options
       .templates
       .borrow()
       .iter()
+      .skip(start)
       .position(|i| i.0.eq(&content) && i.1.eq(&root));
     if let Some(existing) = existing {
-      return existing as i32;
+      return (start + existing) as i32;
     }
     self.options.templates.borrow_mut().push((content, root));
     len as i32
@@ -349,6 +565,95 @@ impl<'a> TransformContext<'a> {
     }
   }
 
+  /// Reports `code` and returns `(() => { throw new Error(message) })()`, which
+  /// takes the place of the broken node so that it fails when it's rendered
+  /// while the rest of the file still compiles.
+  pub fn error_placeholder(&self, code: ErrorCodes, span: Span) -> Expression<'a> {
+    self.options.on_error.as_ref()(code, span);
+    let ast = AstBuilder::new(self.allocator);
+    let message = ERROR_MESSAGES.get(&code).copied().unwrap_or_default();
+    ast.expression_call(
+      SPAN,
+      ast.expression_parenthesized(
+        SPAN,
+        ast.expression_arrow_function(
+          SPAN,
+          false,
+          false,
+          NONE,
+          ast.formal_parameters(
+            SPAN,
+            FormalParameterKind::ArrowFormalParameters,
+            ast.vec(),
+            NONE,
+          ),
+          NONE,
+          ast.function_body(
+            SPAN,
+            ast.vec(),
+            ast.vec1(ast.statement_throw(
+              SPAN,
+              ast.expression_new(
+                SPAN,
+                ast.expression_identifier(SPAN, "Error"),
+                NONE,
+                ast.vec1(Argument::StringLiteral(ast.alloc_string_literal(
+                  SPAN,
+                  ast.atom(message),
+                  None,
+                ))),
+              ),
+            )),
+          ),
+        ),
+      ),
+      NONE,
+      ast.vec(),
+      false,
+    )
+  }
+
+  /// Replaces `context_node` with an empty `v-if` whose condition is the
+  /// error placeholder of `code`, so it's inserted like any other dynamic node.
+  /// Its `v-else` branch is taken as well, so that no sibling `v-else` attaches to it.
+  pub fn replace_with_error(
+    &self,
+    context_node: &mut ContextNode<'a>,
+    context_block: &mut BlockIRNode<'a>,
+    code: ErrorCodes,
+    span: Span,
+  ) {
+    let placeholder = self.error_placeholder(code, span);
+    let dynamic = &mut context_block.dynamic;
+    dynamic.flags |= DynamicFlag::NonTemplate as i32 | DynamicFlag::Insert as i32;
+    let id = self.reference(dynamic);
+    dynamic.operation = Some(Box::new(OperationNode::If(IfIRNode {
+      id,
+      positive: BlockIRNode::new(),
+      once: false,
+      condition: SimpleExpressionNode {
+        content: String::new(),
+        is_static: false,
+        loc: span,
+        ast: Some(self.allocator.alloc(placeholder)),
+      },
+      negative: Some(Box::new(Either::A(BlockIRNode::new()))),
+      anchor: None,
+      parent: None,
+    })));
+    let node_span = match context_node {
+      Either::B(node) => node.span(),
+      Either::A(_) => SPAN,
+    };
+    *context_node = Either::B(JSXChild::ExpressionContainer(oxc_allocator::Box::new_in(
+      JSXExpressionContainer {
+        span: node_span,
+        expression: JSXExpression::EmptyExpression(JSXEmptyExpression { span: node_span }),
+      },
+      self.allocator,
+    )));
+  }
+
   pub fn create_block(
     &'a self,
     context_node: &mut ContextNode<'a>,
@@ -447,70 +752,169 @@ impl<'a> TransformContext<'a> {
   }
 }
 
-#[cfg(feature = "napi")]
-#[napi(object)]
+#[cfg_attr(feature = "napi", napi(object))]
 pub struct TransformReturn {
   pub code: String,
   pub map: Option<String>,
+  /// JSON array of the IR of each JSX root, available when `dump_ir` is enabled.
+  pub ir: Option<String>,
+  pub metadata: TransformMetadata,
+}
+
+/// What a file uses, e.g. for auto-import plugins and bundle analyzers.
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TransformMetadata {
+  /// Component tags in source order, including the ones of dynamic components.
+  pub components: Vec<ComponentUsage>,
+  /// Custom directives without the `v-` prefix, e.g. `focus` for `v-focus`.
+  pub directives: Vec<String>,
+  /// Runtime helpers imported by the output, e.g. `template`.
+  pub helpers: Vec<String>,
+  /// Events delegated with `delegateEvents`.
+  pub delegates: Vec<String>,
+  pub template_count: u32,
+  /// Whether an element or component has a `ref`.
+  pub has_template_ref: bool,
+  /// `__hmrId`s of the components registered for HMR.
+  pub hmr_ids: Vec<String>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentUsage {
+  /// Tag name as written, e.g. `Foo.Bar` or `my-comp`.
+  pub name: String,
+  /// Span of the tag name in the opening tag.
+  pub loc: (u32, u32),
+}
+
+impl TransformMetadata {
+  /// Merges the metadata of another file or block into this one.
+  pub fn extend(&mut self, other: TransformMetadata) {
+    self.components.extend(other.components);
+    for (names, other) in [
+      (&mut self.directives, other.directives),
+      (&mut self.helpers, other.helpers),
+      (&mut self.delegates, other.delegates),
+    ] {
+      for name in other {
+        if !names.contains(&name) {
+          names.push(name);
+        }
+      }
+    }
+    self.template_count += other.template_count;
+    self.has_template_ref |= other.has_template_ref;
+    self.hmr_ids.extend(other.hmr_ids);
+  }
 }
 
 #[cfg(feature = "napi")]
 #[napi]
 pub fn _transform(env: Env, source: String, options: Option<CompilerOptions>) -> TransformReturn {
-  use crate::utils::error::ErrorCodes;
-  let options = options.unwrap_or_default();
-  let filename = &options.filename.unwrap_or("index.jsx".to_string());
-  let CodegenReturn { code, map, .. } = transform(
+  let mut options = options.unwrap_or_default();
+  let filename = options.filename.take().unwrap_or("index.jsx".to_string());
+  let config = TransformConfig::from(&mut options);
+  transform_file(
     &source,
-    Some(TransformOptions {
-      filename,
-      source_type: SourceType::from_path(filename).unwrap(),
-      templates: RefCell::new(vec![]),
-      helpers: RefCell::new(BTreeSet::new()),
-      delegates: RefCell::new(BTreeSet::new()),
-      source_map: options.source_map.unwrap_or(false),
-      with_fallback: options.with_fallback.unwrap_or(false),
-      interop: options.interop.unwrap_or(false),
-      hmr: options.hmr.unwrap_or(false),
-      ssr: options.ssr.unwrap_or(false),
-      is_custom_element: if let Some(is_custom_element) = options.is_custom_element {
-        Box::new(move |tag: String| is_custom_element.call(tag).unwrap())
-          as Box<dyn Fn(String) -> bool>
-      } else {
-        Box::new(|_: String| false) as Box<dyn Fn(String) -> bool>
-      },
-      on_error: if let Some(on_error) = options.on_error {
-        use crate::utils::error::create_compiler_error;
+    Some(napi_transform_options(env, &config, options, &filename)),
+  )
+}
 
-        Box::new(move |code: ErrorCodes, span: Span| {
-          let compiler_error = create_compiler_error(&env, code, span).unwrap();
-          on_error.call(compiler_error).unwrap();
-        }) as Box<dyn Fn(ErrorCodes, Span)>
-      } else {
-        Box::new(|_: ErrorCodes, _: Span| {}) as Box<dyn Fn(ErrorCodes, Span)>
-      },
-    }),
-  );
+/// Options of `filename` with the callbacks passed from JS, shared by the
+/// entries that transform a single file.
+#[cfg(feature = "napi")]
+pub(crate) fn napi_transform_options<'a>(
+  env: Env,
+  config: &'a TransformConfig,
+  options: CompilerOptions,
+  filename: &'a str,
+) -> TransformOptions<'a> {
+  use crate::transform::custom_directive::{CustomDirective, DirectiveTransform};
+  use crate::utils::error::{ErrorCodes, create_compiler_error};
+  TransformOptions {
+    is_custom_element: match options.is_custom_element {
+      Some(is_custom_element) => {
+        Box::new(move |tag: String| is_custom_element.call(tag).unwrap_or(false))
+      }
+      None => Box::new(|_: String| false),
+    },
+    on_error: match options.on_error {
+      Some(on_error) => Box::new(move |code: ErrorCodes, span: Span| {
+        if let Ok(compiler_error) = create_compiler_error(&env, code, span, filename) {
+          on_error.call(compiler_error).ok();
+        }
+      }),
+      None => Box::new(|_: ErrorCodes, _: Span| {}),
+    },
+    directive_transforms: options
+      .directive_transforms
+      .unwrap_or_default()
+      .into_iter()
+      .map(|(name, directive_transform)| {
+        (
+          name,
+          Box::new(move |dir: CustomDirective| directive_transform.call(dir).ok().flatten())
+            as DirectiveTransform,
+        )
+      })
+      .collect(),
+    ..config.options(filename)
+  }
+}
+
+pub fn transform(source: &str, options: Option<TransformOptions>) -> CodegenReturn {
+  transform_and_generate(source, options).0
+}
+
+/// Like [`transform`], but also returns the IR dump and the metadata of the
+/// transform, with the source map serialized to JSON.
+pub fn transform_file(source: &str, options: Option<TransformOptions>) -> TransformReturn {
+  let (CodegenReturn { code, map, .. }, TransformProgramReturn { ir, metadata, .. }) =
+    transform_and_generate(source, options);
   TransformReturn {
     code,
-    map: map.map(|m| m.to_json_string()),
+    map: map.map(|map| map.to_json_string()),
+    ir,
+    metadata,
   }
 }
 
-pub fn transform(source: &str, options: Option<TransformOptions>) -> CodegenReturn {
+fn transform_and_generate(
+  source: &str,
+  options: Option<TransformOptions>,
+) -> (CodegenReturn, TransformProgramReturn) {
   use oxc_codegen::CodegenOptions;
   let options = options.unwrap_or_default();
   let filename = options.filename;
   let source_map = options.source_map;
+  let input_source_map = options
+    .input_source_map
+    .filter(|_| source_map)
+    .and_then(|input_source_map| parse_input_source_map(input_source_map, &*options.on_error));
   let source_type = options.source_type;
   let allocator = Allocator::default();
-  let mut program = Parser::new(&allocator, source, source_type).parse().program;
+  let ParserReturn {
+    mut program,
+    errors,
+    ..
+  } = Parser::new(&allocator, source, source_type).parse();
+  for error in errors {
+    let span = error
+      .labels
+      .as_ref()
+      .and_then(|labels| labels.first())
+      .map(|label| Span::sized(label.offset() as u32, label.len() as u32))
+      .unwrap_or_default();
+    (options.on_error)(ErrorCodes::XSyntaxError, span);
+  }
   let context = TransformContext::new(&allocator, &options);
-  JsxTraverse::new(&allocator, &context).traverse(&mut program);
-  Codegen::new()
+  let result = transform_in_place(&mut program, &context, None);
+  let mut codegen = Codegen::new()
     .with_options(CodegenOptions {
       source_map_path: if source_map {
-        Some(PathBuf::from(&filename))
+        Some(PathBuf::from(filename))
       } else {
         None
       },
@@ -518,5 +922,73 @@ pub fn transform(source: &str, options: Option<TransformOptions>) -> CodegenRetu
       indent_char: IndentChar::Space,
       ..CodegenOptions::default()
     })
-    .build(&program)
+    .build(&program);
+  if let Some(input_source_map) = input_source_map {
+    codegen.map = codegen
+      .map
+      .map(|map| chain_source_map(map, &input_source_map));
+  }
+  (codegen, result)
+}
+
+pub struct TransformProgramReturn {
+  pub helpers: BTreeSet<String>,
+  pub templates: Vec<Template>,
+  pub delegates: BTreeSet<String>,
+  /// JSON array of the IR of each JSX root, available when `dump_ir` is enabled.
+  pub ir: Option<String>,
+  pub metadata: TransformMetadata,
+  /// Scoping of the transformed program, can be passed on to later oxc passes.
+  pub scoping: Scoping,
+}
+
+/// Transforms an already parsed `program` in place, for pipelines that run
+/// other oxc passes and a single codegen afterwards. The helper imports,
+/// templates and delegated events are injected into the program as well, and
+/// the returned scoping is rebuilt so that it includes their bindings.
+///
+/// `context` is created with `TransformContext::new(&allocator, &options)`
+/// and has to live as long as the program.
+pub fn transform_program<'a>(
+  program: &mut Program<'a>,
+  context: &'a TransformContext<'a>,
+  scoping: Option<Scoping>,
+) -> TransformProgramReturn {
+  let result = transform_in_place(program, context, scoping);
+  TransformProgramReturn {
+    scoping: SemanticBuilder::new()
+      .build(program)
+      .semantic
+      .into_scoping(),
+    ..result
+  }
+}
+
+/// [`transform_program`] without rebuilding the scoping, which is stale
+/// after the transform.
+fn transform_in_place<'a>(
+  program: &mut Program<'a>,
+  context: &'a TransformContext<'a>,
+  scoping: Option<Scoping>,
+) -> TransformProgramReturn {
+  let scoping =
+    JsxTraverse::new(context.allocator, context).traverse_with_scoping(program, scoping);
+  let options = context.options;
+  let helpers = options.helpers.take();
+  let templates = options.templates.take();
+  let delegates = options.delegates.take();
+  let metadata = TransformMetadata {
+    helpers: helpers.iter().cloned().collect(),
+    delegates: delegates.iter().cloned().collect(),
+    template_count: templates.len() as u32,
+    ..options.metadata.take()
+  };
+  TransformProgramReturn {
+    helpers,
+    templates,
+    delegates,
+    ir: options.take_ir(),
+    metadata,
+    scoping,
+  }
 }
@@ -0,0 +1,204 @@
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+use oxc_ast::ast::{JSXAttribute, JSXAttributeName, JSXElement};
+use oxc_span::SPAN;
+
+use crate::ir::index::OperationNode;
+use crate::utils::either::Either;
+use crate::{
+  ir::index::{BlockIRNode, SetEventIRNode, SimpleExpressionNode},
+  transform::{
+    DirectiveTransformResult, TransformContext,
+    transform_element::register_custom_directive,
+    v_on::{DELEGATED_EVENTS, resolve_modifiers},
+  },
+  utils::{check::is_jsx_component, directive::resolve_directive},
+};
+
+/// The directive passed to a user-provided directive transform.
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug)]
+pub struct CustomDirective {
+  /// The directive name without the `v-` prefix, e.g. `focus` for `v-focus`.
+  pub name: String,
+  pub arg: Option<String>,
+  pub modifiers: Vec<String>,
+  /// The source text of the directive value.
+  pub exp: Option<String>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Default)]
+pub struct CustomDirectiveProp {
+  pub key: String,
+  /**
+   * JavaScript expression of the prop value.
+   * Defaults to the directive expression.
+   */
+  pub value: Option<String>,
+  /**
+   * Whether `value` is a plain string instead of an expression.
+   * @default false
+   */
+  pub is_static: Option<bool>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Default)]
+pub struct CustomDirectiveEvent {
+  /// The event name, e.g. `click`.
+  pub name: String,
+  /**
+   * JavaScript expression of the event handler.
+   * Defaults to the directive expression.
+   */
+  pub value: Option<String>,
+  pub modifiers: Option<Vec<String>>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Default)]
+pub struct CustomDirectiveResult {
+  pub props: Option<Vec<CustomDirectiveProp>>,
+  pub events: Option<Vec<CustomDirectiveEvent>>,
+  /**
+   * Whether the directive should still be applied at runtime.
+   * @default false
+   */
+  pub need_runtime: Option<bool>,
+}
+
+pub type DirectiveTransform<'a> =
+  Box<dyn Fn(CustomDirective) -> Option<CustomDirectiveResult> + 'a>;
+
+pub fn get_directive_transform_name(prop: &JSXAttribute) -> Option<String> {
+  let name = match &prop.name {
+    JSXAttributeName::Identifier(name) => name.name.as_str(),
+    JSXAttributeName::NamespacedName(name) => name.namespace.name.as_str(),
+  }
+  .split('_')
+  .next()
+  .unwrap_or_default();
+  name.strip_prefix("v-").map(|name| name.to_string())
+}
+
+pub fn transform_custom_directive<'a>(
+  name: String,
+  prop: &'a mut JSXAttribute<'a>,
+  node: &JSXElement,
+  context: &'a TransformContext<'a>,
+  context_block: &'a mut BlockIRNode<'a>,
+  get_operation_index: Rc<RefCell<Box<dyn FnMut() -> i32 + 'a>>>,
+) -> Vec<DirectiveTransformResult<'a>> {
+  let Some(directive_transform) = context.options.directive_transforms.get(&name) else {
+    return vec![];
+  };
+  let mut dir = resolve_directive(prop, context);
+  let Some(result) = directive_transform(CustomDirective {
+    name: name.clone(),
+    arg: dir.arg.as_ref().map(|arg| arg.content.clone()),
+    modifiers: dir.modifiers.iter().map(|m| m.content.clone()).collect(),
+    exp: dir.exp.as_ref().map(|exp| exp.content.clone()),
+  }) else {
+    return vec![];
+  };
+
+  let need_runtime = result.need_runtime.unwrap_or_default();
+  // the directive expression keeps its AST for the first binding that uses it,
+  // unless the runtime directive needs it.
+  let cloned_exp = dir.exp.clone();
+  let mut exp = if need_runtime { None } else { dir.exp.take() };
+  let mut resolve_value = |value: Option<String>, is_static: bool| {
+    if let Some(value) = value {
+      SimpleExpressionNode {
+        content: value,
+        is_static,
+        loc: SPAN,
+        ast: None,
+      }
+    } else if let Some(exp) = exp.take() {
+      exp
+    } else {
+      cloned_exp.clone().unwrap_or_default()
+    }
+  };
+
+  let is_component = is_jsx_component(node);
+  let mut results = vec![];
+  for prop in result.props.unwrap_or_default() {
+    let value = resolve_value(prop.value, prop.is_static.unwrap_or_default());
+    results.push(DirectiveTransformResult::new(
+      SimpleExpressionNode {
+        content: prop.key,
+        is_static: true,
+        loc: SPAN,
+        ast: None,
+      },
+      value,
+    ));
+  }
+
+  let context_block = context_block as *mut BlockIRNode;
+  for event in result.events.unwrap_or_default() {
+    let value = resolve_value(event.value, false);
+    let key = SimpleExpressionNode {
+      content: event.name,
+      is_static: true,
+      loc: SPAN,
+      ast: None,
+    };
+    let modifiers = resolve_modifiers(
+      Either::B(format!("on{}", key.content)),
+      event
+        .modifiers
+        .unwrap_or_default()
+        .into_iter()
+        .map(|modifier| SimpleExpressionNode {
+          content: modifier,
+          is_static: true,
+          loc: SPAN,
+          ast: None,
+        })
+        .collect(),
+    );
+    if is_component {
+      results.push(DirectiveTransformResult {
+        handler: Some(true),
+        handler_modifiers: Some(modifiers),
+        ..DirectiveTransformResult::new(key, value)
+      });
+      continue;
+    }
+
+    let delegate = modifiers.options.is_empty() && DELEGATED_EVENTS.contains(key.content.as_str());
+    let element = context.reference(&mut unsafe { &mut *context_block }.dynamic);
+    context.register_effect(
+      unsafe { &mut *context_block },
+      true,
+      OperationNode::SetEvent(SetEventIRNode {
+        set_event: true,
+        element,
+        key,
+        value: Some(value),
+        modifiers,
+        delegate,
+        effect: false,
+      }),
+      None,
+      None,
+    );
+  }
+
+  if need_runtime {
+    register_custom_directive(
+      name,
+      dir,
+      context,
+      unsafe { &mut *context_block },
+      get_operation_index,
+    );
+  }
+  results
+}
@@ -0,0 +1,309 @@
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  panic::{self, AssertUnwindSafe},
+  sync::mpsc::channel,
+  thread,
+};
+
+use napi::{
+  Env, Error, Result, Status, Task,
+  bindgen_prelude::{AsyncTask, FromNapiValue, FunctionRef, JsValuesTupleIntoVec, Object},
+  threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
+use oxc_span::Span;
+
+use crate::{
+  compile::CompilerOptions,
+  transform::{
+    TransformConfig, TransformOptions, TransformReturn,
+    custom_directive::{CustomDirective, CustomDirectiveResult, DirectiveTransform},
+    transform_file,
+  },
+  utils::error::{ErrorCodes, create_compiler_error},
+};
+
+type Tsfn<T, R> = ThreadsafeFunction<T, R, T, Status, false>;
+
+#[napi(object)]
+pub struct TransformFile {
+  pub source: String,
+  /// Filename of the source, defaults to `options.filename`.
+  pub filename: Option<String>,
+  /// Source map of the source, defaults to `options.input_source_map`.
+  pub input_source_map: Option<String>,
+}
+
+/**
+ * `CompilerOptions` whose JS callbacks can be called from other threads.
+ * `is_custom_element` and `directive_transforms` block the compiling thread
+ * until the main thread returns, `on_error` is called after compiling.
+ */
+pub struct ThreadsafeCompilerOptions {
+  filename: String,
+  config: TransformConfig,
+  is_custom_element: Option<Tsfn<String, bool>>,
+  directive_transforms: HashMap<String, Tsfn<CustomDirective, Option<CustomDirectiveResult>>>,
+  on_error: Option<FunctionRef<Object<'static>, ()>>,
+}
+
+impl ThreadsafeCompilerOptions {
+  pub fn new(mut options: CompilerOptions) -> Result<Self> {
+    Ok(ThreadsafeCompilerOptions {
+      filename: options.filename.take().unwrap_or("index.jsx".to_string()),
+      config: TransformConfig::from(&mut options),
+      is_custom_element: options
+        .is_custom_element
+        .map(|is_custom_element| is_custom_element.build_threadsafe_function().build())
+        .transpose()?,
+      directive_transforms: options
+        .directive_transforms
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, directive_transform)| {
+          Ok((
+            name,
+            directive_transform.build_threadsafe_function().build()?,
+          ))
+        })
+        .collect::<Result<_>>()?,
+      on_error: options
+        .on_error
+        .map(|on_error| on_error.create_ref())
+        .transpose()?,
+    })
+  }
+
+  fn filename<'a>(&'a self, filename: Option<&'a str>) -> &'a str {
+    filename.unwrap_or(&self.filename)
+  }
+
+  fn transform(
+    &self,
+    source: &str,
+    filename: Option<&str>,
+    input_source_map: Option<&str>,
+  ) -> (TransformReturn, Vec<CompileError>) {
+    let errors = RefCell::new(vec![]);
+    let options = self.config.options(self.filename(filename));
+    let result = transform_file(
+      source,
+      Some(TransformOptions {
+        input_source_map: input_source_map.or(options.input_source_map),
+        is_custom_element: match &self.is_custom_element {
+          Some(is_custom_element) => {
+            Box::new(|tag: String| call_blocking(is_custom_element, tag).unwrap_or(false))
+          }
+          None => Box::new(|_: String| false),
+        },
+        on_error: Box::new(|code: ErrorCodes, span: Span| errors.borrow_mut().push((code, span))),
+        directive_transforms: self
+          .directive_transforms
+          .iter()
+          .map(|(name, directive_transform)| {
+            (
+              name.clone(),
+              Box::new(|dir: CustomDirective| call_blocking(directive_transform, dir).flatten())
+                as DirectiveTransform,
+            )
+          })
+          .collect(),
+        ..options
+      }),
+    );
+    (result, errors.into_inner())
+  }
+
+  /// Same as `transform`, but a panic becomes an error naming the file
+  /// instead of unwinding into the libuv thread pool and aborting the process.
+  fn try_transform(
+    &self,
+    source: &str,
+    filename: Option<&str>,
+    input_source_map: Option<&str>,
+  ) -> Result<(TransformReturn, Vec<CompileError>)> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+      self.transform(source, filename, input_source_map)
+    }))
+    .map_err(|panic| {
+      let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+      Error::new(
+        Status::GenericFailure,
+        format!(
+          "Failed to transform `{}`: {message}",
+          self.filename(filename)
+        ),
+      )
+    })
+  }
+
+  fn report_errors(&self, env: &Env, filename: &str, errors: Vec<CompileError>) -> Result<()> {
+    if let Some(on_error) = &self.on_error {
+      let on_error = on_error.borrow_back(env)?;
+      for (code, span) in errors {
+        on_error.call(create_compiler_error(env, code, span, filename)?)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+type CompileError = (ErrorCodes, Span);
+
+/// Calls a JS function from a compiling thread and waits for its return value.
+fn call_blocking<T: 'static + JsValuesTupleIntoVec, R: 'static + FromNapiValue + Send>(
+  tsfn: &Tsfn<T, R>,
+  value: T,
+) -> Option<R> {
+  let (sender, receiver) = channel();
+  tsfn.call_with_return_value(
+    value,
+    ThreadsafeFunctionCallMode::Blocking,
+    move |result, _| {
+      sender.send(result.ok()).ok();
+      Ok(())
+    },
+  );
+  receiver.recv().ok().flatten()
+}
+
+pub struct TransformTask {
+  source: String,
+  options: ThreadsafeCompilerOptions,
+}
+
+impl Task for TransformTask {
+  type Output = (TransformReturn, Vec<CompileError>);
+  type JsValue = TransformReturn;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.options.try_transform(&self.source, None, None)
+  }
+
+  fn resolve(&mut self, env: Env, (result, errors): Self::Output) -> Result<Self::JsValue> {
+    self
+      .options
+      .report_errors(&env, &self.options.filename, errors)?;
+    Ok(result)
+  }
+}
+
+/// Same as `transform`, but compiles on the libuv thread pool.
+#[napi]
+pub fn _transform_async(
+  source: String,
+  options: Option<CompilerOptions>,
+) -> Result<AsyncTask<TransformTask>> {
+  Ok(AsyncTask::new(TransformTask {
+    source,
+    options: ThreadsafeCompilerOptions::new(options.unwrap_or_default())?,
+  }))
+}
+
+/// The result of one file of `transformMany`, either `result` or `error` is set.
+#[napi(object)]
+pub struct TransformManyResult {
+  pub filename: String,
+  pub result: Option<TransformReturn>,
+  /// Why the file failed to compile, the other files are compiled regardless.
+  pub error: Option<String>,
+}
+
+pub struct TransformManyTask {
+  files: Vec<TransformFile>,
+  options: ThreadsafeCompilerOptions,
+}
+
+impl Task for TransformManyTask {
+  type Output = Vec<Result<(TransformReturn, Vec<CompileError>)>>;
+  type JsValue = Vec<TransformManyResult>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let files = &self.files;
+    let options = &self.options;
+    let threads = thread::available_parallelism()
+      .map(|threads| threads.get())
+      .unwrap_or(1);
+    let chunk_size = files.len().div_ceil(threads).max(1);
+    Ok(thread::scope(|scope| {
+      files
+        .chunks(chunk_size)
+        .map(|chunk| {
+          scope.spawn(move || {
+            chunk
+              .iter()
+              .map(|file| {
+                options.try_transform(
+                  &file.source,
+                  file.filename.as_deref(),
+                  file.input_source_map.as_deref(),
+                )
+              })
+              .collect::<Vec<_>>()
+          })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .zip(files.chunks(chunk_size))
+        .flat_map(|(handle, chunk)| {
+          // Panics are caught per file, so joining only fails if that did.
+          handle.join().unwrap_or_else(|_| {
+            chunk
+              .iter()
+              .map(|_| {
+                Err(Error::new(
+                  Status::GenericFailure,
+                  "A compiling thread panicked",
+                ))
+              })
+              .collect()
+          })
+        })
+        .collect()
+    }))
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    self
+      .files
+      .iter()
+      .zip(output)
+      .map(|(file, result)| {
+        let filename = self.options.filename(file.filename.as_deref()).to_string();
+        Ok(match result {
+          Ok((result, errors)) => {
+            self.options.report_errors(&env, &filename, errors)?;
+            TransformManyResult {
+              filename,
+              result: Some(result),
+              error: None,
+            }
+          }
+          Err(error) => TransformManyResult {
+            filename,
+            result: None,
+            error: Some(error.reason),
+          },
+        })
+      })
+      .collect()
+  }
+}
+
+/// Compiles many sources in parallel on Rust threads, results are in the same order as `files`.
+/// A file that fails to compile gets an `error` instead of a `result`.
+#[napi]
+pub fn _transform_many(
+  files: Vec<TransformFile>,
+  options: Option<CompilerOptions>,
+) -> Result<AsyncTask<TransformManyTask>> {
+  Ok(AsyncTask::new(TransformManyTask {
+    files,
+    options: ThreadsafeCompilerOptions::new(options.unwrap_or_default())?,
+  }))
+}
@@ -1,9 +1,10 @@
 use std::{collections::VecDeque, mem};
 
-use napi::{Either, bindgen_prelude::Either16};
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::ast::JSXChild;
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::Either;
 use crate::{
   ir::index::{BlockIRNode, DynamicFlag, IRDynamicInfo, InsertNodeIRNode},
   transform::{ContextNode, TransformContext},
@@ -160,7 +161,7 @@ fn register_insertion<'a>(
       // template node due to invalid nesting - generate actual insertion
       context.register_operation(
         context_block,
-        Either16::L(InsertNodeIRNode {
+        OperationNode::InsertNode(InsertNodeIRNode {
           insert_node: true,
           elements: ids.clone(),
           parent,
@@ -171,17 +172,17 @@ fn register_insertion<'a>(
     } else if let Some(operation) = &mut child.operation {
       // block types
       match operation.as_mut() {
-        Either16::A(if_ir_node) => {
+        OperationNode::If(if_ir_node) => {
           let parent = context.reference(&mut context_block.dynamic);
           if_ir_node.parent = Some(parent);
           if_ir_node.anchor = anchor;
         }
-        Either16::B(for_ir_node) => {
+        OperationNode::For(for_ir_node) => {
           let parent = context.reference(&mut context_block.dynamic);
           for_ir_node.parent = Some(parent);
           for_ir_node.anchor = anchor;
         }
-        Either16::N(create_component_ir_node) => {
+        OperationNode::CreateComponent(create_component_ir_node) => {
           let parent = context.reference(&mut context_block.dynamic);
           create_component_ir_node.parent = Some(parent);
           create_component_ir_node.anchor = anchor;
@@ -1,31 +1,35 @@
 use std::{cell::RefCell, rc::Rc};
 
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either16},
-};
 use oxc_ast::ast::{
   JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement,
   JSXElementName,
 };
-use oxc_span::SPAN;
+use oxc_span::{GetSpan, SPAN};
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::{
     component::{IRProp, IRProps, IRPropsDynamicExpression, IRPropsStatic},
     index::{
-      BlockIRNode, CreateComponentIRNode, DirectiveIRNode, DynamicFlag, SetDynamicEventsIRNode,
-      SetDynamicPropsIRNode, SetPropIRNode, SimpleExpressionNode,
+      BlockIRNode, CreateComponentIRNode, DirectiveIRNode, DirectiveNode, DynamicFlag,
+      SetDynamicEventsIRNode, SetDynamicPropsIRNode, SetPropIRNode, SimpleExpressionNode,
     },
   },
   transform::{
-    ContextNode, DirectiveTransformResult, TransformContext, v_bind::transform_v_bind,
-    v_html::transform_v_html, v_model::transform_v_model, v_on::transform_v_on,
-    v_show::transform_v_show, v_text::transform_v_text,
+    ComponentUsage, ContextNode, DirectiveTransformResult, TransformContext,
+    custom_directive::{get_directive_transform_name, transform_custom_directive},
+    v_bind::transform_v_bind,
+    v_html::transform_v_html,
+    v_model::transform_v_model,
+    v_on::transform_v_on,
+    v_show::transform_v_show,
+    v_slot::is_mixed_slot_usage,
+    v_text::transform_v_text,
   },
   utils::{
     check::{is_build_in_directive, is_jsx_component, is_template, is_void_tag},
-    directive::resolve_directive,
+    directive::{find_prop_mut, resolve_directive},
     dom::is_valid_html_nesting,
     error::ErrorCodes,
     text::get_text_like_value,
@@ -82,11 +86,41 @@ pub unsafe fn transform_element<'a>(
     current
   }) as Box<dyn FnMut() -> i32>));
 
-  let tag = get_tag_name(&node.opening_element.name, context);
   let is_component = is_jsx_component(node);
+  if is_component && is_mixed_slot_usage(node) {
+    let span = node.span;
+    context.replace_with_error(
+      unsafe { &mut *context_node },
+      context_block,
+      ErrorCodes::VSlotMixedSlotUsage,
+      span,
+    );
+    return None;
+  }
+  let tag = get_tag_name(&node.opening_element.name, context);
+  if is_component {
+    context
+      .options
+      .metadata
+      .borrow_mut()
+      .components
+      .push(ComponentUsage {
+        name: tag.clone(),
+        loc: {
+          let span = node.opening_element.name.span();
+          (span.start, span.end)
+        },
+      });
+  }
+  let node = node as *mut oxc_allocator::Box<JSXElement>;
+  let dynamic_component = if is_component {
+    resolve_dynamic_component(unsafe { &mut *node }, &tag, context)
+  } else {
+    None
+  };
   let _context_block = context_block as *mut BlockIRNode;
   let props_result = build_props(
-    node,
+    unsafe { &mut *node },
     context,
     unsafe { &mut *_context_block },
     is_component,
@@ -98,7 +132,14 @@ pub unsafe fn transform_element<'a>(
 
   Some(Box::new(move || {
     if is_component {
-      transform_component_element(tag, props_result, single_root, context, context_block);
+      transform_component_element(
+        tag,
+        props_result,
+        dynamic_component,
+        single_root,
+        context,
+        context_block,
+      );
     } else {
       transform_native_element(
         tag,
@@ -136,7 +177,7 @@ pub fn transform_native_element<'a>(
       context.register_effect(
         context_block,
         false,
-        Either16::E(SetDynamicPropsIRNode {
+        OperationNode::SetDynamicProps(SetDynamicPropsIRNode {
           set_dynamic_props: true,
           props,
           element,
@@ -162,7 +203,7 @@ pub fn transform_native_element<'a>(
           context.register_effect(
             context_block,
             context.is_operation(values.iter().collect::<Vec<&SimpleExpressionNode>>()),
-            Either16::D(SetPropIRNode {
+            OperationNode::SetProp(SetPropIRNode {
               set_prop: true,
               prop,
               element,
@@ -201,50 +242,85 @@ pub fn transform_native_element<'a>(
   }
 }
 
+/// Returns the prop that selects the component at runtime, `is` for
+/// `<component>` and `v-is` for the other tags.
+fn dynamic_component_prop(tag: &str) -> &'static str {
+  if tag == "component" { "is" } else { "v-is" }
+}
+
+/// Returns the component of `<component is={Comp}>`, `<component is="my-el">`
+/// or `<tr v-is={Row}>`.
+fn resolve_dynamic_component<'a>(
+  node: &'a mut JSXElement<'a>,
+  tag: &str,
+  context: &'a TransformContext<'a>,
+) -> Option<SimpleExpressionNode<'a>> {
+  let prop = find_prop_mut(node, Either::A(dynamic_component_prop(tag).to_string()))?;
+  let mut exp = SimpleExpressionNode::new(Either3::C(prop.value.as_mut()?), context);
+  if exp.is_empty() {
+    return None;
+  }
+  // e.g. `is={"my-el"}`
+  if exp.is_static
+    && let Some(value) = exp.get_literal_expression_value()
+  {
+    exp.content = value;
+  }
+  Some(exp)
+}
+
 pub fn transform_component_element<'a>(
   mut tag: String,
   props_result: PropsResult<'a>,
+  dynamic_component: Option<SimpleExpressionNode<'a>>,
   single_root: bool,
   context: &'a TransformContext<'a>,
   context_block: &mut BlockIRNode<'a>,
 ) {
   let mut asset = context.options.with_fallback;
 
-  if let Some(dot_index) = tag.find('.') {
-    let ns = tag[0..dot_index].to_string();
-    if !ns.is_empty() {
-      tag = ns + &tag[dot_index..];
+  if let Some(dynamic_component) = &dynamic_component {
+    // A string is resolved at runtime and falls back to an element.
+    asset = dynamic_component.is_static;
+  } else {
+    if let Some(dot_index) = tag.find('.') {
+      let ns = tag[0..dot_index].to_string();
+      if !ns.is_empty() {
+        tag = ns + &tag[dot_index..];
+      }
     }
-  }
 
-  if tag.contains("-") {
-    asset = true
-  }
+    if tag.contains("-") {
+      asset = true
+    }
 
-  if asset {
-    let component = &mut context.ir.borrow_mut().component;
-    component.insert(tag.clone());
+    if asset {
+      let component = &mut context.ir.borrow_mut().component;
+      component.insert(tag.clone());
+    }
   }
 
   let dynamic = &mut context_block.dynamic;
   dynamic.flags = dynamic.flags | DynamicFlag::NonTemplate as i32 | DynamicFlag::Insert as i32;
 
-  dynamic.operation = Some(Box::new(Either16::N(CreateComponentIRNode {
-    create_component: true,
-    id: context.reference(dynamic),
-    tag,
-    props: match props_result.props {
-      Either::A(props) => props,
-      Either::B(props) => vec![Either3::A(props)],
+  dynamic.operation = Some(Box::new(OperationNode::CreateComponent(
+    CreateComponentIRNode {
+      create_component: true,
+      id: context.reference(dynamic),
+      tag,
+      props: match props_result.props {
+        Either::A(props) => props,
+        Either::B(props) => vec![IRProps::Static(props)],
+      },
+      asset,
+      root: single_root && *context.in_v_for.borrow() == 0,
+      slots: context.slots.take(),
+      once: *context.in_v_once.borrow(),
+      parent: None,
+      anchor: None,
+      dynamic: dynamic_component,
     },
-    asset,
-    root: single_root && *context.in_v_for.borrow() == 0,
-    slots: context.slots.take(),
-    once: *context.in_v_once.borrow(),
-    parent: None,
-    anchor: None,
-    dynamic: None,
-  })));
+  )));
 }
 
 pub struct PropsResult<'a> {
@@ -271,16 +347,22 @@ pub fn build_props<'a>(
 
   let mut dynamic_args: Vec<IRProps> = vec![];
   let mut results: Vec<DirectiveTransformResult> = vec![];
+  let dynamic_component_prop = is_component.then(|| {
+    dynamic_component_prop(&get_tag_name(
+      &unsafe { &*node }.opening_element.name,
+      context,
+    ))
+  });
 
   for prop in props {
     match prop {
       JSXAttributeItem::SpreadAttribute(prop) => {
         let value = SimpleExpressionNode::new(Either3::A(&mut prop.argument), context);
         if !results.is_empty() {
-          dynamic_args.push(Either3::A(dedupe_properties(results)));
+          dynamic_args.push(IRProps::Static(dedupe_properties(results)));
           results = vec![];
         }
-        dynamic_args.push(Either3::C(IRPropsDynamicExpression {
+        dynamic_args.push(IRProps::Expression(IRPropsDynamicExpression {
           value,
           handler: None,
         }));
@@ -288,16 +370,19 @@ pub fn build_props<'a>(
       }
       JSXAttributeItem::Attribute(prop) => {
         let span = prop.span;
+        if dynamic_component_prop == Some(prop.name.get_identifier().name.as_str()) {
+          continue;
+        }
         if prop.name.get_identifier().name.eq("v-on") {
           // v-on={obj}
           if let Some(prop_value) = &mut prop.value {
             let value = SimpleExpressionNode::new(Either3::C(prop_value), context);
             if is_component {
               if !results.is_empty() {
-                dynamic_args.push(Either3::A(dedupe_properties(results)));
+                dynamic_args.push(IRProps::Static(dedupe_properties(results)));
                 results = vec![];
               }
-              dynamic_args.push(Either3::C(IRPropsDynamicExpression {
+              dynamic_args.push(IRProps::Expression(IRPropsDynamicExpression {
                 value,
                 handler: Some(true),
               }))
@@ -306,7 +391,7 @@ pub fn build_props<'a>(
               context.register_effect(
                 context_block,
                 context.is_operation(vec![&value]),
-                Either16::F(SetDynamicEventsIRNode {
+                OperationNode::SetDynamicEvents(SetDynamicEventsIRNode {
                   set_dynamic_events: true,
                   element,
                   value,
@@ -322,21 +407,37 @@ pub fn build_props<'a>(
         }
 
         let context_block = context_block as *mut BlockIRNode;
-        if let Some(prop) = transform_prop(
-          prop,
-          unsafe { &mut *node },
-          is_component,
-          context,
-          unsafe { &mut *context_block },
-          Rc::clone(&get_operation_index),
-        ) {
+        let props = if let Some(name) = get_directive_transform_name(prop)
+          && context.options.directive_transforms.contains_key(&name)
+        {
+          transform_custom_directive(
+            name,
+            prop,
+            unsafe { &*node },
+            context,
+            unsafe { &mut *context_block },
+            Rc::clone(&get_operation_index),
+          )
+        } else {
+          transform_prop(
+            prop,
+            unsafe { &mut *node },
+            is_component,
+            context,
+            unsafe { &mut *context_block },
+            Rc::clone(&get_operation_index),
+          )
+          .into_iter()
+          .collect()
+        };
+        for prop in props {
           if is_component && !prop.key.is_static {
             // v-model:&name&="value"
             if !results.is_empty() {
-              dynamic_args.push(Either3::A(dedupe_properties(results)));
+              dynamic_args.push(IRProps::Static(dedupe_properties(results)));
               results = vec![];
             }
-            dynamic_args.push(Either3::B(IRProp {
+            dynamic_args.push(IRProps::Dynamic(IRProp {
               key: prop.key,
               modifier: prop.modifier,
               runtime_camelize: prop.runtime_camelize,
@@ -360,7 +461,7 @@ pub fn build_props<'a>(
   if !dynamic_args.is_empty() || results.iter().any(|prop| !prop.key.is_static) {
     // take rest of props as dynamic props
     if !results.is_empty() {
-      dynamic_args.push(Either3::A(dedupe_properties(results)));
+      dynamic_args.push(IRProps::Static(dedupe_properties(results)));
     }
     return PropsResult {
       dynamic: true,
@@ -390,9 +491,10 @@ pub fn transform_prop<'a>(
   .collect::<Vec<&str>>()[0];
   let value = if let Some(value) = &prop.value {
     match value {
-      JSXAttributeValue::ExpressionContainer(value) => {
-        get_text_like_value(value.expression.to_expression(), Some(is_component))
-      }
+      JSXAttributeValue::ExpressionContainer(value) => value
+        .expression
+        .as_expression()
+        .and_then(|expression| get_text_like_value(expression, Some(is_component))),
       JSXAttributeValue::StringLiteral(value) => Some(value.value.to_string()),
       _ => None,
     }
@@ -428,7 +530,7 @@ pub fn transform_prop<'a>(
     ));
   }
 
-  let mut name = if is_event(name) {
+  let name = if is_event(name) {
     "on".to_string()
   } else if is_directive(name) {
     name[2..].to_string()
@@ -447,32 +549,54 @@ pub fn transform_prop<'a>(
   };
 
   if !is_build_in_directive(&name) {
-    let with_fallback = context.options.with_fallback;
-    if with_fallback {
-      let directive = &mut context.ir.borrow_mut().directive;
-      directive.insert(name.clone());
-    } else {
-      name = camelize(&format!("v-{name}"))
-    };
-
-    let element = context.reference(&mut context_block.dynamic);
-    context.register_operation(
+    register_custom_directive(
+      name,
+      resolve_directive(prop, context),
+      context,
       context_block,
-      Either16::M(DirectiveIRNode {
-        directive: true,
-        element,
-        dir: resolve_directive(prop, context),
-        name,
-        asset: Some(with_fallback),
-        builtin: None,
-        model_type: None,
-      }),
-      Some(Rc::clone(&get_operation_index)),
-    )
+      get_operation_index,
+    );
   }
   None
 }
 
+pub fn register_custom_directive<'a>(
+  mut name: String,
+  dir: DirectiveNode<'a>,
+  context: &'a TransformContext<'a>,
+  context_block: &mut BlockIRNode<'a>,
+  get_operation_index: Rc<RefCell<Box<dyn FnMut() -> i32 + 'a>>>,
+) {
+  {
+    let directives = &mut context.options.metadata.borrow_mut().directives;
+    if !directives.contains(&name) {
+      directives.push(name.clone());
+    }
+  }
+  let with_fallback = context.options.with_fallback;
+  if with_fallback {
+    let directive = &mut context.ir.borrow_mut().directive;
+    directive.insert(name.clone());
+  } else {
+    name = camelize(&format!("v-{name}"))
+  };
+
+  let element = context.reference(&mut context_block.dynamic);
+  context.register_operation(
+    context_block,
+    OperationNode::Directive(DirectiveIRNode {
+      directive: true,
+      element,
+      dir,
+      name,
+      asset: Some(with_fallback),
+      builtin: None,
+      model_type: None,
+    }),
+    Some(get_operation_index),
+  )
+}
+
 // Dedupe props in an object literal.
 // Literal duplicated attributes would have been warned during the parse phase,
 // however, it's possible to encounter duplicated `onXXX` handlers with different
@@ -1,9 +1,7 @@
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either16},
-};
 use oxc_ast::ast::JSXChild;
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::index::{BlockIRNode, DeclareOldRefIRNode, SetTemplateRefIRNode, SimpleExpressionNode},
   transform::{ContextNode, TransformContext},
@@ -31,6 +29,7 @@ pub unsafe fn transform_template_ref<'a>(
     return None;
   };
   context.ir.borrow_mut().has_template_ref = true;
+  context.options.metadata.borrow_mut().has_template_ref = true;
 
   let value = SimpleExpressionNode::new(Either3::C(value), context);
   Some(Box::new(move || {
@@ -39,7 +38,7 @@ pub unsafe fn transform_template_ref<'a>(
     if effect {
       context.register_operation(
         context_block,
-        Either16::O(DeclareOldRefIRNode {
+        OperationNode::DeclareOldRef(DeclareOldRefIRNode {
           declare_older_ref: true,
           id,
         }),
@@ -50,7 +49,7 @@ pub unsafe fn transform_template_ref<'a>(
     context.register_effect(
       context_block,
       context.is_operation(vec![&value]),
-      Either16::J(SetTemplateRefIRNode {
+      OperationNode::SetTemplateRef(SetTemplateRefIRNode {
         set_template_ref: true,
         element: id,
         value,
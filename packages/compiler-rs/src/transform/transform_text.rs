@@ -1,13 +1,11 @@
 use std::collections::HashSet;
 
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either16},
-};
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::ast::{ConditionalExpression, Expression, JSXChild, LogicalExpression};
 use oxc_span::GetSpan;
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::index::{
     BlockIRNode, CreateNodesIRNode, DynamicFlag, GetTextChildIRNode, IfIRNode, SetNodesIRNode,
@@ -214,7 +212,7 @@ fn process_interpolation<'a>(
   } {
     context.register_operation(
       context_block,
-      Either16::K(CreateNodesIRNode {
+      OperationNode::CreateNodes(CreateNodesIRNode {
         create_nodes: true,
         id,
         once,
@@ -227,7 +225,7 @@ fn process_interpolation<'a>(
     *template = template.to_string() + " ";
     context.register_operation(
       context_block,
-      Either16::G(SetNodesIRNode {
+      OperationNode::SetNodes(SetNodesIRNode {
         set_nodes: true,
         element: id,
         once,
@@ -261,7 +259,7 @@ fn process_text_container<'a>(
     let parent = context.reference(&mut context_block.dynamic);
     context.register_operation(
       context_block,
-      Either16::P(GetTextChildIRNode {
+      OperationNode::GetTextChild(GetTextChildIRNode {
         get_text_child: true,
         parent,
       }),
@@ -270,7 +268,7 @@ fn process_text_container<'a>(
     let element = context.reference(&mut context_block.dynamic);
     context.register_operation(
       context_block,
-      Either16::G(SetNodesIRNode {
+      OperationNode::SetNodes(SetNodesIRNode {
         set_nodes: true,
         element,
         once: *context.in_v_once.borrow(),
@@ -304,10 +302,9 @@ fn is_text_like(node: &JSXChild) -> bool {
     !matches!(
       node
         .expression
-        .to_expression()
-        .without_parentheses()
-        .get_inner_expression(),
-      Expression::ConditionalExpression(_) | Expression::LogicalExpression(_)
+        .as_expression()
+        .map(|expression| expression.without_parentheses().get_inner_expression()),
+      Some(Expression::ConditionalExpression(_) | Expression::LogicalExpression(_))
     )
   } else {
     matches!(node, JSXChild::Text(_))
@@ -363,7 +360,7 @@ pub fn process_conditional_expression<'a>(
       unsafe { &mut *_context_block },
       parent_node,
     );
-    context_block.dynamic.operation = Some(Box::new(Either16::A(operation)));
+    context_block.dynamic.operation = Some(Box::new(OperationNode::If(operation)));
   })
 }
 
@@ -418,7 +415,7 @@ fn process_logical_expression<'a>(
       parent_node,
     );
 
-    context_block.dynamic.operation = Some(Box::new(Either16::A(operation)));
+    context_block.dynamic.operation = Some(Box::new(OperationNode::If(operation)));
   })
 }
 
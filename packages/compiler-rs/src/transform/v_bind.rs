@@ -1,7 +1,7 @@
-use napi::bindgen_prelude::Either3;
 use oxc_ast::ast::{JSXAttribute, JSXAttributeName, JSXElement};
 use oxc_span::SPAN;
 
+use crate::utils::either::Either3;
 use crate::{
   ir::index::{BlockIRNode, SimpleExpressionNode},
   transform::{DirectiveTransformResult, TransformContext},
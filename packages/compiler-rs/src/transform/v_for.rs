@@ -1,12 +1,10 @@
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either16},
-};
 use oxc_allocator::TakeIn;
 use oxc_ast::ast::{
   BinaryExpression, Expression, JSXAttribute, JSXAttributeValue, JSXChild, JSXElement,
 };
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::index::{BlockIRNode, DynamicFlag, ForIRNode, IRFor, SimpleExpressionNode},
   transform::{ContextNode, TransformContext},
@@ -43,15 +41,29 @@ pub unsafe fn transform_v_for<'a>(
   }
   seen.insert(span.start);
 
-  let IRFor {
+  let Some(IRFor {
     value,
     index,
     key,
     source,
-  } = get_for_parse_result(dir, context)?;
+  }) = get_for_parse_result(dir, context)
+  else {
+    context.replace_with_error(
+      unsafe { &mut *context_node },
+      context_block,
+      ErrorCodes::VForNoExpression,
+      span,
+    );
+    return None;
+  };
 
   let Some(source) = source else {
-    context.options.on_error.as_ref()(ErrorCodes::VForMalformedExpression, span);
+    context.replace_with_error(
+      unsafe { &mut *context_node },
+      context_block,
+      ErrorCodes::VForMalformedExpression,
+      span,
+    );
     return None;
   };
 
@@ -109,7 +121,7 @@ pub unsafe fn transform_v_for<'a>(
   Some(Box::new(move || {
     let block = exit_block();
 
-    context_block.dynamic.operation = Some(Box::new(Either16::B(ForIRNode {
+    context_block.dynamic.operation = Some(Box::new(OperationNode::For(ForIRNode {
       id,
       value,
       key,
@@ -126,6 +138,8 @@ pub unsafe fn transform_v_for<'a>(
   }))
 }
 
+/// Parses `v-for={(item, key, index) in source}`, returns `None` when the
+/// expression is missing and no `source` when it's malformed.
 pub fn get_for_parse_result<'a>(
   dir: &'a mut JSXAttribute<'a>,
   context: &'a TransformContext<'a>,
@@ -136,13 +150,11 @@ pub fn get_for_parse_result<'a>(
   let mut source: Option<SimpleExpressionNode> = None;
   if let Some(dir_value) = &mut dir.value {
     let expression = if let JSXAttributeValue::ExpressionContainer(dir_value) = dir_value {
-      Some(
-        dir_value
-          .expression
-          .to_expression_mut()
+      dir_value.expression.as_expression_mut().map(|expression| {
+        expression
           .without_parentheses_mut()
-          .get_inner_expression_mut(),
-      )
+          .get_inner_expression_mut()
+      })
     } else {
       None
     };
@@ -174,7 +186,6 @@ pub fn get_for_parse_result<'a>(
       ));
     }
   } else {
-    context.options.on_error.as_ref()(ErrorCodes::VForNoExpression, dir.span);
     return None;
   }
   Some(IRFor {
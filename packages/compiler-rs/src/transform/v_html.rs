@@ -1,6 +1,7 @@
-use napi::bindgen_prelude::{Either3, Either16};
 use oxc_ast::ast::{JSXAttribute, JSXElement};
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::Either3;
 use crate::{
   ir::index::{BlockIRNode, SetHtmlIRNode, SimpleExpressionNode},
   transform::{DirectiveTransformResult, TransformContext},
@@ -9,7 +10,7 @@ use crate::{
 
 pub fn transform_v_html<'a>(
   dir: &'a mut JSXAttribute<'a>,
-  node: &JSXElement,
+  node: &mut JSXElement,
   context: &'a TransformContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
 ) -> Option<DirectiveTransformResult<'a>> {
@@ -19,17 +20,22 @@ pub fn transform_v_html<'a>(
     context.options.on_error.as_ref()(ErrorCodes::VHtmlNoExpression, dir.span);
     SimpleExpressionNode::default()
   };
+  // e.g. `v-html={}`
+  if exp.is_empty() {
+    context.options.on_error.as_ref()(ErrorCodes::VHtmlNoExpression, dir.span);
+  }
 
+  // Like Vue, the directive replaces the children.
   if !node.children.is_empty() {
     context.options.on_error.as_ref()(ErrorCodes::VHtmlWithChildren, node.span);
-    return None;
+    node.children.clear();
   }
 
   let element = context.reference(&mut context_block.dynamic);
   context.register_effect(
     context_block,
     context.is_operation(vec![&exp]),
-    Either16::I(SetHtmlIRNode {
+    OperationNode::SetHtml(SetHtmlIRNode {
       set_html: true,
       element,
       value: exp,
@@ -1,8 +1,8 @@
-use napi::{Either, bindgen_prelude::Either16};
 use oxc_allocator::TakeIn;
 use oxc_ast::ast::{Expression, JSXChild, JSXElement};
-use oxc_span::SPAN;
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::Either;
 use crate::{
   ir::index::{BlockIRNode, DynamicFlag, IRDynamicInfo, IfIRNode, SimpleExpressionNode},
   transform::{ContextNode, TransformContext},
@@ -47,12 +47,13 @@ pub unsafe fn transform_v_if<'a>(
   if dir.name != "else"
     && (dir.exp.is_none() || dir.exp.as_ref().unwrap().content.trim().is_empty())
   {
-    context.options.on_error.as_ref()(ErrorCodes::VIfNoExpression, dir.loc);
+    // The condition throws when it's evaluated, the other branches still compile.
+    let placeholder = context.error_placeholder(ErrorCodes::VIfNoExpression, dir.loc);
     dir.exp = Some(SimpleExpressionNode {
-      content: "true".to_string(),
+      content: String::new(),
       is_static: false,
-      loc: SPAN,
-      ast: None,
+      loc: dir.loc,
+      ast: Some(context.allocator.alloc(placeholder)),
     });
   }
 
@@ -75,7 +76,7 @@ pub unsafe fn transform_v_if<'a>(
     return Some(Box::new(move || {
       let block = exit_block();
 
-      context_block.dynamic.operation = Some(Box::new(Either16::A(IfIRNode {
+      context_block.dynamic.operation = Some(Box::new(OperationNode::If(IfIRNode {
         id,
         positive: block,
         once: *context.in_v_once.borrow()
@@ -96,7 +97,7 @@ pub unsafe fn transform_v_if<'a>(
       i -= 1;
       let sibling = siblings.get_mut(i).unwrap() as *mut IRDynamicInfo;
       if let Some(operation) = (unsafe { &mut *sibling }).operation.as_mut()
-        && let Either16::A(operation) = operation.as_mut()
+        && let OperationNode::If(operation) = operation.as_mut()
       {
         last_if_node = Some(operation);
         break;
@@ -106,7 +107,13 @@ pub unsafe fn transform_v_if<'a>(
 
   // check if IfNode is the last operation and get the root IfNode
   let Some(mut last_if_node) = last_if_node else {
-    context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, unsafe { &*node }.span);
+    let span = unsafe { &*node }.span;
+    context.replace_with_error(
+      unsafe { &mut *context_node },
+      context_block,
+      ErrorCodes::VElseNoAdjacentIf,
+      span,
+    );
     return None;
   };
 
@@ -118,9 +125,15 @@ pub unsafe fn transform_v_if<'a>(
   }
   last_if_node = unsafe { &mut *last_if_node_ptr };
 
-  // Check if v-else was followed by v-else-if
-  if dir.name == "else-if" && last_if_node.negative.is_some() {
-    context.options.on_error.as_ref()(ErrorCodes::VElseNoAdjacentIf, dir.loc);
+  // Check if v-else was followed by v-else or v-else-if
+  if last_if_node.negative.is_some() {
+    context.replace_with_error(
+      unsafe { &mut *context_node },
+      context_block,
+      ErrorCodes::VElseNoAdjacentIf,
+      dir.loc,
+    );
+    return None;
   };
 
   let exit_block = context.create_block(
@@ -1,9 +1,10 @@
-use napi::{Either, bindgen_prelude::Either16};
 use oxc_ast::ast::{
   JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXElement,
 };
 use oxc_span::SPAN;
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::Either;
 use crate::{
   ir::index::{BlockIRNode, DirectiveIRNode, SimpleExpressionNode},
   transform::{DirectiveTransformResult, TransformContext},
@@ -112,7 +113,7 @@ pub fn transform_v_model<'a>(
     let element = context.reference(&mut context_block.dynamic);
     context.register_operation(
       context_block,
-      Either16::M(DirectiveIRNode {
+      OperationNode::Directive(DirectiveIRNode {
         directive: true,
         element,
         dir,
@@ -1,11 +1,9 @@
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either16},
-};
 use oxc_ast::ast::{JSXAttribute, JSXAttributeName, JSXElement};
 use oxc_span::SPAN;
 use phf::phf_set;
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::index::{BlockIRNode, Modifiers, SetEventIRNode, SimpleExpressionNode},
   transform::{DirectiveTransformResult, TransformContext},
@@ -110,7 +108,7 @@ pub fn transform_v_on<'a>(
   context.register_effect(
     context_block,
     context.is_operation(vec![&arg]),
-    Either16::H(SetEventIRNode {
+    OperationNode::SetEvent(SetEventIRNode {
       set_event: true,
       element,
       value: exp,
@@ -129,7 +127,7 @@ pub fn transform_v_on<'a>(
   None
 }
 
-static DELEGATED_EVENTS: phf::Set<&'static str> = phf_set! {
+pub static DELEGATED_EVENTS: phf::Set<&'static str> = phf_set! {
     "beforeinput",
     "click",
     "dblclick",
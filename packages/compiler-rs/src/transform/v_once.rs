@@ -1,6 +1,6 @@
-use napi::Either;
 use oxc_ast::ast::JSXChild;
 
+use crate::utils::either::Either;
 use crate::{
   ir::index::BlockIRNode,
   transform::{ContextNode, TransformContext},
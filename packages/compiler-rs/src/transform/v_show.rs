@@ -1,6 +1,6 @@
-use napi::bindgen_prelude::Either16;
 use oxc_ast::ast::{JSXAttribute, JSXElement};
 
+use crate::ir::index::OperationNode;
 use crate::{
   ir::index::{BlockIRNode, DirectiveIRNode, SimpleExpressionNode},
   transform::{DirectiveTransformResult, TransformContext},
@@ -22,7 +22,7 @@ pub fn transform_v_show<'a>(
   let element = context.reference(&mut context_block.dynamic);
   context.register_operation(
     context_block,
-    Either16::M(DirectiveIRNode {
+    OperationNode::Directive(DirectiveIRNode {
       directive: true,
       element,
       dir,
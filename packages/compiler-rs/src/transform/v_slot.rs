@@ -1,7 +1,7 @@
 use indexmap::IndexMap;
-use napi::{Either, bindgen_prelude::Either4};
 use oxc_ast::ast::{JSXChild, JSXElement};
 
+use crate::utils::either::Either;
 use crate::{
   ir::{
     component::{IRSlotDynamicBasic, IRSlotDynamicConditional, IRSlotType, IRSlots, IRSlotsStatic},
@@ -56,6 +56,19 @@ pub unsafe fn transform_v_slot<'a>(
   None
 }
 
+/// Whether the slots of a component come from more than one source, i.e.
+/// `v-slots` and children, or its own `v-slot` and `<template v-slot>` children.
+pub fn is_mixed_slot_usage(node: &JSXElement) -> bool {
+  if find_prop(node, Either::A(String::from("v-slots"))).is_some() {
+    return node.children.iter().any(|child| !is_empty_text(child));
+  }
+  find_prop(node, Either::A(String::from("v-slot"))).is_some()
+    && node.children.iter().any(|child| {
+      matches!(child, JSXChild::Element(child)
+        if is_template(child) && find_prop(child, Either::A(String::from("v-slot"))).is_some())
+    })
+}
+
 // <Foo v-slot:default>
 fn transform_component_slot<'a>(
   dir: Option<DirectiveNode<'a>>,
@@ -63,8 +76,6 @@ fn transform_component_slot<'a>(
   context: &'a TransformContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
 ) -> Box<dyn FnOnce() + 'a> {
-  let has_dir = dir.is_some();
-
   let (arg, exp) = if let Some(DirectiveNode { arg, exp, .. }) = dir {
     (arg, exp)
   } else {
@@ -91,12 +102,6 @@ fn transform_component_slot<'a>(
 
     let block = exit_block();
     let has_other_slots = !slots.is_empty();
-    if has_dir && has_other_slots {
-      // already has on-component slot - this is incorrect usage.
-      context.options.on_error.as_ref()(ErrorCodes::VSlotMixedSlotUsage, node.span);
-      return;
-    }
-
     if non_slot_template_children_len > 0 {
       if has_static_slot(&slots, "default") {
         context.options.on_error.as_ref()(
@@ -128,7 +133,12 @@ fn transform_template_slot<'a>(
 
   let v_for = find_prop_mut(unsafe { &mut *node }, Either::A(String::from("v-for")));
   let for_parse_result = if let Some(v_for) = v_for {
-    get_for_parse_result(v_for, context)
+    let span = v_for.span;
+    let for_parse_result = get_for_parse_result(v_for, context);
+    if for_parse_result.is_none() {
+      context.options.on_error.as_ref()(ErrorCodes::VForNoExpression, span);
+    }
+    for_parse_result
   } else {
     None
   };
@@ -155,7 +165,7 @@ fn transform_template_slot<'a>(
         register_slot(slots, arg, block);
       }
     } else if let Some(v_if_dir) = v_if_dir {
-      slots.push(Either4::C(IRSlotDynamicConditional {
+      slots.push(IRSlots::Conditional(IRSlotDynamicConditional {
         slot_type: IRSlotType::CONDITIONAL,
         condition: v_if_dir.exp.unwrap(),
         negative: None,
@@ -168,7 +178,7 @@ fn transform_template_slot<'a>(
       }));
     } else if let Some(v_else_dir) = v_else_dir {
       if let Some(last_slot) = slots.last_mut() {
-        if let Either4::C(v_if_slot) = last_slot {
+        if let IRSlots::Conditional(v_if_slot) = last_slot {
           let positive = IRSlotDynamicBasic {
             slot_type: IRSlotType::DYNAMIC,
             name: arg.unwrap(),
@@ -193,7 +203,7 @@ fn transform_template_slot<'a>(
     } else if let Some(for_parse_result) = for_parse_result
       && for_parse_result.source.is_some()
     {
-      slots.push(Either4::B(IRSlotDynamicBasic {
+      slots.push(IRSlots::Dynamic(IRSlotDynamicBasic {
         slot_type: IRSlotType::DYNAMIC,
         name: arg.unwrap(),
         _fn: block,
@@ -228,14 +238,14 @@ fn register_slot<'a>(
     if slots.is_empty()
       || slots
         .last()
-        .is_some_and(|last_slot| !matches!(last_slot, Either4::A(_)))
+        .is_some_and(|last_slot| !matches!(last_slot, IRSlots::Static(_)))
     {
-      slots.push(Either4::A(IRSlotsStatic {
+      slots.push(IRSlots::Static(IRSlotsStatic {
         slot_type: IRSlotType::STATIC,
         slots: IndexMap::new(),
       }));
     }
-    if let Some(Either4::A(slot)) = slots.last_mut() {
+    if let Some(IRSlots::Static(slot)) = slots.last_mut() {
       slot.slots.insert(
         if let Some(name) = &name {
           name.content.clone()
@@ -246,7 +256,7 @@ fn register_slot<'a>(
       );
     }
   } else {
-    slots.push(Either4::B(IRSlotDynamicBasic {
+    slots.push(IRSlots::Dynamic(IRSlotDynamicBasic {
       slot_type: IRSlotType::DYNAMIC,
       name: name.unwrap(),
       _fn: block,
@@ -257,7 +267,7 @@ fn register_slot<'a>(
 
 fn has_static_slot(slots: &Vec<IRSlots>, name: &str) -> bool {
   slots.iter().any(|slot| match slot {
-    Either4::A(static_slot) => {
+    IRSlots::Static(static_slot) => {
       matches!(static_slot.slot_type, IRSlotType::STATIC) && static_slot.slots.get(name).is_some()
     }
     _ => false,
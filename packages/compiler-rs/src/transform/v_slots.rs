@@ -1,9 +1,7 @@
-use napi::{
-  Either,
-  bindgen_prelude::{Either3, Either4},
-};
 use oxc_ast::ast::{JSXAttributeValue, JSXChild, JSXElement};
 
+use crate::ir::component::IRSlots;
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::{
     component::{IRSlotType, IRSlotsExpression},
@@ -32,16 +30,12 @@ pub unsafe fn transform_v_slots<'a>(
       return None;
     }
 
-    if !unsafe { &mut *node }.children.is_empty() {
-      context.options.on_error.as_ref()(ErrorCodes::VSlotMixedSlotUsage, unsafe { &*node }.span);
-      return None;
-    }
-
-    if let Some(JSXAttributeValue::ExpressionContainer(value)) = &mut dir.value {
-      let slots =
-        SimpleExpressionNode::new(Either3::A(value.expression.to_expression_mut()), context);
+    if let Some(JSXAttributeValue::ExpressionContainer(value)) = &mut dir.value
+      && let Some(expression) = value.expression.as_expression_mut()
+    {
+      let slots = SimpleExpressionNode::new(Either3::A(expression), context);
       Some(Box::new(move || {
-        *context.slots.borrow_mut() = vec![Either4::D(IRSlotsExpression {
+        *context.slots.borrow_mut() = vec![IRSlots::Expression(IRSlotsExpression {
           slot_type: IRSlotType::EXPRESSION,
           slots,
         })];
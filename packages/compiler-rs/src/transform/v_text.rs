@@ -1,6 +1,7 @@
-use napi::bindgen_prelude::{Either3, Either16};
 use oxc_ast::ast::{JSXAttribute, JSXElement};
 
+use crate::ir::index::OperationNode;
+use crate::utils::either::Either3;
 use crate::{
   ir::index::{BlockIRNode, GetTextChildIRNode, SetTextIRNode, SimpleExpressionNode},
   transform::{DirectiveTransformResult, TransformContext},
@@ -9,7 +10,7 @@ use crate::{
 
 pub fn transform_v_text<'a>(
   dir: &'a mut JSXAttribute<'a>,
-  node: &JSXElement,
+  node: &mut JSXElement,
   context: &'a TransformContext<'a>,
   context_block: &'a mut BlockIRNode<'a>,
 ) -> Option<DirectiveTransformResult<'a>> {
@@ -19,11 +20,16 @@ pub fn transform_v_text<'a>(
     context.options.on_error.as_ref()(ErrorCodes::VTextNoExpression, dir.span);
     SimpleExpressionNode::default()
   };
+  // e.g. `v-text={}`
+  if exp.is_empty() {
+    context.options.on_error.as_ref()(ErrorCodes::VTextNoExpression, dir.span);
+  }
 
+  // Like Vue, the directive replaces the children.
   if !node.children.is_empty() {
     context.options.on_error.as_ref()(ErrorCodes::VTextWithChildren, node.span);
-    return None;
-  };
+    node.children.clear();
+  }
 
   // v-text on void tags do nothing
   if let Some(name) = &node.opening_element.name.get_identifier_name()
@@ -40,7 +46,7 @@ pub fn transform_v_text<'a>(
     let parent = context.reference(&mut context_block.dynamic);
     context.register_operation(
       context_block,
-      Either16::P(GetTextChildIRNode {
+      OperationNode::GetTextChild(GetTextChildIRNode {
         get_text_child: true,
         parent,
       }),
@@ -50,7 +56,7 @@ pub fn transform_v_text<'a>(
     context.register_effect(
       context_block,
       context.is_operation(vec![&exp]),
-      Either16::C(SetTextIRNode {
+      OperationNode::SetText(SetTextIRNode {
         set_text: true,
         values: vec![exp],
         element,
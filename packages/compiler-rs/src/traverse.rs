@@ -1,2 +1,5 @@
 pub mod hmr_or_ssr;
+pub mod imports;
 pub mod jsx;
+pub mod template_module;
+pub mod vdom;
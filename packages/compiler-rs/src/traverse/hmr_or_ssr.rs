@@ -1,56 +1,125 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::{
+  cell::RefCell,
+  collections::HashSet,
+  hash::{DefaultHasher, Hash, Hasher},
+};
 
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::{
-  NONE,
+  AstBuilder, NONE,
   ast::{
     Argument, AssignmentOperator, AssignmentTarget, BinaryOperator, BindingPatternKind,
-    Declaration, ExportDefaultDeclarationKind, Expression, FormalParameterKind, ImportOrExportKind,
-    Statement, UnaryOperator, VariableDeclaration, VariableDeclarationKind,
+    Declaration, ExportDefaultDeclarationKind, Expression, FormalParameterKind, Function,
+    JSXElement, JSXFragment, LogicalOperator, Statement, UnaryOperator, VariableDeclaration,
+    VariableDeclarationKind,
   },
 };
-use oxc_span::{GetSpan, SPAN};
+use oxc_ast_visit::Visit;
+use oxc_semantic::{ScopeFlags, Scoping};
+use oxc_span::{GetSpan, SPAN, Span};
 use oxc_traverse::Traverse;
 
-use crate::transform::TransformOptions;
+use crate::{
+  transform::TransformOptions,
+  traverse::imports::{import_named, runtime_module},
+  utils::{hash::fnv1a, names::UniqueNames},
+};
 
 pub struct HmrOrSsrTraverse<'a> {
   has_default_export: bool,
   components: Vec<Component>,
   options: &'a TransformOptions<'a>,
+  names: &'a RefCell<UniqueNames>,
+  /// Bindings of the functions that return JSX, see `JsxTraverse`.
+  compiled: HashSet<String>,
 }
 
 impl<'a> HmrOrSsrTraverse<'a> {
-  pub fn new(options: &'a TransformOptions<'a>) -> Self {
+  pub fn new(
+    options: &'a TransformOptions<'a>,
+    names: &'a RefCell<UniqueNames>,
+    compiled: HashSet<String>,
+  ) -> Self {
     Self {
       has_default_export: false,
       components: vec![],
       options,
+      names,
+      compiled,
     }
   }
 
-  fn is_define_component_call(&self, node: Option<&Expression>) -> bool {
-    if let Some(Expression::CallExpression(node)) = node
-      && let Expression::Identifier(id) = &node.callee
-      && ["defineComponent", "defineVaporComponent"].contains(&id.name.as_str())
-    {
-      true
-    } else {
-      false
+  /// Whether `node` is a function, a component factory call, or one of them
+  /// wrapped in `component_wrappers`.
+  fn is_component_expression(&self, node: &Expression, scoping: &Scoping) -> bool {
+    match node.without_parentheses() {
+      Expression::CallExpression(node) => {
+        self
+          .options
+          .factories
+          .borrow()
+          .resolve(&node.callee, scoping)
+          .is_some()
+          || matches!(&node.callee, Expression::Identifier(id) if self
+            .options
+            .component_wrappers
+            .iter()
+            .any(|name| id.name == name))
+            && node
+              .arguments
+              .first()
+              .and_then(|argument| argument.as_expression())
+              .is_some_and(|argument| self.is_component_expression(argument, scoping))
+      }
+      node => node.is_function(),
     }
   }
 
-  fn parse_component_decls(&self, node: &VariableDeclaration) -> Vec<String> {
+  fn parse_component_decls(
+    &self,
+    node: &VariableDeclaration,
+    source: &str,
+    scoping: &Scoping,
+  ) -> Vec<(String, Signature)> {
     let mut names = vec![];
     for decl in &node.declarations {
       if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind
         && let Some(init) = &decl.init
-        && (init.is_function() || self.is_define_component_call(Some(init)))
+        && self.is_component_expression(init, scoping)
       {
-        names.push(id.name.to_string());
+        names.push((
+          id.name.to_string(),
+          self.expression_signature(init, source, scoping),
+        ));
       }
     }
-    return names;
+    names
+  }
+
+  fn expression_signature(&self, node: &Expression, source: &str, scoping: &Scoping) -> Signature {
+    let mut jsx = JsxSpans::default();
+    jsx.visit_expression(node);
+    let (setup, render) = jsx.hashes(source, node.span());
+    Signature {
+      setup,
+      render,
+      factory: matches!(
+        node.without_parentheses(),
+        Expression::CallExpression(node)
+          if self.options.factories.borrow().resolve(&node.callee, scoping).is_some()
+      ),
+    }
+  }
+
+  fn function_signature(&self, node: &Function, source: &str) -> Signature {
+    let mut jsx = JsxSpans::default();
+    jsx.visit_function(node, ScopeFlags::empty());
+    let (setup, render) = jsx.hashes(source, node.span);
+    Signature {
+      setup,
+      render,
+      factory: false,
+    }
   }
 
   fn hash_string(&self, s: &str) -> String {
@@ -62,8 +131,55 @@ impl<'a> HmrOrSsrTraverse<'a> {
 
 struct Component {
   local: String,
-  exported: String,
+  /// `None` for components that are only used in this file.
+  exported: Option<String>,
   id: String,
+  signature: Signature,
+}
+
+/// What the accept callback compares to update a component, see `hmr_update`.
+#[derive(Clone)]
+struct Signature {
+  /// Hash of the component without its JSX, see `JsxSpans::hashes`.
+  setup: String,
+  /// Hash of the JSX of the component.
+  render: String,
+  /// Whether the component is created by a component factory, which
+  /// returns an object instead of a function.
+  factory: bool,
+}
+
+/// Spans of the outermost JSX of a component.
+#[derive(Default)]
+struct JsxSpans(Vec<Span>);
+
+impl JsxSpans {
+  /// Returns the hashes of the source of `span` without the JSX and of the
+  /// JSX alone, so that a component whose setup is unchanged is rerendered
+  /// instead of reloaded when only its JSX changes, and left alone when
+  /// neither changes. Whitespace doesn't change them.
+  fn hashes(&self, source: &str, span: Span) -> (String, String) {
+    let mut setup = vec![];
+    let mut render = vec![];
+    let mut start = span.start;
+    for jsx in self.0.iter().chain([&Span::new(span.end, span.end)]) {
+      setup.extend(source[start as usize..jsx.start as usize].split_whitespace());
+      render.extend(jsx.source_text(source).split_whitespace());
+      start = jsx.end;
+    }
+    let hash = |words: Vec<&str>| format!("{:x}", fnv1a(words.join(" ").as_bytes()));
+    (hash(setup), hash(render))
+  }
+}
+
+impl<'a> Visit<'a> for JsxSpans {
+  fn visit_jsx_element(&mut self, node: &JSXElement<'a>) {
+    self.0.push(node.span);
+  }
+
+  fn visit_jsx_fragment(&mut self, node: &JSXFragment<'a>) {
+    self.0.push(node.span);
+  }
 }
 
 impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
@@ -73,27 +189,30 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
     ctx: &mut oxc_traverse::TraverseCtx<'a, ()>,
   ) {
     let ast = &ctx.ast;
+    let source = program.source_text;
+    let default_local = self.names.borrow_mut().get("__default__");
 
     let mut declared_components = vec![];
     let mut default_declaration_index = 0;
 
     for (index, node) in program.body.iter_mut().enumerate() {
       if let Statement::VariableDeclaration(node) = node {
-        declared_components.extend(self.parse_component_decls(&node));
+        declared_components.extend(self.parse_component_decls(node, source, ctx.scoping()));
       } else if let Statement::FunctionDeclaration(node) = node
         && let Some(id) = &node.id
       {
-        declared_components.push(id.name.to_string())
+        declared_components.push((id.name.to_string(), self.function_signature(node, source)))
       } else if let Statement::ExportNamedDeclaration(node) = node {
         if let Some(Declaration::VariableDeclaration(declaration)) = &node.declaration {
           self.components.extend(
             self
-              .parse_component_decls(&declaration)
+              .parse_component_decls(declaration, source, ctx.scoping())
               .into_iter()
-              .map(|name| Component {
+              .map(|(name, signature)| Component {
                 local: name.clone(),
-                exported: name.clone(),
+                exported: Some(name.clone()),
                 id: self.hash_string(&name),
+                signature,
               })
               .collect::<Vec<_>>(),
           )
@@ -102,30 +221,36 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
         {
           self.components.push(Component {
             local: id.name.to_string(),
-            exported: id.name.to_string(),
+            exported: Some(id.name.to_string()),
             id: self.hash_string(&id.name),
+            signature: self.function_signature(declaration, source),
           });
         } else {
           for spec in &node.specifiers {
-            if let Some(name) = spec.exported.identifier_name() {
-              if declared_components.iter().any(|n| name.eq(n.as_str())) {
-                self.components.push(Component {
-                  local: spec.local.name().to_string(),
-                  exported: name.to_string(),
-                  id: self.hash_string(&name),
-                })
-              }
+            if let Some(name) = spec.exported.identifier_name()
+              && let Some((_, signature)) = declared_components
+                .iter()
+                .find(|(n, _)| spec.local.name().eq(n.as_str()))
+            {
+              self.components.push(Component {
+                local: spec.local.name().to_string(),
+                exported: Some(name.to_string()),
+                id: self.hash_string(&name),
+                signature: signature.clone(),
+              })
             }
           }
         }
       } else if let Statement::ExportDefaultDeclaration(node) = node {
         if let ExportDefaultDeclarationKind::Identifier(id) = &node.declaration {
           let _name = id.name.as_str();
-          if declared_components.iter().any(|name| name.eq(_name)) {
+          if let Some((_, signature)) = declared_components.iter().find(|(name, _)| name.eq(_name))
+          {
             self.components.push(Component {
               local: _name.to_string(),
-              exported: String::from("default"),
+              exported: Some(String::from("default")),
               id: self.hash_string("default"),
+              signature: signature.clone(),
             })
           }
         } else if let ExportDefaultDeclarationKind::FunctionDeclaration(declaration) =
@@ -136,34 +261,47 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
             local: if let Some(id) = &declaration.id {
               id.name.to_string()
             } else {
-              String::from("__default__")
+              default_local.clone()
             },
-            exported: String::from("default"),
+            exported: Some(String::from("default")),
             id: self.hash_string("default"),
+            signature: self.function_signature(declaration, source),
           })
-        } else if self.is_define_component_call(node.declaration.as_expression())
-          || node
-            .declaration
-            .as_expression()
-            .and_then(|e| Some(e.is_function()))
-            .unwrap_or_default()
+        } else if let Some(expression) = node.declaration.as_expression()
+          && self.is_component_expression(expression, ctx.scoping())
         {
           self.has_default_export = true;
           self.components.push(Component {
-            local: if let ExportDefaultDeclarationKind::Identifier(id) = &node.declaration {
-              self.has_default_export = false;
-              id.name.to_string()
-            } else {
-              String::from("__default__")
-            },
-            exported: String::from("default"),
+            local: default_local.clone(),
+            exported: Some(String::from("default")),
             id: self.hash_string("default"),
+            signature: self.expression_signature(expression, source, ctx.scoping()),
           })
         }
         default_declaration_index = index;
       }
     }
 
+    if !self.options.ssr {
+      // Components only used in this file are registered as well, they're
+      // told apart from other functions by the JSX they return.
+      for (name, signature) in declared_components {
+        if self.compiled.contains(&name)
+          && !self
+            .components
+            .iter()
+            .any(|component| component.local == name)
+        {
+          self.components.push(Component {
+            id: self.hash_string(&name),
+            local: name,
+            exported: None,
+            signature,
+          });
+        }
+      }
+    }
+
     if !self.components.is_empty() {
       if let Some(default_declaration) = program.body.get_mut(default_declaration_index)
         && let Statement::ExportDefaultDeclaration(default_declaration) = default_declaration
@@ -171,7 +309,7 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
       {
         let mut declaration = default_declaration.declaration.take_in(ast.allocator);
         default_declaration.declaration = ExportDefaultDeclarationKind::Identifier(
-          ast.alloc_identifier_reference(declaration.span(), "__default__"),
+          ast.alloc_identifier_reference(declaration.span(), ast.atom(&default_local)),
         );
         program.body.insert(
           default_declaration_index,
@@ -184,9 +322,7 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
                   SPAN,
                   VariableDeclarationKind::Const,
                   ast.binding_pattern(
-                    ctx
-                      .ast
-                      .binding_pattern_kind_binding_identifier(SPAN, "__default__"),
+                    ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom(&default_local)),
                     NONE,
                     false,
                   ),
@@ -212,6 +348,7 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
       }
 
       if self.options.ssr {
+        let module_id = self.names.borrow_mut().get("__moduleId");
         program.body.insert(
           0,
           Statement::VariableDeclaration(ast.alloc_variable_declaration(
@@ -222,7 +359,7 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
               VariableDeclarationKind::Const,
               ast.binding_pattern(
                 BindingPatternKind::BindingIdentifier(
-                  ast.alloc_binding_identifier(SPAN, "__moduleId"),
+                  ast.alloc_binding_identifier(SPAN, ast.atom(&module_id)),
                 ),
                 NONE,
                 false,
@@ -235,19 +372,12 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
         );
         program.body.insert(
           0,
-          Statement::ImportDeclaration(ast.alloc_import_declaration(
-            SPAN,
-            Some(ast.vec1(ast.import_declaration_specifier_import_specifier(
-              SPAN,
-              ast.module_export_name_identifier_reference(SPAN, "ssrRegisterHelper"),
-              ast.binding_identifier(SPAN, "ssrRegisterHelper"),
-              ImportOrExportKind::Value,
-            ))),
-            ast.string_literal(SPAN, "/__vue-jsx-ssr-register-helper", None),
-            None,
-            NONE,
-            ImportOrExportKind::Value,
-          )),
+          import_named(
+            *ast,
+            self.options,
+            runtime_module(self.options, "ssrRegisterHelper"),
+            &[("ssrRegisterHelper", "ssrRegisterHelper")],
+          ),
         );
 
         for Component { local, .. } in self.components.drain(..) {
@@ -259,170 +389,275 @@ impl<'a> Traverse<'a, ()> for HmrOrSsrTraverse<'a> {
               NONE,
               ast.vec_from_array([
                 Argument::Identifier(ast.alloc_identifier_reference(SPAN, ast.atom(&local))),
-                Argument::Identifier(ast.alloc_identifier_reference(SPAN, "__moduleId")),
+                Argument::Identifier(ast.alloc_identifier_reference(SPAN, ast.atom(&module_id))),
               ]),
               false,
             ),
           ))
         }
-        return;
       } else if !self.options.filename.contains("?vue&type=script") {
         let mut callbacks = ast.vec();
+        let mut hot_statements = vec![];
+        let mod_local = self.names.borrow_mut().get("mod");
+        let import_meta_hot = ast.member_expression_static(
+          SPAN,
+          ast
+            .member_expression_static(
+              SPAN,
+              ast.expression_identifier(SPAN, "import"),
+              ast.identifier_name(SPAN, "meta"),
+              false,
+            )
+            .into(),
+          ast.identifier_name(SPAN, "hot"),
+          false,
+        );
+        // `import.meta.hot.data[id]`, a local component before the update.
+        let hot_data = |id: &str| {
+          Expression::from(ast.member_expression_computed(
+            SPAN,
+            Expression::StaticMemberExpression(ast.alloc_static_member_expression(
+              SPAN,
+              import_meta_hot.clone_in(ast.allocator).into(),
+              ast.identifier_name(SPAN, "data"),
+              false,
+            )),
+            ast.expression_string_literal(SPAN, ast.atom(id), None),
+            false,
+          ))
+        };
 
         for Component {
           local,
           exported,
           id,
+          signature,
         } in self.components.drain(..)
         {
-          program.body.push(ast.statement_expression(
-            SPAN,
-            ast.expression_assignment(
+          self.options.metadata.borrow_mut().hmr_ids.push(id.clone());
+          for (key, value) in [
+            ("__hmrId", &id),
+            ("__hmrSetupHash", &signature.setup),
+            ("__hmrRenderHash", &signature.render),
+          ] {
+            program.body.push(ast.statement_expression(
               SPAN,
-              AssignmentOperator::Assign,
-              AssignmentTarget::StaticMemberExpression(ast.alloc_static_member_expression(
+              ast.expression_assignment(
                 SPAN,
-                ast.expression_identifier(SPAN, ast.atom(&local)),
-                ast.identifier_name(SPAN, "__hmrId"),
-                false,
-              )),
-              ast.expression_string_literal(SPAN, ast.atom(&id), None),
-            ),
-          ));
-          program.body.push(ast.statement_expression(
+                AssignmentOperator::Assign,
+                AssignmentTarget::StaticMemberExpression(ast.alloc_static_member_expression(
+                  SPAN,
+                  ast.expression_identifier(SPAN, ast.atom(&local)),
+                  ast.identifier_name(SPAN, key),
+                  false,
+                )),
+                ast.expression_string_literal(SPAN, ast.atom(value), None),
+              ),
+            ));
+          }
+          let create_record = ast.expression_call(
             SPAN,
-            ast.expression_call(
+            Expression::StaticMemberExpression(ast.alloc_static_member_expression(
               SPAN,
-              Expression::StaticMemberExpression(ast.alloc_static_member_expression(
-                SPAN,
-                ast.expression_identifier(SPAN, "__VUE_HMR_RUNTIME__"),
-                ast.identifier_name(SPAN, "createRecord"),
-                false,
-              )),
-              NONE,
-              ast.vec_from_array([
-                Argument::StringLiteral(ast.alloc_string_literal(SPAN, ast.atom(&id), None)),
-                Argument::Identifier(ast.alloc_identifier_reference(SPAN, ast.atom(&local))),
-              ]),
+              ast.expression_identifier(SPAN, "__VUE_HMR_RUNTIME__"),
+              ast.identifier_name(SPAN, "createRecord"),
               false,
-            ),
-          ));
+            )),
+            NONE,
+            ast.vec_from_array([
+              Argument::StringLiteral(ast.alloc_string_literal(SPAN, ast.atom(&id), None)),
+              Argument::Identifier(ast.alloc_identifier_reference(SPAN, ast.atom(&local))),
+            ]),
+            false,
+          );
+
+          let Some(exported) = exported else {
+            // A local component can't be reached from the accept callback, so
+            // the module updates it itself when it's executed again.
+            hot_statements.push(ast.statement_if(
+              SPAN,
+              ast.expression_unary(SPAN, UnaryOperator::LogicalNot, create_record),
+              ast.statement_block(
+                SPAN,
+                ast.vec1(hmr_update(
+                  ast,
+                  ast.expression_identifier(SPAN, ast.atom(&local)),
+                  ast.expression_string_literal(SPAN, ast.atom(&id), None),
+                  hot_data(&id),
+                  &signature,
+                )),
+              ),
+              None,
+            ));
+            hot_statements.push(ast.statement_expression(
+              SPAN,
+              ast.expression_assignment(
+                SPAN,
+                AssignmentOperator::Assign,
+                AssignmentTarget::from(hot_data(&id).into_member_expression()),
+                ast.expression_identifier(SPAN, ast.atom(&local)),
+              ),
+            ));
+            continue;
+          };
+          program
+            .body
+            .push(ast.statement_expression(SPAN, create_record));
 
           let exported_expression =
             Expression::StaticMemberExpression(ast.alloc_static_member_expression(
               SPAN,
-              ast.expression_identifier(SPAN, "mod"),
+              ast.expression_identifier(SPAN, ast.atom(&mod_local)),
               ast.identifier_name(SPAN, ast.atom(&exported)),
               false,
             ));
-          callbacks.push(Statement::ExpressionStatement(
-            ast.alloc_expression_statement(
+          callbacks.push(hmr_update(
+            ast,
+            exported_expression.clone_in(ast.allocator),
+            Expression::StaticMemberExpression(ast.alloc_static_member_expression(
               SPAN,
-              ast.expression_call(
-                SPAN,
-                Expression::ComputedMemberExpression(ast.alloc_computed_member_expression(
-                  SPAN,
-                  ast.expression_identifier(SPAN, "__VUE_HMR_RUNTIME__"),
-                  ast.expression_conditional(
-                    SPAN,
-                    ast.expression_binary(
-                      SPAN,
-                      ast.expression_unary(
-                        SPAN,
-                        UnaryOperator::Typeof,
-                        exported_expression.clone_in(ast.allocator),
-                      ),
-                      BinaryOperator::StrictEquality,
-                      ast.expression_string_literal(SPAN, "function", None),
-                    ),
-                    ast.expression_string_literal(SPAN, "rerender", None),
-                    ast.expression_string_literal(SPAN, "reload", None),
-                  ),
-                  false,
-                )),
-                NONE,
-                ast.vec_from_array([
-                  Expression::StaticMemberExpression(ast.alloc_static_member_expression(
-                    SPAN,
-                    exported_expression.clone_in(ast.allocator),
-                    ast.identifier_name(SPAN, "__hmrId"),
-                    false,
-                  ))
-                  .into(),
-                  exported_expression.into(),
-                ]),
-                false,
-              ),
-            ),
+              exported_expression.clone_in(ast.allocator),
+              ast.identifier_name(SPAN, "__hmrId"),
+              false,
+            )),
+            exported_expression,
+            &signature,
           ));
         }
 
-        let import_meta_hot = ast.member_expression_static(
+        let accept = ast.statement_expression(
           SPAN,
-          ast
-            .member_expression_static(
-              SPAN,
-              ast.expression_identifier(SPAN, "import"),
-              ast.identifier_name(SPAN, "meta"),
-              false,
-            )
-            .into(),
-          ast.identifier_name(SPAN, "hot"),
-          false,
-        );
-        program.body.push(
-          ast.statement_if(
+          ast.expression_call(
             SPAN,
-            import_meta_hot.clone_in(ast.allocator).into(),
-            ast.statement_expression(
+            Expression::StaticMemberExpression(ast.alloc_static_member_expression(
               SPAN,
-              ast.expression_call(
-                SPAN,
-                Expression::StaticMemberExpression(ast.alloc_static_member_expression(
+              import_meta_hot.clone_in(ast.allocator).into(),
+              ast.identifier_name(SPAN, "accept"),
+              false,
+            )),
+            NONE,
+            ast.vec1(
+              ast
+                .expression_arrow_function(
                   SPAN,
-                  import_meta_hot.into(),
-                  ast.identifier_name(SPAN, "accept"),
                   false,
-                )),
-                NONE,
-                ast.vec1(
-                  ast
-                    .expression_arrow_function(
+                  false,
+                  NONE,
+                  ast.formal_parameters(
+                    SPAN,
+                    FormalParameterKind::ArrowFormalParameters,
+                    ast.vec1(ast.formal_parameter(
                       SPAN,
-                      false,
-                      false,
-                      NONE,
-                      ast.formal_parameters(
-                        SPAN,
-                        FormalParameterKind::ArrowFormalParameters,
-                        ast.vec1(ast.formal_parameter(
-                          SPAN,
-                          ast.vec(),
-                          ast.binding_pattern(
-                            BindingPatternKind::BindingIdentifier(
-                              ast.alloc_binding_identifier(SPAN, "mod"),
-                            ),
-                            NONE,
-                            false,
-                          ),
-                          None,
-                          false,
-                          false,
-                        )),
+                      ast.vec(),
+                      ast.binding_pattern(
+                        BindingPatternKind::BindingIdentifier(
+                          ast.alloc_binding_identifier(SPAN, ast.atom(&mod_local)),
+                        ),
                         NONE,
+                        false,
                       ),
-                      NONE,
-                      ast.function_body(SPAN, ast.vec(), callbacks),
-                    )
-                    .into(),
-                ),
-                false,
-              ),
+                      None,
+                      false,
+                      false,
+                    )),
+                    NONE,
+                  ),
+                  NONE,
+                  ast.function_body(SPAN, ast.vec(), callbacks),
+                )
+                .into(),
             ),
-            None,
+            false,
           ),
         );
+        program.body.push(ast.statement_if(
+          SPAN,
+          import_meta_hot.into(),
+          if hot_statements.is_empty() {
+            accept
+          } else {
+            hot_statements.push(accept);
+            ast.statement_block(SPAN, ast.vec_from_iter(hot_statements))
+          },
+          None,
+        ));
       }
     }
   }
 }
+
+/// Creates
+/// `if (previous.__hmrSetupHash !== "..." || typeof component !== "function") __VUE_HMR_RUNTIME__.reload(id, component); else if (previous.__hmrRenderHash !== "...") __VUE_HMR_RUNTIME__.rerender(id, component);`,
+/// components are only rerendered when just their JSX changed, and left
+/// alone when nothing did. Factory components are objects and skip the
+/// `typeof` check.
+fn hmr_update<'a>(
+  ast: &AstBuilder<'a>,
+  component: Expression<'a>,
+  id: Expression<'a>,
+  previous: Expression<'a>,
+  signature: &Signature,
+) -> Statement<'a> {
+  let changed = |key: &'static str, hash: &str| {
+    ast.expression_binary(
+      SPAN,
+      Expression::from(ast.member_expression_static(
+        SPAN,
+        previous.clone_in(ast.allocator),
+        ast.identifier_name(SPAN, key),
+        false,
+      )),
+      BinaryOperator::StrictInequality,
+      ast.expression_string_literal(SPAN, ast.atom(hash), None),
+    )
+  };
+  let update = |method: &'static str| {
+    ast.statement_expression(
+      SPAN,
+      ast.expression_call(
+        SPAN,
+        Expression::from(ast.member_expression_static(
+          SPAN,
+          ast.expression_identifier(SPAN, "__VUE_HMR_RUNTIME__"),
+          ast.identifier_name(SPAN, method),
+          false,
+        )),
+        NONE,
+        ast.vec_from_array([
+          id.clone_in(ast.allocator).into(),
+          component.clone_in(ast.allocator).into(),
+        ]),
+        false,
+      ),
+    )
+  };
+  let mut reload = changed("__hmrSetupHash", &signature.setup);
+  if !signature.factory {
+    reload = ast.expression_logical(
+      SPAN,
+      reload,
+      LogicalOperator::Or,
+      ast.expression_binary(
+        SPAN,
+        ast.expression_unary(
+          SPAN,
+          UnaryOperator::Typeof,
+          component.clone_in(ast.allocator),
+        ),
+        BinaryOperator::StrictInequality,
+        ast.expression_string_literal(SPAN, "function", None),
+      ),
+    );
+  }
+  ast.statement_if(
+    SPAN,
+    reload,
+    update("reload"),
+    Some(ast.statement_if(
+      SPAN,
+      changed("__hmrRenderHash", &signature.render),
+      update("rerender"),
+      None,
+    )),
+  )
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use oxc_ast::{
+  AstBuilder, NONE,
+  ast::{
+    Argument, BindingPattern, Expression, IdentifierReference, ImportDeclaration,
+    ImportDeclarationSpecifier, ImportOrExportKind, Program, Statement, VariableDeclarationKind,
+  },
+};
+use oxc_ast_visit::{VisitMut, walk_mut};
+use oxc_semantic::Scoping;
+use oxc_span::SPAN;
+
+use crate::{
+  transform::{ImportStyle, TransformOptions},
+  utils::names::UniqueNames,
+};
+
+/// Helpers imported from `vue-jsx-vapor` instead of `vue` by default.
+const JSX_HELPERS: [&str; 4] = [
+  "setNodes",
+  "createNodes",
+  "createComponent",
+  "createComponentWithFallback",
+];
+
+fn default_runtime_module(helper: &str) -> &'static str {
+  if JSX_HELPERS.contains(&helper) {
+    "vue-jsx-vapor"
+  } else if helper == "ssrRegisterHelper" {
+    "/__vue-jsx-ssr-register-helper"
+  } else {
+    "vue"
+  }
+}
+
+/// Returns the module `helper` is imported from.
+pub fn runtime_module<'o>(options: &'o TransformOptions, helper: &str) -> &'o str {
+  options
+    .runtime_modules
+    .get(helper)
+    .map_or_else(|| default_runtime_module(helper), String::as_str)
+}
+
+/// Splits `module` into words, e.g. `@vue/runtime-dom` into `vue`, `runtime`, `dom`.
+fn module_words(module: &str) -> impl Iterator<Item = &str> {
+  module
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|word| !word.is_empty())
+}
+
+/// Returns the global variable of `module`, e.g. `Vue` for `vue`.
+fn runtime_global(options: &TransformOptions, module: &str) -> String {
+  if let Some(global) = options.runtime_globals.get(module) {
+    return global.clone();
+  }
+  module_words(module)
+    .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+    .collect()
+}
+
+/// Returns the local name of the namespace import of `module`, e.g. `_vue` for `vue`.
+fn namespace_local(module: &str) -> String {
+  module_words(module)
+    .enumerate()
+    .fold("_".to_string(), |local, (index, word)| {
+      if index == 0 {
+        local + word
+      } else {
+        local + &word[..1].to_ascii_uppercase() + &word[1..]
+      }
+    })
+}
+
+/// Creates `import { a as b } from "module"`, or `const { a: b } = require("module")`
+/// for scripts.
+pub fn import_named<'a>(
+  ast: AstBuilder<'a>,
+  options: &TransformOptions,
+  module: &str,
+  specifiers: &[(&str, &str)],
+) -> Statement<'a> {
+  if options.source_type.is_script() {
+    return require_statement(
+      ast,
+      module,
+      ast.binding_pattern(
+        ast.binding_pattern_kind_object_pattern(
+          SPAN,
+          ast.vec_from_iter(specifiers.iter().map(|(imported, local)| {
+            ast.binding_property(
+              SPAN,
+              ast.property_key_static_identifier(SPAN, ast.atom(imported)),
+              ast.binding_pattern(
+                ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom(local)),
+                NONE,
+                false,
+              ),
+              imported == local,
+              false,
+            )
+          })),
+          NONE,
+        ),
+        NONE,
+        false,
+      ),
+    );
+  }
+  Statement::ImportDeclaration(ast.alloc_import_declaration(
+    SPAN,
+    Some(
+      ast.vec_from_iter(specifiers.iter().map(|(imported, local)| {
+        ast.import_declaration_specifier_import_specifier(
+          SPAN,
+          ast.module_export_name_identifier_name(SPAN, ast.atom(imported)),
+          ast.binding_identifier(SPAN, ast.atom(local)),
+          ImportOrExportKind::Value,
+        )
+      })),
+    ),
+    ast.string_literal(SPAN, ast.atom(module), None),
+    None,
+    NONE,
+    ImportOrExportKind::Value,
+  ))
+}
+
+/// Creates `import * as local from "module"`, or `const local = require("module")`
+/// for scripts.
+fn import_namespace<'a>(
+  ast: AstBuilder<'a>,
+  options: &TransformOptions,
+  module: &str,
+  local: &str,
+) -> Statement<'a> {
+  if options.source_type.is_script() {
+    return require_statement(
+      ast,
+      module,
+      ast.binding_pattern(
+        ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom(local)),
+        NONE,
+        false,
+      ),
+    );
+  }
+  Statement::ImportDeclaration(ast.alloc_import_declaration(
+    SPAN,
+    Some(
+      ast.vec1(ast.import_declaration_specifier_import_namespace_specifier(
+        SPAN,
+        ast.binding_identifier(SPAN, ast.atom(local)),
+      )),
+    ),
+    ast.string_literal(SPAN, ast.atom(module), None),
+    None,
+    NONE,
+    ImportOrExportKind::Value,
+  ))
+}
+
+fn require_statement<'a>(
+  ast: AstBuilder<'a>,
+  module: &str,
+  id: BindingPattern<'a>,
+) -> Statement<'a> {
+  Statement::VariableDeclaration(ast.alloc_variable_declaration(
+    SPAN,
+    VariableDeclarationKind::Const,
+    ast.vec1(ast.variable_declarator(
+      SPAN,
+      VariableDeclarationKind::Const,
+      id,
+      Some(ast.expression_call(
+        SPAN,
+        ast.expression_identifier(SPAN, "require"),
+        NONE,
+        ast.vec1(Argument::StringLiteral(ast.alloc_string_literal(
+          SPAN,
+          ast.atom(module),
+          None,
+        ))),
+        false,
+      )),
+      false,
+    )),
+    false,
+  ))
+}
+
+/// Returns the value import declaration of `module` that named specifiers can be added to.
+fn find_import<'p, 'a>(
+  program: &'p mut Program<'a>,
+  module: &str,
+) -> Option<&'p mut ImportDeclaration<'a>> {
+  program.body.iter_mut().find_map(|stmt| match stmt {
+    Statement::ImportDeclaration(decl)
+      if decl.source.value == module
+        && decl.import_kind.is_value()
+        && decl.phase.is_none()
+        && decl.with_clause.is_none()
+        && !decl.specifiers.iter().flatten().any(|specifier| {
+          matches!(
+            specifier,
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)
+          )
+        }) =>
+    {
+      Some(&mut **decl)
+    }
+    _ => None,
+  })
+}
+
+/// Creates the imports of the runtime `helpers`, grouped by module, and
+/// rewrites the helper references of `program` for namespace and global
+/// import styles. Named imports are merged into the existing imports of the
+/// same module.
+pub fn import_helpers<'a>(
+  ast: AstBuilder<'a>,
+  options: &TransformOptions,
+  scoping: &Scoping,
+  names: &mut UniqueNames,
+  program: &mut Program<'a>,
+  helpers: impl IntoIterator<Item = String>,
+) -> Vec<Statement<'a>> {
+  let mut helpers = helpers.into_iter().collect::<Vec<_>>();
+  // The JSX helpers come first, in the order of `JSX_HELPERS`.
+  helpers.sort_by_key(|helper| {
+    JSX_HELPERS
+      .iter()
+      .position(|jsx_helper| jsx_helper == helper)
+      .unwrap_or(JSX_HELPERS.len())
+  });
+  let mut modules: IndexMap<&str, Vec<(String, String)>> = IndexMap::new();
+  for helper in helpers {
+    let local = names.get(&format!("_{helper}"));
+    modules
+      .entry(runtime_module(options, &helper))
+      .or_default()
+      .push((helper, local));
+  }
+
+  let mut statements = vec![];
+  match options.import_style {
+    ImportStyle::Named => {
+      // Helpers the program already imports keep the local of that import,
+      // unless another binding of the same name could shadow it where the
+      // helper is used.
+      let mut renames = HashMap::new();
+      for (module, helpers) in modules {
+        if !options.source_type.is_script()
+          && let Some(decl) = find_import(program, module)
+        {
+          let specifiers = decl.specifiers.get_or_insert_with(|| ast.vec());
+          for (helper, local) in helpers {
+            let existing = specifiers.iter().find_map(|specifier| match specifier {
+              ImportDeclarationSpecifier::ImportSpecifier(specifier)
+                if specifier.imported.name() == helper
+                  && !is_shadowed(scoping, &specifier.local.name) =>
+              {
+                Some(specifier.local.name.to_string())
+              }
+              _ => None,
+            });
+            match existing {
+              Some(existing) => {
+                if existing != local {
+                  renames.insert(local, existing);
+                }
+              }
+              None => specifiers.push(ast.import_declaration_specifier_import_specifier(
+                SPAN,
+                ast.module_export_name_identifier_name(SPAN, ast.atom(&helper)),
+                ast.binding_identifier(SPAN, ast.atom(&local)),
+                ImportOrExportKind::Value,
+              )),
+            }
+          }
+          continue;
+        }
+        let specifiers = helpers
+          .iter()
+          .map(|(helper, local)| (helper.as_str(), local.as_str()))
+          .collect::<Vec<_>>();
+        statements.push(import_named(ast, options, module, &specifiers));
+      }
+      if !renames.is_empty() {
+        HelperRenames { ast, renames }.visit_program(program);
+      }
+    }
+    ImportStyle::Namespace | ImportStyle::Global => {
+      let mut references = HashMap::new();
+      for (module, helpers) in modules {
+        let object = if options.import_style == ImportStyle::Namespace {
+          let local = names.get(&namespace_local(module));
+          statements.push(import_namespace(ast, options, module, &local));
+          local
+        } else {
+          runtime_global(options, module)
+        };
+        for (helper, local) in helpers {
+          references.insert(local, (object.clone(), helper));
+        }
+      }
+      HelperReferences { ast, references }.visit_program(program);
+    }
+  }
+  statements
+}
+
+/// Whether `name` is bound more than once in the program, so that a
+/// reference to the top-level binding may resolve to another one.
+fn is_shadowed(scoping: &Scoping, name: &str) -> bool {
+  scoping
+    .symbol_names()
+    .filter(|symbol| *symbol == name)
+    .nth(1)
+    .is_some()
+}
+
+/// Replaces the local of each helper with `object.helper`.
+struct HelperReferences<'a> {
+  ast: AstBuilder<'a>,
+  references: HashMap<String, (String, String)>,
+}
+
+impl<'a> VisitMut<'a> for HelperReferences<'a> {
+  fn visit_expression(&mut self, node: &mut Expression<'a>) {
+    if let Expression::Identifier(id) = node
+      && let Some((object, helper)) = self.references.get(id.name.as_str())
+    {
+      let ast = self.ast;
+      *node = Expression::from(ast.member_expression_static(
+        id.span,
+        ast.expression_identifier(SPAN, ast.atom(object)),
+        ast.identifier_name(SPAN, ast.atom(helper)),
+        false,
+      ));
+      return;
+    }
+    walk_mut::walk_expression(self, node);
+  }
+}
+
+/// Replaces the local of each helper with the local of an existing import.
+struct HelperRenames<'a> {
+  ast: AstBuilder<'a>,
+  renames: HashMap<String, String>,
+}
+
+impl<'a> VisitMut<'a> for HelperRenames<'a> {
+  fn visit_identifier_reference(&mut self, node: &mut IdentifierReference<'a>) {
+    if let Some(local) = self.renames.get(node.name.as_str()) {
+      node.name = self.ast.atom(local);
+    }
+  }
+}
@@ -1,21 +1,49 @@
-use crate::{transform::TransformContext, traverse::hmr_or_ssr::HmrOrSsrTraverse};
+use crate::{
+  transform::TransformContext,
+  traverse::{
+    hmr_or_ssr::HmrOrSsrTraverse,
+    imports::{import_helpers, import_named},
+    template_module::{create_template, template_export},
+    vdom::VDomTransform,
+  },
+  utils::{
+    factory::Factories,
+    names::UniqueNames,
+    pragma::{JsxMode, Pragmas},
+  },
+};
 use oxc_allocator::{Allocator, TakeIn};
 use oxc_ast::{
-  NONE,
+  AstBuilder, NONE,
   ast::{
-    Argument, BindingPatternKind, Expression, ImportOrExportKind, Program, Statement,
+    Argument, ArrowFunctionExpression, BindingPatternKind, Declaration,
+    ExportDefaultDeclarationKind, Expression, Function, IdentifierReference, Program, Statement,
     VariableDeclarationKind,
   },
 };
-use oxc_semantic::SemanticBuilder;
+use oxc_ast_visit::Visit;
+use oxc_semantic::{Scoping, SemanticBuilder};
 use oxc_span::{GetSpan, SPAN};
 use oxc_traverse::{Ancestor, Traverse, TraverseCtx, traverse_mut};
+use std::collections::{HashMap, HashSet};
 
 pub struct JsxTraverse<'a, 'ctx> {
   allocator: &'a Allocator,
   source_text: &'a str,
-  roots: Vec<*mut Expression<'a>>,
+  /// JSX roots, with the start of the top-level statement they belong to.
+  roots: Vec<(*mut Expression<'a>, u32)>,
+  statement: u32,
   context: &'ctx TransformContext<'a>,
+  pragmas: Pragmas,
+  /// Modes of the statements and functions with a pragma that are being
+  /// traversed, with their start.
+  modes: Vec<(u32, JsxMode)>,
+  /// Modes of the JSX expressions that are being traversed.
+  jsx_modes: Vec<JsxMode>,
+  vdom: VDomTransform<'a, 'ctx>,
+  /// Names of the outermost bindings of the functions that return JSX, the
+  /// components compiled in this file.
+  components: HashSet<String>,
 }
 
 impl<'a, 'ctx: 'a> JsxTraverse<'a, 'ctx> {
@@ -24,29 +52,93 @@ impl<'a, 'ctx: 'a> JsxTraverse<'a, 'ctx> {
       allocator,
       source_text: "",
       roots: vec![],
+      statement: 0,
       context,
+      pragmas: Pragmas::default(),
+      modes: vec![],
+      jsx_modes: vec![],
+      vdom: VDomTransform::new(AstBuilder::new(allocator), context),
+      components: HashSet::new(),
     }
   }
 
-  pub fn traverse(mut self, program: &mut Program<'a>) {
+  pub fn traverse(self, program: &mut Program<'a>) -> Scoping {
+    self.traverse_with_scoping(program, None)
+  }
+
+  /// Reuses `scoping` of the program if it's already built.
+  pub fn traverse_with_scoping(
+    mut self,
+    program: &mut Program<'a>,
+    scoping: Option<Scoping>,
+  ) -> Scoping {
     let allocator = self.allocator;
 
     self.source_text = program.source_text;
+    self.vdom.source_text = program.source_text;
+    *self.context.options.factories.borrow_mut() = Factories::new(program, self.context.options);
+    self.pragmas = Pragmas::new(program);
 
-    traverse_mut(
-      &mut self,
-      allocator,
-      program,
+    let scoping = scoping.unwrap_or_else(|| {
       SemanticBuilder::new()
         .build(program)
         .semantic
-        .into_scoping(),
-      (),
-    );
+        .into_scoping()
+    });
+    traverse_mut(&mut self, allocator, program, scoping, ())
+  }
+}
+
+impl<'a, 'ctx: 'a> JsxTraverse<'a, 'ctx> {
+  fn enter_pragma(&mut self, start: u32) {
+    if let Some(mode) = self.pragmas.get(start) {
+      self.modes.push((start, mode));
+    }
+  }
+
+  fn exit_pragma(&mut self, start: u32) {
+    if self.modes.last().is_some_and(|(last, _)| *last == start) {
+      self.modes.pop();
+    }
   }
 }
 
 impl<'a, 'ctx: 'a> Traverse<'a, ()> for JsxTraverse<'a, 'ctx> {
+  fn enter_statement(&mut self, node: &mut Statement<'a>, ctx: &mut TraverseCtx<'a, ()>) {
+    if matches!(ctx.parent(), Ancestor::ProgramBody(_)) {
+      self.statement = node.span().start;
+    }
+    self.enter_pragma(node.span().start);
+  }
+
+  fn exit_statement(&mut self, node: &mut Statement<'a>, _: &mut TraverseCtx<'a, ()>) {
+    self.exit_pragma(node.span().start);
+  }
+
+  fn enter_function(&mut self, node: &mut Function<'a>, _: &mut TraverseCtx<'a, ()>) {
+    self.enter_pragma(node.span.start);
+  }
+
+  fn exit_function(&mut self, node: &mut Function<'a>, _: &mut TraverseCtx<'a, ()>) {
+    self.exit_pragma(node.span.start);
+  }
+
+  fn enter_arrow_function_expression(
+    &mut self,
+    node: &mut ArrowFunctionExpression<'a>,
+    _: &mut TraverseCtx<'a, ()>,
+  ) {
+    self.enter_pragma(node.span.start);
+  }
+
+  fn exit_arrow_function_expression(
+    &mut self,
+    node: &mut ArrowFunctionExpression<'a>,
+    _: &mut TraverseCtx<'a, ()>,
+  ) {
+    self.exit_pragma(node.span.start);
+  }
+
   fn enter_expression(
     &mut self,
     node: &mut Expression<'a>,
@@ -55,28 +147,83 @@ impl<'a, 'ctx: 'a> Traverse<'a, ()> for JsxTraverse<'a, 'ctx> {
     if !matches!(node, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
       return;
     }
+    let pragma = self.modes.last().copied();
+    let mut mode = None;
     if self.context.options.interop {
+      let factories = self.context.options.factories.borrow();
       for node in ctx.ancestors() {
         if let Ancestor::CallExpressionArguments(node) = node {
-          let name = node.callee().span().source_text(self.source_text);
-          if name == "defineVaporComponent" {
+          // A pragma closer to the JSX than the call wins.
+          if pragma.is_some_and(|(start, _)| start >= node.span().start) {
+            break;
+          }
+          if let Some(factory) = factories.resolve(node.callee(), ctx.scoping()) {
+            mode = Some(factory);
             break;
-          } else if name == "defineComponent" {
-            return;
           }
         }
       }
     }
-    self.roots.push(node as *mut Expression);
+    if self.jsx_modes.is_empty()
+      && let Some(name) = ctx
+        .ancestors()
+        .filter_map(|node| match node {
+          Ancestor::VariableDeclaratorInit(node) => node.id().get_identifier_name(),
+          Ancestor::FunctionBody(node) => node.id().as_ref().map(|id| id.name),
+          _ => None,
+        })
+        .last()
+    {
+      self.components.insert(name.to_string());
+    }
+    let mode = mode
+      .or(pragma.map(|(_, mode)| mode))
+      .or(self.pragmas.file)
+      .unwrap_or(JsxMode::Vapor);
+    self.jsx_modes.push(mode);
+    if mode == JsxMode::Vapor {
+      self.roots.push((node as *mut Expression, self.statement));
+    }
+  }
+
+  fn exit_expression(&mut self, node: &mut Expression<'a>, ctx: &mut TraverseCtx<'a, ()>) {
+    if !matches!(node, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
+      return;
+    }
+    // vDOM JSX is compiled bottom-up, so that its nested JSX is already compiled.
+    if self.jsx_modes.pop() == Some(JsxMode::VDom) && self.context.options.vdom {
+      let is_root = self.jsx_modes.is_empty();
+      *node = self
+        .vdom
+        .transform(node.take_in(ctx.ast.allocator), is_root);
+    }
+  }
+
+  fn enter_program(&mut self, _: &mut Program<'a>, ctx: &mut TraverseCtx<'a, ()>) {
+    let mut names = UniqueNames::new(ctx.scoping());
+    names.reserve(self.context.options.reserved_names.iter().cloned());
+    *self.context.names.borrow_mut() = names;
   }
   fn exit_program(&mut self, program: &mut Program<'a>, ctx: &mut TraverseCtx<'a, ()>) {
     if self.context.options.ssr || self.context.options.hmr {
-      HmrOrSsrTraverse::new(self.context.options).exit_program(program, ctx);
+      HmrOrSsrTraverse::new(
+        self.context.options,
+        &self.context.names,
+        std::mem::take(&mut self.components),
+      )
+      .exit_program(program, ctx);
     }
 
     let allocator = ctx.ast.allocator;
+    let per_component_templates = self.context.options.per_component_templates;
+    let mut last_statement = None;
     unsafe {
-      for root in self.roots.drain(..) {
+      for (root, statement) in self.roots.drain(..) {
+        // Templates are only shared by the roots of the same top-level statement.
+        if per_component_templates && last_statement != Some(statement) {
+          *self.context.template_start.borrow_mut() = self.context.options.templates.borrow().len();
+          last_statement = Some(statement);
+        }
         let root = &mut *root;
         let source = &self.source_text[..root.span().end as usize];
         *root = self.context.transform(root.take_in(allocator), source);
@@ -85,13 +232,16 @@ impl<'a, 'ctx: 'a> Traverse<'a, ()> for JsxTraverse<'a, 'ctx> {
 
     let ast = &ctx.ast;
     let mut statements = vec![];
-    let delegates = self.context.options.delegates.take();
+    let delegates = self.context.options.delegates.borrow();
     if !delegates.is_empty() {
       statements.push(ast.statement_expression(
         SPAN,
         ast.expression_call(
           SPAN,
-          ast.expression_identifier(SPAN, ast.atom("_delegateEvents")),
+          ast.expression_identifier(
+            SPAN,
+            ast.atom(&self.context.names.borrow_mut().get("_delegateEvents")),
+          ),
           NONE,
           oxc_allocator::Vec::from_iter_in(
             delegates.iter().map(|delegate| {
@@ -104,72 +254,58 @@ impl<'a, 'ctx: 'a> Traverse<'a, ()> for JsxTraverse<'a, 'ctx> {
       ));
     }
 
-    let mut helpers = self.context.options.helpers.take();
-    if !helpers.is_empty() {
-      let jsx_helpers = vec![
-        "setNodes",
-        "createNodes",
-        "createComponent",
-        "createComponentWithFallback",
-      ]
-      .into_iter()
-      .filter(|helper| {
-        if helpers.contains(*helper) {
-          helpers.remove(*helper);
-          true
-        } else {
-          false
-        }
-      })
-      .collect::<Vec<_>>();
-      if !jsx_helpers.is_empty() {
-        statements.push(Statement::ImportDeclaration(ast.alloc_import_declaration(
-          SPAN,
-          Some(ast.vec_from_iter(jsx_helpers.into_iter().map(|helper| {
-            ast.import_declaration_specifier_import_specifier(
-              SPAN,
-              ast.module_export_name_identifier_name(SPAN, ast.atom(helper)),
-              ast.binding_identifier(SPAN, ast.atom(format!("_{}", helper).as_str())),
-              ImportOrExportKind::Value,
-            )
-          }))),
-          ast.string_literal(SPAN, ast.atom("vue-jsx-vapor"), None),
-          None,
-          NONE,
-          ImportOrExportKind::Value,
-        )))
-      }
-
-      if !helpers.is_empty() {
-        statements.push(Statement::ImportDeclaration(ast.alloc_import_declaration(
+    for (name, vnode) in self.vdom.hoisted.drain(..) {
+      statements.push(Statement::VariableDeclaration(
+        ast.alloc_variable_declaration(
           SPAN,
-          Some(ast.vec_from_iter(helpers.iter().map(|helper| {
-            ast.import_declaration_specifier_import_specifier(
-              SPAN,
-              ast.module_export_name_identifier_name(SPAN, ast.atom(helper)),
-              ast.binding_identifier(SPAN, ast.atom(format!("_{}", helper).as_str())),
-              ImportOrExportKind::Value,
-            )
-          }))),
-          ast.string_literal(SPAN, ast.atom("vue"), None),
-          None,
-          NONE,
-          ImportOrExportKind::Value,
-        )))
-      }
+          VariableDeclarationKind::Const,
+          ast.vec1(ast.variable_declarator(
+            SPAN,
+            VariableDeclarationKind::Const,
+            ast.binding_pattern(
+              BindingPatternKind::BindingIdentifier(
+                ast.alloc_binding_identifier(SPAN, ast.atom(&name)),
+              ),
+              NONE,
+              false,
+            ),
+            Some(vnode),
+            false,
+          )),
+          false,
+        ),
+      ));
     }
 
-    let templates = self.context.options.templates.take();
-    let template_len = templates.len();
-    if template_len > 0 {
-      let template_statements = templates
+    let templates = self.context.options.templates.borrow();
+    let mut template_statements = vec![];
+    if let Some(module) = self.context.options.template_module {
+      if !templates.is_empty() {
+        let specifiers = templates
+          .iter()
+          .enumerate()
+          .map(|(index, template)| {
+            let name = self.context.names.borrow_mut().get(&format!("t{index}"));
+            (template_export(template), name)
+          })
+          .collect::<Vec<_>>();
+        let specifiers = specifiers
+          .iter()
+          .map(|(export, name)| (export.as_str(), name.as_str()))
+          .collect::<Vec<_>>();
+        statements.insert(
+          0,
+          import_named(*ast, self.context.options, module, &specifiers),
+        );
+      }
+    } else if !templates.is_empty() {
+      let template_helper = self.context.names.borrow_mut().get("_template");
+      template_statements = templates
         .iter()
         .enumerate()
         .map(|(index, template)| {
-          let template_literal =
-            Argument::StringLiteral(ast.alloc_string_literal(SPAN, ast.atom(&template.0), None));
-
-          Statement::VariableDeclaration(ast.alloc_variable_declaration(
+          let name = self.context.names.borrow_mut().get(&format!("t{index}"));
+          let statement = Statement::VariableDeclaration(ast.alloc_variable_declaration(
             SPAN,
             VariableDeclarationKind::Const,
             ast.vec1(ast.variable_declarator(
@@ -177,42 +313,181 @@ impl<'a, 'ctx: 'a> Traverse<'a, ()> for JsxTraverse<'a, 'ctx> {
               VariableDeclarationKind::Const,
               ast.binding_pattern(
                 BindingPatternKind::BindingIdentifier(
-                  ast.alloc_binding_identifier(SPAN, ast.atom(&format!("t{index}"))),
+                  ast.alloc_binding_identifier(SPAN, ast.atom(&name)),
                 ),
                 NONE,
                 false,
               ),
-              Some(ast.expression_call(
-                SPAN,
-                ast.expression_identifier(SPAN, ast.atom("_template")),
-                NONE,
-                if template.1 {
-                  ast.vec_from_array([
-                    template_literal,
-                    Argument::BooleanLiteral(ast.alloc_boolean_literal(SPAN, template.1)),
-                  ])
-                } else {
-                  oxc_allocator::Vec::from_array_in([template_literal], ast.allocator)
-                },
-                false,
-              )),
+              Some(create_template(*ast, &template_helper, template)),
               false,
             )),
             false,
-          ))
+          ));
+          (name, statement)
         })
         .collect::<Vec<_>>();
-      statements.extend(template_statements);
     }
 
-    if !statements.is_empty() {
-      // Insert statements before the first non-import statement.
-      let index = program
-        .body
-        .iter()
-        .position(|stmt| !matches!(stmt, Statement::ImportDeclaration(_)))
-        .unwrap_or(program.body.len());
+    // Insert statements before the first non-import statement.
+    let index = program
+      .body
+      .iter()
+      .position(|stmt| !matches!(stmt, Statement::ImportDeclaration(_)))
+      .unwrap_or(program.body.len());
+    if per_component_templates {
       program.body.splice(index..index, statements);
+      insert_templates(program, template_statements, index);
+    } else {
+      statements.extend(
+        template_statements
+          .into_iter()
+          .map(|(_, statement)| statement),
+      );
+      program.body.splice(index..index, statements);
+    }
+
+    // Helper imports are created last so that namespace and global import
+    // styles can rewrite the inserted statements too. Imports are hoisted and
+    // go after `_delegateEvents`, while `require` must come before it.
+    let helpers = self.context.options.helpers.borrow().clone();
+    if !helpers.is_empty() {
+      let index = if self.context.options.source_type.is_script() {
+        index
+      } else {
+        index + usize::from(!delegates.is_empty())
+      };
+      let imports = import_helpers(
+        *ast,
+        self.context.options,
+        ctx.scoping(),
+        &mut self.context.names.borrow_mut(),
+        program,
+        helpers,
+      );
+      program.body.splice(index..index, imports);
+    }
+  }
+}
+
+/// Inserts each template right before the first top-level statement that
+/// references it, or at `index` if it's unused. A template of a component
+/// that can be used before its own statement, i.e. a function declaration or
+/// a binding referenced by an earlier statement, goes before the first such
+/// use instead.
+fn insert_templates<'a>(
+  program: &mut Program<'a>,
+  templates: Vec<(String, Statement<'a>)>,
+  index: usize,
+) {
+  let mut positions = templates
+    .iter()
+    .map(|_| None)
+    .collect::<Vec<Option<usize>>>();
+  let names = templates
+    .iter()
+    .enumerate()
+    .map(|(template, (name, _))| (name.as_str(), template))
+    .collect::<HashMap<_, _>>();
+  for (position, statement) in program.body.iter().enumerate() {
+    let mut references = TemplateReferences {
+      names: &names,
+      templates: vec![],
+    };
+    references.visit_statement(statement);
+    for template in references.templates {
+      positions[template].get_or_insert(position);
+    }
+  }
+  let positions = positions
+    .into_iter()
+    .map(|position| {
+      let position = position?;
+      let (bindings, hoisted) = declared_bindings(&program.body[position]);
+      if hoisted {
+        return None;
+      }
+      let bindings = bindings
+        .into_iter()
+        .map(|binding| (binding, 0))
+        .collect::<HashMap<_, _>>();
+      let first_use = program.body[..position].iter().position(|statement| {
+        let mut references = TemplateReferences {
+          names: &bindings,
+          templates: vec![],
+        };
+        references.visit_statement(statement);
+        !references.templates.is_empty()
+      });
+      Some(first_use.unwrap_or(position))
+    })
+    .collect::<Vec<_>>();
+  let mut templates = templates
+    .into_iter()
+    .zip(positions)
+    .map(|((_, statement), position)| (position.unwrap_or(index).max(index), statement))
+    .collect::<Vec<_>>();
+  // Insert from the end so that the positions stay valid.
+  templates.sort_by_key(|(position, _)| *position);
+  for (position, statement) in templates.into_iter().rev() {
+    program.body.insert(position, statement);
+  }
+}
+
+/// Returns the bindings declared by a top-level statement, and whether they
+/// are hoisted, i.e. initialized before any statement runs.
+fn declared_bindings<'s>(statement: &'s Statement) -> (Vec<&'s str>, bool) {
+  let declaration = match statement {
+    Statement::ExportNamedDeclaration(decl) => decl.declaration.as_ref(),
+    Statement::ExportDefaultDeclaration(decl) => {
+      return match &decl.declaration {
+        ExportDefaultDeclarationKind::FunctionDeclaration(function) => (
+          function.id.iter().map(|id| id.name.as_str()).collect(),
+          true,
+        ),
+        ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+          (class.id.iter().map(|id| id.name.as_str()).collect(), false)
+        }
+        _ => (vec![], false),
+      };
+    }
+    _ => statement.as_declaration(),
+  };
+  match declaration {
+    Some(Declaration::VariableDeclaration(decl)) => (
+      decl
+        .declarations
+        .iter()
+        .flat_map(|declarator| declarator.id.get_binding_identifiers())
+        .map(|id| id.name.as_str())
+        .collect(),
+      false,
+    ),
+    Some(Declaration::FunctionDeclaration(function)) => (
+      function.id.iter().map(|id| id.name.as_str()).collect(),
+      true,
+    ),
+    Some(declaration) => (
+      declaration
+        .id()
+        .map(|id| id.name.as_str())
+        .into_iter()
+        .collect(),
+      false,
+    ),
+    None => (vec![], false),
+  }
+}
+
+/// Collects the templates referenced by a statement.
+struct TemplateReferences<'n> {
+  names: &'n HashMap<&'n str, usize>,
+  templates: Vec<usize>,
+}
+
+impl<'a> Visit<'a> for TemplateReferences<'_> {
+  fn visit_identifier_reference(&mut self, node: &IdentifierReference<'a>) {
+    if let Some(template) = self.names.get(node.name.as_str()) {
+      self.templates.push(*template);
     }
   }
 }
@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, BTreeSet, btree_map::Entry};
+
+use oxc_allocator::Allocator;
+use oxc_ast::{
+  AstBuilder, NONE,
+  ast::{Argument, Expression, ImportOrExportKind, Statement, VariableDeclarationKind},
+};
+use oxc_codegen::{Codegen, CodegenOptions, IndentChar};
+use oxc_semantic::Scoping;
+use oxc_span::{SPAN, SourceType};
+
+#[cfg(feature = "napi")]
+use crate::compile::CompilerOptions;
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+
+use crate::{
+  compile::Template,
+  transform::TransformOptions,
+  traverse::imports::import_helpers,
+  utils::{error::ErrorCodes, hash::fnv1a, names::UniqueNames},
+};
+
+/// Returns the export of `template` from the template module. It only depends
+/// on the template, so every file imports the same template by the same name.
+pub fn template_export(template: &Template) -> String {
+  let (html, root) = template;
+  let hash = fnv1a(format!("{}{html}", u8::from(*root)).as_bytes());
+  format!("t_{hash:016x}")
+}
+
+/// Creates `/* @__PURE__ */ _template("<div></div>", true)`.
+pub fn create_template<'a>(
+  ast: AstBuilder<'a>,
+  helper: &str,
+  template: &Template,
+) -> Expression<'a> {
+  let template_literal =
+    Argument::StringLiteral(ast.alloc_string_literal(SPAN, ast.atom(&template.0), None));
+  ast.expression_call_with_pure(
+    SPAN,
+    ast.expression_identifier(SPAN, ast.atom(helper)),
+    NONE,
+    if template.1 {
+      ast.vec_from_array([
+        template_literal,
+        Argument::BooleanLiteral(ast.alloc_boolean_literal(SPAN, template.1)),
+      ])
+    } else {
+      ast.vec1(template_literal)
+    },
+    false,
+    true,
+  )
+}
+
+/// Generates the module that `template_module` points to, which exports each
+/// of the `templates` collected from many files once, sorted by their export.
+/// Different templates whose exports collide are reported, and only the first
+/// one is exported.
+pub fn generate_template_module<'t>(
+  templates: impl IntoIterator<Item = &'t Template>,
+  options: &TransformOptions,
+) -> String {
+  let templates = templates.into_iter().collect::<BTreeSet<_>>();
+  let mut exports = BTreeMap::new();
+  for template in templates {
+    let export = template_export(template);
+    match exports.entry(export) {
+      Entry::Vacant(entry) => {
+        entry.insert(template);
+      }
+      Entry::Occupied(_) => (options.on_error)(ErrorCodes::XTemplateExportCollision, SPAN),
+    }
+  }
+  let templates = exports;
+
+  let allocator = Allocator::default();
+  let ast = AstBuilder::new(&allocator);
+  let mut names = UniqueNames::from_names(templates.keys().cloned());
+  let helper = names.get("_template");
+  let mut program = ast.program(
+    SPAN,
+    SourceType::mjs(),
+    "",
+    ast.vec(),
+    None,
+    ast.vec(),
+    ast.vec_from_iter(templates.iter().map(|(export, template)| {
+      Statement::from(ast.module_declaration_export_named_declaration(
+        SPAN,
+        Some(ast.declaration_variable(
+          SPAN,
+          VariableDeclarationKind::Const,
+          ast.vec1(ast.variable_declarator(
+            SPAN,
+            VariableDeclarationKind::Const,
+            ast.binding_pattern(
+              ast.binding_pattern_kind_binding_identifier(SPAN, ast.atom(export)),
+              NONE,
+              false,
+            ),
+            Some(create_template(ast, &helper, template)),
+            false,
+          )),
+          false,
+        )),
+        ast.vec(),
+        None,
+        ImportOrExportKind::Value,
+        NONE,
+      ))
+    })),
+  );
+  if !templates.is_empty() {
+    let imports = import_helpers(
+      ast,
+      options,
+      &Scoping::default(),
+      &mut names,
+      &mut program,
+      ["template".to_string()],
+    );
+    program.body.splice(0..0, imports);
+  }
+  Codegen::new()
+    .with_options(CodegenOptions {
+      indent_char: IndentChar::Space,
+      indent_width: 2,
+      ..CodegenOptions::default()
+    })
+    .build(&program)
+    .code
+}
+
+/// Generates the template module from the templates of many compilations.
+#[cfg(feature = "napi")]
+#[napi]
+pub fn _generate_template_module(
+  env: napi::Env,
+  templates: Vec<Template>,
+  options: Option<CompilerOptions>,
+) -> String {
+  use crate::utils::error::create_compiler_error;
+
+  let options = options.unwrap_or_default();
+  let filename = options.template_module.unwrap_or_default();
+  let on_error = options.on_error;
+  generate_template_module(
+    &templates,
+    &TransformOptions {
+      on_error: Box::new(|code, span| {
+        if let Some(on_error) = &on_error
+          && let Ok(error) = create_compiler_error(&env, code, span, &filename)
+        {
+          on_error.call(error).ok();
+        }
+      }),
+      source_type: SourceType::mjs(),
+      runtime_modules: options.runtime_modules.unwrap_or_default(),
+      runtime_globals: options.runtime_globals.unwrap_or_default(),
+      import_style: options.import_style.unwrap_or_default(),
+      ..Default::default()
+    },
+  )
+}
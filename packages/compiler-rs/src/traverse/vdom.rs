@@ -0,0 +1,1130 @@
+use std::mem;
+
+use oxc_allocator::{CloneIn, TakeIn};
+use oxc_ast::{
+  AstBuilder, NONE,
+  ast::{
+    Argument, ArrayExpressionElement, AssignmentOperator, BinaryOperator, BindingPatternKind,
+    Expression, FormalParameterKind, JSXAttribute, JSXAttributeItem, JSXAttributeName,
+    JSXAttributeValue, JSXChild, JSXElement, JSXElementName, JSXExpression, JSXFragment,
+    JSXMemberExpression, JSXMemberExpressionObject, NumberBase, ObjectPropertyKind, PropertyKey,
+    PropertyKind, UnaryOperator,
+  },
+};
+use oxc_span::{GetSpan, SPAN, Span};
+
+use crate::{
+  transform::TransformContext,
+  utils::{
+    check::{is_html_tag, is_simple_identifier, is_svg_tag},
+    error::ErrorCodes,
+    expression::into_assignment_target,
+    text::{is_empty_text, resolve_jsx_text},
+  },
+};
+
+// Patch flags of `@vue/shared`, a vnode with a patch flag only has the
+// flagged parts patched.
+const TEXT: i32 = 1;
+const CLASS: i32 = 1 << 1;
+const STYLE: i32 = 1 << 2;
+const PROPS: i32 = 1 << 3;
+const FULL_PROPS: i32 = 1 << 4;
+const STABLE_FRAGMENT: i32 = 1 << 6;
+const KEYED_FRAGMENT: i32 = 1 << 7;
+const UNKEYED_FRAGMENT: i32 = 1 << 8;
+const NEED_PATCH: i32 = 1 << 9;
+const DYNAMIC_SLOTS: i32 = 1 << 10;
+const HOISTED: i32 = -1;
+const BAIL: i32 = -2;
+
+// Slot flags of `@vue/shared`, stable slots are only updated when their own
+// dependencies change, dynamic ones whenever the parent re-renders.
+const SLOTS_STABLE: i32 = 1;
+const SLOTS_DYNAMIC: i32 = 2;
+
+/// Directives that only work in Vapor JSX.
+const UNSUPPORTED_DIRECTIVES: [&str; 3] = ["v-slot", "v-once", "v-memo"];
+
+/// Compiles JSX in vDOM mode to `createVNode` calls, so that mixed files
+/// don't need another JSX plugin.
+pub struct VDomTransform<'a, 'ctx> {
+  ast: AstBuilder<'a>,
+  context: &'ctx TransformContext<'a>,
+  /// Fully static vnodes, declared once at the top of the file.
+  pub hoisted: Vec<(String, Expression<'a>)>,
+  /// Whether the vnode being compiled is part of a block tree, i.e. is
+  /// rendered as a child of a root block.
+  blocks: bool,
+  /// Source of the file, for the parameters of `v-for`.
+  pub source_text: &'a str,
+  /// Spans of the nested JSX compiled for the current root, an expression
+  /// child without any is rendered as text.
+  nested: Vec<Span>,
+}
+
+/// A compiled child, static if neither it nor its descendants ever change.
+struct VNode<'a> {
+  expression: Expression<'a>,
+  is_static: bool,
+}
+
+/// A compiled child of an element, component or fragment.
+enum Child<'a> {
+  Text(String),
+  /// An expression without JSX, which is rendered as text like an
+  /// interpolation of a template.
+  DynamicText(Expression<'a>),
+  VNode(VNode<'a>),
+  Expression(Expression<'a>),
+  Spread(Expression<'a>),
+}
+
+impl<'a, 'ctx> VDomTransform<'a, 'ctx> {
+  pub fn new(ast: AstBuilder<'a>, context: &'ctx TransformContext<'a>) -> Self {
+    Self {
+      ast,
+      context,
+      hoisted: vec![],
+      blocks: false,
+      source_text: "",
+      nested: vec![],
+    }
+  }
+
+  /// Compiles a JSX root, `is_root` unless it's nested in other JSX. Like the
+  /// render of a Vue template, a root opens a block that collects its dynamic
+  /// descendants, so that only they are patched. Nested JSX, e.g. in a prop,
+  /// isn't necessarily rendered where it's created and never opens a block.
+  pub fn transform(&mut self, node: Expression<'a>, is_root: bool) -> Expression<'a> {
+    self.blocks = is_root;
+    if !is_root {
+      self.nested.push(node.span());
+    }
+    let vnode = match node {
+      Expression::JSXElement(node) => self.transform_element(node.unbox(), is_root),
+      Expression::JSXFragment(node) => self.transform_fragment(node.unbox(), is_root),
+      node => return node,
+    };
+    // Nested JSX is compiled before its root.
+    if is_root {
+      self.nested.clear();
+    }
+    self.hoist(vnode)
+  }
+
+  fn helper(&self, name: &str) -> Expression<'a> {
+    self
+      .context
+      .options
+      .helpers
+      .borrow_mut()
+      .insert(name.to_string());
+    let name = self.context.names.borrow_mut().get(&format!("_{name}"));
+    self.ast.expression_identifier(SPAN, self.ast.atom(&name))
+  }
+
+  fn call_helper(&self, name: &str, arguments: Vec<Expression<'a>>) -> Expression<'a> {
+    self.ast.expression_call(
+      SPAN,
+      self.helper(name),
+      NONE,
+      self
+        .ast
+        .vec_from_iter(arguments.into_iter().map(Argument::from)),
+      false,
+    )
+  }
+
+  fn string(&self, value: &str) -> Expression<'a> {
+    self
+      .ast
+      .expression_string_literal(SPAN, self.ast.atom(value), None)
+  }
+
+  fn number(&self, value: i32) -> Expression<'a> {
+    let literal = self.ast.expression_numeric_literal(
+      SPAN,
+      value.unsigned_abs().into(),
+      None,
+      NumberBase::Decimal,
+    );
+    if value < 0 {
+      self
+        .ast
+        .expression_unary(SPAN, UnaryOperator::UnaryNegation, literal)
+    } else {
+      literal
+    }
+  }
+
+  fn property(&self, key: &str, value: Expression<'a>) -> ObjectPropertyKind<'a> {
+    let key = if is_simple_identifier(key) {
+      self
+        .ast
+        .property_key_static_identifier(SPAN, self.ast.atom(key))
+    } else {
+      PropertyKey::StringLiteral(
+        self
+          .ast
+          .alloc_string_literal(SPAN, self.ast.atom(key), None),
+      )
+    };
+    self.ast.object_property_kind_object_property(
+      SPAN,
+      PropertyKind::Init,
+      key,
+      value,
+      false,
+      false,
+      false,
+    )
+  }
+
+  /// `() => [children]`
+  fn slot_function(&self, children: Expression<'a>) -> Expression<'a> {
+    self.ast.expression_arrow_function(
+      SPAN,
+      true,
+      false,
+      NONE,
+      self.ast.formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        self.ast.vec(),
+        NONE,
+      ),
+      NONE,
+      self.ast.function_body(
+        SPAN,
+        self.ast.vec(),
+        self.ast.vec1(self.ast.statement_expression(SPAN, children)),
+      ),
+    )
+  }
+
+  /// `(openBlock(), vnode)`, `openBlock(true)` doesn't collect the dynamic
+  /// descendants, e.g. of a `v-for` fragment whose items are blocks themselves.
+  fn open_block(&self, vnode: Expression<'a>, disable_tracking: bool) -> Expression<'a> {
+    let ast = self.ast;
+    let arguments = if disable_tracking {
+      vec![ast.expression_boolean_literal(SPAN, true)]
+    } else {
+      vec![]
+    };
+    ast.expression_parenthesized(
+      SPAN,
+      ast.expression_sequence(
+        SPAN,
+        ast.vec_from_array([self.call_helper("openBlock", arguments), vnode]),
+      ),
+    )
+  }
+
+  /// Declares a static vnode at the top of the file and returns its name.
+  fn hoist(&mut self, vnode: VNode<'a>) -> Expression<'a> {
+    let VNode {
+      mut expression,
+      is_static,
+    } = vnode;
+    if !is_static {
+      return expression;
+    }
+    if let Expression::CallExpression(call) = &mut expression {
+      while call.arguments.len() < 3 {
+        call
+          .arguments
+          .push(self.ast.expression_null_literal(SPAN).into());
+      }
+      call.arguments.push(self.number(HOISTED).into());
+      call.pure = true;
+    }
+    let name = self
+      .context
+      .names
+      .borrow_mut()
+      .get(&format!("_hoisted_{}", self.hoisted.len() + 1));
+    self.hoisted.push((name.clone(), expression));
+    self.ast.expression_identifier(SPAN, self.ast.atom(&name))
+  }
+
+  fn transform_fragment(&mut self, mut node: JSXFragment<'a>, is_root: bool) -> VNode<'a> {
+    let children = self.transform_children(node.children.take_in(self.ast.allocator));
+    let bail = has_unknown_children(&children);
+    let is_block = is_root || (self.blocks && bail);
+    let patch_flag = match (is_block, bail) {
+      (false, _) => 0,
+      (true, false) => STABLE_FRAGMENT,
+      (true, true) => BAIL,
+    };
+    let children = self.children_array(children);
+    let mut expression = self.create_vnode(
+      if is_block {
+        "createElementBlock"
+      } else {
+        "createVNode"
+      },
+      self.helper("Fragment"),
+      None,
+      Some(children),
+      patch_flag,
+      vec![],
+    );
+    if is_block {
+      expression = self.open_block(expression, false);
+    }
+    VNode {
+      expression,
+      is_static: false,
+    }
+  }
+
+  /// `a ? (openBlock(), createElementBlock(...)) : createCommentVNode("v-if", true)`
+  /// for an element with `v-if` and its `v-else-if` and `v-else` siblings.
+  /// Like in a template, each branch is a block keyed by its index from `key`,
+  /// so that switching branches replaces the vnode instead of patching it.
+  fn transform_if(&mut self, branches: Vec<JSXElement<'a>>, key: usize) -> VNode<'a> {
+    let ast = self.ast;
+    let blocks = mem::replace(&mut self.blocks, true);
+    let mut compiled = vec![];
+    for (index, mut node) in branches.into_iter().enumerate() {
+      let condition = ["v-if", "v-else-if"]
+        .into_iter()
+        .find_map(|name| take_attribute(&mut node, name))
+        .map(|directive| match directive.value {
+          Some(value) => self.attribute_value(value),
+          None => self
+            .context
+            .error_placeholder(ErrorCodes::VIfNoExpression, directive.span),
+        });
+      take_attribute(&mut node, "v-else");
+      let key = self.number((key + index) as i32);
+      let vnode = if let Some(directive) = take_attribute(&mut node, "v-for") {
+        self.transform_for(node, directive, Some(key))
+      } else {
+        if find_attribute(&node, "key").is_none() {
+          node.opening_element.attributes.insert(
+            0,
+            ast.jsx_attribute_item_attribute(
+              SPAN,
+              ast.jsx_attribute_name_identifier(SPAN, "key"),
+              Some(ast.jsx_attribute_value_expression_container(SPAN, key.into())),
+            ),
+          );
+        }
+        self.transform_element(node, true)
+      };
+      compiled.push((condition, vnode.expression));
+    }
+    self.blocks = blocks;
+
+    let mut expression = self.call_helper(
+      "createCommentVNode",
+      vec![
+        self.string("v-if"),
+        ast.expression_boolean_literal(SPAN, true),
+      ],
+    );
+    for (condition, consequent) in compiled.into_iter().rev() {
+      expression = match condition {
+        Some(condition) => ast.expression_conditional(SPAN, condition, consequent, expression),
+        None => consequent,
+      };
+    }
+    VNode {
+      expression,
+      is_static: false,
+    }
+  }
+
+  /// `(openBlock(true), createElementBlock(Fragment, null, renderList(source, (item) => vnode), flag))`
+  /// for an element with `v-for={item in source}`. The items are blocks, which
+  /// the fragment tells apart by their `key` if they have one.
+  fn transform_for(
+    &mut self,
+    node: JSXElement<'a>,
+    directive: JSXAttribute<'a>,
+    key: Option<Expression<'a>>,
+  ) -> VNode<'a> {
+    let ast = self.ast;
+    let Some(value) = directive.value else {
+      return VNode {
+        expression: self
+          .context
+          .error_placeholder(ErrorCodes::VForNoExpression, directive.span),
+        is_static: false,
+      };
+    };
+    let mut expression = self.attribute_value(value);
+    while let Expression::ParenthesizedExpression(parenthesized) = expression {
+      expression = parenthesized.unbox().expression;
+    }
+    let expression = match expression {
+      Expression::BinaryExpression(expression) if expression.operator == BinaryOperator::In => {
+        expression.unbox()
+      }
+      _ => {
+        return VNode {
+          expression: self
+            .context
+            .error_placeholder(ErrorCodes::VForMalformedExpression, directive.span),
+          is_static: false,
+        };
+      }
+    };
+    // The parameters are kept as they're written, e.g. `({ id }, index)`.
+    let params = match expression.left.without_parentheses() {
+      Expression::SequenceExpression(params) => params.expressions.iter().collect(),
+      param => vec![param],
+    };
+    let params = ast.vec_from_iter(params.into_iter().map(|param| {
+      ast.formal_parameter(
+        SPAN,
+        ast.vec(),
+        ast.binding_pattern(
+          BindingPatternKind::BindingIdentifier(ast.alloc_binding_identifier(
+            param.span(),
+            ast.atom(param.span().source_text(self.source_text)),
+          )),
+          NONE,
+          false,
+        ),
+        None,
+        false,
+        false,
+      )
+    }));
+
+    let patch_flag = if find_attribute(&node, "key").is_some() {
+      KEYED_FRAGMENT
+    } else {
+      UNKEYED_FRAGMENT
+    };
+    let blocks = mem::replace(&mut self.blocks, true);
+    let item = self.transform_element(node, true).expression;
+    self.blocks = blocks;
+    let render = ast.expression_arrow_function(
+      SPAN,
+      true,
+      false,
+      NONE,
+      ast.formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        params,
+        NONE,
+      ),
+      NONE,
+      ast.function_body(
+        SPAN,
+        ast.vec(),
+        ast.vec1(ast.statement_expression(SPAN, item)),
+      ),
+    );
+    let props = key.map(|key| ast.expression_object(SPAN, ast.vec1(self.property("key", key))));
+    let expression = self.create_vnode(
+      "createElementBlock",
+      self.helper("Fragment"),
+      props,
+      Some(self.call_helper("renderList", vec![expression.right, render])),
+      patch_flag,
+      vec![],
+    );
+    VNode {
+      expression: self.open_block(expression, true),
+      is_static: false,
+    }
+  }
+
+  fn transform_element(&mut self, mut node: JSXElement<'a>, is_root: bool) -> VNode<'a> {
+    let ast = self.ast;
+    if find_attribute(&node, "v-if").is_some() {
+      return self.transform_if(vec![node], 0);
+    }
+    if let Some(directive) = take_attribute(&mut node, "v-for") {
+      return self.transform_for(node, directive, None);
+    }
+    // Branches are taken by the `v-if` before them.
+    if let Some(directive) =
+      take_attribute(&mut node, "v-else-if").or_else(|| take_attribute(&mut node, "v-else"))
+    {
+      return VNode {
+        expression: self
+          .context
+          .error_placeholder(ErrorCodes::VElseNoAdjacentIf, directive.span),
+        is_static: false,
+      };
+    }
+    let options = self.context.options;
+    let name = node.opening_element.name.take_in(ast.allocator);
+    let tag = match &name {
+      JSXElementName::Identifier(name) => Some(name.name.to_string()),
+      _ => None,
+    };
+    let mut attributes = node.opening_element.attributes.take_in(ast.allocator);
+
+    let dynamic_component = tag.as_deref().and_then(|tag| {
+      let prop = if tag == "component" { "is" } else { "v-is" };
+      let index = attributes.iter().position(|attribute| {
+        matches!(attribute, JSXAttributeItem::Attribute(attribute)
+          if matches!(&attribute.name, JSXAttributeName::Identifier(name) if name.name == prop))
+      })?;
+      let JSXAttributeItem::Attribute(attribute) = attributes.remove(index) else {
+        unreachable!()
+      };
+      Some(self.attribute_value(attribute.unbox().value?))
+    });
+    let is_component = dynamic_component.is_some()
+      || match &tag {
+        Some(tag) => {
+          !is_html_tag(tag) && !is_svg_tag(tag) && !(options.is_custom_element)(tag.clone())
+        }
+        None => !matches!(name, JSXElementName::NamespacedName(_)),
+      };
+    let tag_expression = if let Some(component) = dynamic_component {
+      self.call_helper("resolveDynamicComponent", vec![component])
+    } else {
+      match name {
+        JSXElementName::Identifier(name) if is_component => {
+          self.call_helper("resolveComponent", vec![self.string(&name.name)])
+        }
+        JSXElementName::Identifier(name) => self.string(&name.name),
+        JSXElementName::NamespacedName(name) => {
+          self.string(&format!("{}:{}", name.namespace.name, name.name.name))
+        }
+        JSXElementName::IdentifierReference(name) => {
+          ast.expression_identifier(name.span, name.name)
+        }
+        JSXElementName::MemberExpression(name) => self.member_expression(name.unbox()),
+        JSXElementName::ThisExpression(name) => ast.expression_this(name.span),
+      }
+    };
+
+    let mut is_static = !is_component;
+    let mut patch_flag = 0;
+    let mut dynamic_props = vec![];
+    let mut has_ref = false;
+    let mut objects = vec![];
+    let mut properties = ast.vec();
+    let mut directives = vec![];
+    let mut slots = None;
+    for attribute in attributes {
+      let attribute = match attribute {
+        JSXAttributeItem::SpreadAttribute(spread) => {
+          if !properties.is_empty() {
+            objects.push(ast.expression_object(SPAN, properties.take_in(ast.allocator)));
+          }
+          objects.push(spread.unbox().argument);
+          patch_flag |= FULL_PROPS;
+          is_static = false;
+          continue;
+        }
+        JSXAttributeItem::Attribute(attribute) => attribute.unbox(),
+      };
+      let span = attribute.span;
+      let (name, arg) = match &attribute.name {
+        JSXAttributeName::Identifier(name) => (name.name.to_string(), None),
+        JSXAttributeName::NamespacedName(name) => (
+          name.namespace.name.to_string(),
+          Some(name.name.name.to_string()),
+        ),
+      };
+      let value = attribute.value.map(|value| self.attribute_value(value));
+
+      if name.starts_with("v-") {
+        is_static = false;
+        // e.g. `v-model_trim` or `v-model:value_trim`
+        let (name, arg, modifiers) = match arg {
+          Some(arg) => {
+            let mut words = arg.split('_').map(String::from);
+            (name, words.next(), words.collect::<Vec<_>>())
+          }
+          None => {
+            let mut words = name.split('_').map(String::from);
+            (words.next().unwrap_or_default(), None, words.collect())
+          }
+        };
+        match name.as_str() {
+          "v-slots" => match value {
+            Some(value) => slots = Some(value),
+            None => options.on_error.as_ref()(ErrorCodes::VSlotsNoExpression, span),
+          },
+          "v-show" => match value {
+            Some(value) => directives.push(vec![self.helper("vShow"), value]),
+            None => options.on_error.as_ref()(ErrorCodes::VShowNoExpression, span),
+          },
+          "v-html" | "v-text" => match value {
+            Some(value) => {
+              let key = if name == "v-html" {
+                "innerHTML"
+              } else {
+                "textContent"
+              };
+              dynamic_props.push(key.to_string());
+              properties.push(self.property(key, value));
+            }
+            None => options.on_error.as_ref()(
+              if name == "v-html" {
+                ErrorCodes::VHtmlNoExpression
+              } else {
+                ErrorCodes::VTextNoExpression
+              },
+              span,
+            ),
+          },
+          "v-model" => {
+            let Some(value) = value else {
+              options.on_error.as_ref()(ErrorCodes::VModelNoExpression, span);
+              continue;
+            };
+            let Ok(target) = into_assignment_target(value.clone_in(ast.allocator)) else {
+              options.on_error.as_ref()(ErrorCodes::VModelMalformedExpression, span);
+              continue;
+            };
+            let prop = arg.unwrap_or_else(|| "modelValue".to_string());
+            let event = format!("onUpdate:{prop}");
+            let handler = self.model_handler(target);
+            let modifiers = self.modifiers(&modifiers);
+            if is_component {
+              dynamic_props.push(prop.clone());
+              properties.push(self.property(&prop, value));
+              if let Some(modifiers) = modifiers {
+                let key = if prop == "modelValue" {
+                  "modelModifiers".to_string()
+                } else {
+                  format!("{prop}Modifiers")
+                };
+                properties.push(self.property(&key, modifiers));
+              }
+            } else {
+              let directive = match tag.as_deref() {
+                Some("input") => "vModelDynamic",
+                Some("select") => "vModelSelect",
+                Some("textarea") => "vModelText",
+                _ => {
+                  options.on_error.as_ref()(ErrorCodes::VModelOnInvalidElement, span);
+                  "vModelText"
+                }
+              };
+              let mut directive = vec![self.helper(directive), value];
+              if let Some(modifiers) = modifiers {
+                directive.push(ast.expression_identifier(SPAN, "undefined"));
+                directive.push(modifiers);
+              }
+              directives.push(directive);
+            }
+            dynamic_props.push(event.clone());
+            properties.push(self.property(&event, handler));
+          }
+          _ if UNSUPPORTED_DIRECTIVES.contains(&name.as_str()) => {
+            options.on_error.as_ref()(ErrorCodes::VDomUnsupportedDirective, span);
+          }
+          _ => {
+            let mut directive = vec![
+              self.call_helper("resolveDirective", vec![self.string(&name[2..])]),
+              value.unwrap_or_else(|| ast.expression_identifier(SPAN, "undefined")),
+            ];
+            let modifiers = self.modifiers(&modifiers);
+            if arg.is_some() || modifiers.is_some() {
+              directive.push(match arg {
+                Some(arg) => self.string(&arg),
+                None => ast.expression_identifier(SPAN, "undefined"),
+              });
+            }
+            directive.extend(modifiers);
+            directives.push(directive);
+          }
+        }
+        continue;
+      }
+
+      let key = match arg {
+        Some(arg) => format!("{name}:{arg}"),
+        None => name,
+      };
+      let value = value.unwrap_or_else(|| ast.expression_boolean_literal(SPAN, true));
+      if key == "key" || key == "ref" {
+        is_static = false;
+        has_ref |= key == "ref";
+      } else if !is_static_value(&value) {
+        is_static = false;
+        match key.as_str() {
+          "class" if !is_component => patch_flag |= CLASS,
+          "style" if !is_component => patch_flag |= STYLE,
+          _ => dynamic_props.push(key.clone()),
+        }
+      }
+      properties.push(self.property(&key, value));
+    }
+
+    let props = if objects.is_empty() {
+      (!properties.is_empty()).then(|| ast.expression_object(SPAN, properties))
+    } else {
+      if !properties.is_empty() {
+        objects.push(ast.expression_object(SPAN, properties));
+      }
+      Some(self.call_helper("mergeProps", objects))
+    };
+
+    let mut children = self.transform_children(node.children.take_in(ast.allocator));
+    // A block only patches the vnodes it collected, children of unknown
+    // shape are only patched when the element bails out of that.
+    let bail = !is_component && has_unknown_children(&children);
+    let children = if is_component {
+      let (slots, slots_flag) = self.component_slots(children, slots);
+      if slots_flag == SLOTS_DYNAMIC {
+        patch_flag |= DYNAMIC_SLOTS;
+      }
+      slots
+    } else if children.iter().all(|child| matches!(child, Child::Text(_))) {
+      let text = children
+        .iter()
+        .map(|child| match child {
+          Child::Text(text) => text.as_str(),
+          _ => unreachable!(),
+        })
+        .collect::<String>();
+      (!text.is_empty()).then(|| self.string(&text))
+    } else if !is_component
+      && children
+        .iter()
+        .all(|child| matches!(child, Child::Text(_) | Child::DynamicText(_)))
+    {
+      // Like an element with interpolations in a template, only its text is patched.
+      patch_flag |= TEXT;
+      is_static = false;
+      Some(self.concatenate_text(children))
+    } else if is_static
+      && children.iter().all(|child| {
+        matches!(
+          child,
+          Child::Text(_)
+            | Child::VNode(VNode {
+              is_static: true,
+              ..
+            })
+        )
+      })
+    {
+      // The descendants are hoisted together with the element.
+      let children = children
+        .drain(..)
+        .map(|child| match child {
+          Child::Text(text) => self.string(&text).into(),
+          Child::VNode(vnode) => vnode.expression.into(),
+          _ => unreachable!(),
+        })
+        .collect::<Vec<ArrayExpressionElement>>();
+      Some(ast.expression_array(SPAN, ast.vec_from_iter(children)))
+    } else {
+      is_static = false;
+      Some(self.children_array(children))
+    };
+
+    if patch_flag & FULL_PROPS != 0 {
+      patch_flag &= !(CLASS | STYLE | PROPS);
+      dynamic_props.clear();
+    } else if !dynamic_props.is_empty() {
+      patch_flag |= PROPS;
+    }
+    if patch_flag == 0 && (has_ref || !directives.is_empty()) {
+      patch_flag = NEED_PATCH;
+    }
+    let is_block = !is_static && (is_root || (self.blocks && bail));
+    if is_block && bail {
+      patch_flag = BAIL;
+      dynamic_props.clear();
+    }
+
+    let helper = match (is_block, is_component) {
+      (false, _) => "createVNode",
+      (true, false) => "createElementBlock",
+      (true, true) => "createBlock",
+    };
+    let mut expression = self.create_vnode(
+      helper,
+      tag_expression,
+      props,
+      children,
+      patch_flag,
+      dynamic_props,
+    );
+    if is_block {
+      expression = self.open_block(expression, false);
+    }
+    if !directives.is_empty() {
+      let directives = directives
+        .into_iter()
+        .map(|directive| {
+          ast
+            .expression_array(
+              SPAN,
+              ast.vec_from_iter(directive.into_iter().map(ArrayExpressionElement::from)),
+            )
+            .into()
+        })
+        .collect::<Vec<ArrayExpressionElement>>();
+      expression = self.call_helper(
+        "withDirectives",
+        vec![
+          expression,
+          ast.expression_array(SPAN, ast.vec_from_iter(directives)),
+        ],
+      );
+    }
+    VNode {
+      expression,
+      is_static,
+    }
+  }
+
+  /// `helper(tag, props, children, patchFlag, dynamicProps)` without the trailing nulls,
+  /// where `helper` is `createVNode` or creates a block.
+  fn create_vnode(
+    &self,
+    helper: &str,
+    tag: Expression<'a>,
+    props: Option<Expression<'a>>,
+    children: Option<Expression<'a>>,
+    patch_flag: i32,
+    dynamic_props: Vec<String>,
+  ) -> Expression<'a> {
+    let ast = self.ast;
+    let mut arguments = vec![Some(tag), props, children];
+    if patch_flag != 0 {
+      arguments.push(Some(self.number(patch_flag)));
+    }
+    if !dynamic_props.is_empty() {
+      arguments.push(Some(
+        ast.expression_array(
+          SPAN,
+          ast.vec_from_iter(
+            dynamic_props
+              .iter()
+              .map(|prop| ArrayExpressionElement::from(self.string(prop))),
+          ),
+        ),
+      ));
+    }
+    while arguments.last().is_some_and(Option::is_none) {
+      arguments.pop();
+    }
+    self.call_helper(
+      helper,
+      arguments
+        .into_iter()
+        .map(|argument| argument.unwrap_or_else(|| ast.expression_null_literal(SPAN)))
+        .collect(),
+    )
+  }
+
+  fn member_expression(&self, node: JSXMemberExpression<'a>) -> Expression<'a> {
+    let object = match node.object {
+      JSXMemberExpressionObject::IdentifierReference(object) => {
+        self.ast.expression_identifier(object.span, object.name)
+      }
+      JSXMemberExpressionObject::MemberExpression(object) => self.member_expression(object.unbox()),
+      JSXMemberExpressionObject::ThisExpression(object) => self.ast.expression_this(object.span),
+    };
+    self
+      .ast
+      .member_expression_static(
+        node.span,
+        object,
+        self
+          .ast
+          .identifier_name(node.property.span, node.property.name),
+        false,
+      )
+      .into()
+  }
+
+  fn attribute_value(&mut self, value: JSXAttributeValue<'a>) -> Expression<'a> {
+    match value {
+      JSXAttributeValue::StringLiteral(value) => Expression::StringLiteral(value),
+      JSXAttributeValue::ExpressionContainer(mut value) => {
+        match value.expression.take_in(self.ast.allocator) {
+          JSXExpression::EmptyExpression(_) => self.ast.expression_boolean_literal(SPAN, true),
+          expression => expression.into_expression(),
+        }
+      }
+      // A prop isn't necessarily rendered, so it's left out of the block tree.
+      JSXAttributeValue::Element(value) => {
+        let blocks = mem::replace(&mut self.blocks, false);
+        let vnode = self.transform_element(value.unbox(), false);
+        self.blocks = blocks;
+        self.hoist(vnode)
+      }
+      JSXAttributeValue::Fragment(value) => {
+        let blocks = mem::replace(&mut self.blocks, false);
+        let vnode = self.transform_fragment(value.unbox(), false);
+        self.blocks = blocks;
+        self.hoist(vnode)
+      }
+    }
+  }
+
+  /// `{ trim: true }` for `["trim"]`.
+  fn modifiers(&self, modifiers: &[String]) -> Option<Expression<'a>> {
+    (!modifiers.is_empty()).then(|| {
+      self.ast.expression_object(
+        SPAN,
+        self.ast.vec_from_iter(modifiers.iter().map(|modifier| {
+          self.property(modifier, self.ast.expression_boolean_literal(SPAN, true))
+        })),
+      )
+    })
+  }
+
+  /// `$event => (value = $event)`
+  fn model_handler(&self, target: oxc_ast::ast::SimpleAssignmentTarget<'a>) -> Expression<'a> {
+    let ast = self.ast;
+    ast.expression_arrow_function(
+      SPAN,
+      true,
+      false,
+      NONE,
+      ast.formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec1(ast.formal_parameter(
+          SPAN,
+          ast.vec(),
+          ast.binding_pattern(
+            BindingPatternKind::BindingIdentifier(ast.alloc_binding_identifier(SPAN, "$event")),
+            NONE,
+            false,
+          ),
+          None,
+          false,
+          false,
+        )),
+        NONE,
+      ),
+      NONE,
+      ast.function_body(
+        SPAN,
+        ast.vec(),
+        ast.vec1(ast.statement_expression(
+          SPAN,
+          ast.expression_parenthesized(
+            SPAN,
+            ast.expression_assignment(
+              SPAN,
+              AssignmentOperator::Assign,
+              target.into(),
+              ast.expression_identifier(SPAN, "$event"),
+            ),
+          ),
+        )),
+      ),
+    )
+  }
+
+  fn transform_children(
+    &mut self,
+    children: oxc_allocator::Vec<'a, JSXChild<'a>>,
+  ) -> Vec<Child<'a>> {
+    let mut result = vec![];
+    // Keys of the `v-if` branches, unique among their siblings.
+    let mut key = 0;
+    let mut children = children.into_iter().peekable();
+    while let Some(child) = children.next() {
+      match child {
+        JSXChild::Text(text) => {
+          let text = resolve_jsx_text(&text);
+          if !text.is_empty() {
+            result.push(Child::Text(text));
+          }
+        }
+        JSXChild::ExpressionContainer(mut container) => {
+          match container.expression.take_in(self.ast.allocator) {
+            JSXExpression::EmptyExpression(_) => {}
+            JSXExpression::StringLiteral(literal) => {
+              result.push(Child::Text(literal.value.to_string()))
+            }
+            expression => {
+              let expression = expression.into_expression();
+              result.push(
+                if self
+                  .nested
+                  .iter()
+                  .any(|span| container.span.contains_inclusive(*span))
+                {
+                  Child::Expression(expression)
+                } else {
+                  Child::DynamicText(expression)
+                },
+              );
+            }
+          }
+        }
+        JSXChild::Element(element) if find_attribute(&element, "v-if").is_some() => {
+          let mut branches = vec![element.unbox()];
+          while let Some(next) = children.peek() {
+            if is_empty_text(next) {
+              children.next();
+              continue;
+            }
+            let JSXChild::Element(next) = next else {
+              break;
+            };
+            let is_else = find_attribute(next, "v-else").is_some();
+            if !is_else && find_attribute(next, "v-else-if").is_none() {
+              break;
+            }
+            let Some(JSXChild::Element(next)) = children.next() else {
+              unreachable!()
+            };
+            branches.push(next.unbox());
+            if is_else {
+              break;
+            }
+          }
+          let count = branches.len();
+          result.push(Child::VNode(self.transform_if(branches, key)));
+          key += count;
+        }
+        JSXChild::Element(element) => {
+          result.push(Child::VNode(self.transform_element(element.unbox(), false)));
+        }
+        JSXChild::Fragment(fragment) => {
+          result.push(Child::VNode(
+            self.transform_fragment(fragment.unbox(), false),
+          ));
+        }
+        JSXChild::Spread(spread) => result.push(Child::Spread(spread.unbox().expression)),
+      }
+    }
+    result
+  }
+
+  /// `[children]`, with the static elements hoisted.
+  fn children_array(&mut self, children: Vec<Child<'a>>) -> Expression<'a> {
+    let ast = self.ast;
+    let elements = children
+      .into_iter()
+      .map(|child| match child {
+        Child::Text(text) => self.string(&text).into(),
+        Child::DynamicText(expression) => {
+          let text = self.call_helper("toDisplayString", vec![expression]);
+          self
+            .call_helper("createTextVNode", vec![text, self.number(TEXT)])
+            .into()
+        }
+        Child::VNode(vnode) => self.hoist(vnode).into(),
+        Child::Expression(expression) => expression.into(),
+        Child::Spread(expression) => {
+          ArrayExpressionElement::SpreadElement(ast.alloc_spread_element(SPAN, expression))
+        }
+      })
+      .collect::<Vec<_>>();
+    ast.expression_array(SPAN, ast.vec_from_iter(elements))
+  }
+
+  /// `"text " + toDisplayString(expression)` for children that are all text.
+  fn concatenate_text(&self, children: Vec<Child<'a>>) -> Expression<'a> {
+    children
+      .into_iter()
+      .map(|child| match child {
+        Child::Text(text) => self.string(&text),
+        Child::DynamicText(expression) => self.call_helper("toDisplayString", vec![expression]),
+        _ => unreachable!(),
+      })
+      .reduce(|left, right| {
+        self
+          .ast
+          .expression_binary(SPAN, left, BinaryOperator::Addition, right)
+      })
+      .unwrap()
+  }
+
+  /// The slots object of a component and its slot flag, its children are the
+  /// default slot, unless they are an object of slots themselves. Only the
+  /// compiled slots are flagged, they are stable if they are fully static,
+  /// otherwise they may close over values of the render and are dynamic.
+  fn component_slots(
+    &mut self,
+    mut children: Vec<Child<'a>>,
+    slots: Option<Expression<'a>>,
+  ) -> (Option<Expression<'a>>, i32) {
+    let ast = self.ast;
+    if children.is_empty() {
+      return (slots, 0);
+    }
+    if slots.is_none()
+      && let [
+        Child::Expression(Expression::ObjectExpression(_))
+        | Child::DynamicText(Expression::ObjectExpression(_)),
+      ] = children.as_slice()
+      && let Some(Child::Expression(object) | Child::DynamicText(object)) = children.pop()
+    {
+      return (Some(object), 0);
+    }
+    let is_static = slots.is_none()
+      && children.iter().all(|child| {
+        matches!(
+          child,
+          Child::Text(_)
+            | Child::VNode(VNode {
+              is_static: true,
+              ..
+            })
+        )
+      });
+    let flag = if is_static {
+      SLOTS_STABLE
+    } else {
+      SLOTS_DYNAMIC
+    };
+    let children = self.children_array(children);
+    let default = self.slot_function(children);
+    let mut properties = ast.vec1(self.property("default", default));
+    if let Some(slots) = slots {
+      properties.push(ObjectPropertyKind::SpreadProperty(
+        ast.alloc_spread_element(SPAN, slots),
+      ));
+    }
+    properties.push(self.property("_", self.number(flag)));
+    (Some(ast.expression_object(SPAN, properties)), flag)
+  }
+}
+
+/// Index of the attribute `name`, e.g. of a directive without arguments.
+fn find_attribute(node: &JSXElement, name: &str) -> Option<usize> {
+  node
+    .opening_element
+    .attributes
+    .iter()
+    .position(|attribute| {
+      matches!(attribute, JSXAttributeItem::Attribute(attribute)
+      if matches!(&attribute.name, JSXAttributeName::Identifier(id) if id.name == name))
+    })
+}
+
+/// Removes the attribute `name` from the element.
+fn take_attribute<'a>(node: &mut JSXElement<'a>, name: &str) -> Option<JSXAttribute<'a>> {
+  let index = find_attribute(node, name)?;
+  let JSXAttributeItem::Attribute(attribute) = node.opening_element.attributes.remove(index) else {
+    unreachable!()
+  };
+  Some(attribute.unbox())
+}
+
+/// Whether a child is an expression with JSX, which may render anything.
+fn has_unknown_children(children: &[Child]) -> bool {
+  children
+    .iter()
+    .any(|child| matches!(child, Child::Expression(_) | Child::Spread(_)))
+}
+
+/// Whether the prop value never changes.
+fn is_static_value(value: &Expression) -> bool {
+  matches!(
+    value.without_parentheses(),
+    Expression::StringLiteral(_)
+      | Expression::NumericLiteral(_)
+      | Expression::BooleanLiteral(_)
+      | Expression::NullLiteral(_)
+  )
+}
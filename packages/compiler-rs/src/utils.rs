@@ -1,7 +1,14 @@
 pub mod check;
 pub mod directive;
 pub mod dom;
+pub mod either;
 pub mod error;
 pub mod expression;
+pub mod factory;
+pub mod hash;
+pub mod names;
+pub mod offset_map;
+pub mod pragma;
+pub mod source_map;
 pub mod text;
 pub mod walk;
@@ -6,7 +6,10 @@ use oxc_span::GetSpan;
 use oxc_traverse::{Ancestor, TraverseAncestry};
 use phf::phf_set;
 
-use crate::{ir::index::SimpleExpressionNode, utils::expression::is_globally_allowed};
+use crate::{
+  ir::index::SimpleExpressionNode,
+  utils::{directive::find_prop, either::Either, expression::is_globally_allowed},
+};
 
 pub fn is_member_expression(exp: &SimpleExpressionNode) -> bool {
   let Some(ast) = &exp.ast else { return false };
@@ -295,9 +298,14 @@ pub fn is_svg_tag(tag_name: &str) -> bool {
   SVG_TAGS.contains(tag_name)
 }
 
+/// Whether `node` is rendered as a component, native elements are too when
+/// they're escaped with `v-is={Comp}`.
 pub fn is_jsx_component<'a>(node: &'a JSXElement<'a>) -> bool {
   match &node.opening_element.name {
-    JSXElementName::Identifier(name) => !is_html_tag(&name.name) && !is_svg_tag(&name.name),
+    JSXElementName::Identifier(name) => {
+      (!is_html_tag(&name.name) && !is_svg_tag(&name.name))
+        || find_prop(node, Either::A(String::from("v-is"))).is_some()
+    }
     _ => true,
   }
 }
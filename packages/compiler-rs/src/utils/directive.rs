@@ -1,7 +1,7 @@
-use napi::bindgen_prelude::{Either, Either3};
 use oxc_ast::ast::{JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXElement};
 use oxc_span::SPAN;
 
+use crate::utils::either::{Either, Either3};
 use crate::{
   ir::index::{DirectiveNode, SimpleExpressionNode},
   transform::TransformContext,
@@ -35,9 +35,26 @@ pub fn resolve_directive<'a>(
   } else {
     let cloned = arg_string.clone();
     let splited = &mut cloned.split("$").collect::<Vec<_>>();
-    if splited.len() > 1 {
+    if splited.len() > 3 && splited[1].is_empty() {
+      // `$$...$` is an expression, see `encode_dynamic_argument`.
       is_static = false;
-      arg_string = splited[1].replace("_", ".");
+      arg_string = decode_dynamic_argument(splited[2]);
+      if !splited[3].is_empty() {
+        modifiers = splited[3][1..]
+          .split("_")
+          .map(|s| s.to_string())
+          .collect::<Vec<_>>();
+      }
+    } else if splited.len() > 1 {
+      is_static = false;
+      // `$foo_bar$` is `foo.bar`, leading underscores belong to the name, e.g. `$_ctx_foo$`.
+      let path = splited[1];
+      let name = path.trim_start_matches('_');
+      arg_string = format!(
+        "{}{}",
+        &path[..path.len() - name.len()],
+        name.replace("_", ".")
+      );
       if !splited[2].is_empty() {
         modifiers = splited[2][1..]
           .split("_")
@@ -84,7 +101,8 @@ pub fn resolve_directive<'a>(
   let exp = node
     .value
     .as_mut()
-    .map(|exp| SimpleExpressionNode::new(Either3::C(exp), context));
+    .map(|exp| SimpleExpressionNode::new(Either3::C(exp), context))
+    .filter(|exp| !exp.is_empty());
 
   let modifiers = modifiers
     .into_iter()
@@ -104,6 +122,41 @@ pub fn resolve_directive<'a>(
   }
 }
 
+/// Encodes `expression` as a dynamic argument of a JSX directive, e.g.
+/// `v-slot:$$-5fctx-2ename$` for `_ctx.name`. Unlike `$foo_bar$`, which is the
+/// path `foo.bar`, it keeps any expression: characters other than ASCII letters
+/// and digits are escaped as `-` and the hex of their bytes.
+pub fn encode_dynamic_argument(expression: &str) -> String {
+  let mut encoded = String::from("$$");
+  for byte in expression.bytes() {
+    if byte.is_ascii_alphanumeric() {
+      encoded.push(byte as char);
+    } else {
+      encoded.push_str(&format!("-{byte:02x}"));
+    }
+  }
+  encoded.push('$');
+  encoded
+}
+
+fn decode_dynamic_argument(encoded: &str) -> String {
+  let mut bytes = vec![];
+  let mut rest = encoded.as_bytes();
+  while let [byte, tail @ ..] = rest {
+    if *byte == b'-'
+      && let Some(hex) = tail.get(..2)
+      && let Ok(decoded) = u8::from_str_radix(&String::from_utf8_lossy(hex), 16)
+    {
+      bytes.push(decoded);
+      rest = &tail[2..];
+    } else {
+      bytes.push(*byte);
+      rest = tail;
+    }
+  }
+  String::from_utf8_lossy(&bytes).into_owned()
+}
+
 macro_rules! define_find_prop {
   ($fn_name:ident, $node_type: ty, $ret_type: ty, $iter: tt) => {
     pub fn $fn_name<'a>(node: $node_type, key: Either<String, Vec<String>>) -> Option<$ret_type> {
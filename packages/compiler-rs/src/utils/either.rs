@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+/// Serialized as `{ "type": "A", "value": ... }`, so the variant stays known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Either<A, B> {
+  A(A),
+  B(B),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Either3<A, B, C> {
+  A(A),
+  B(B),
+  C(C),
+}
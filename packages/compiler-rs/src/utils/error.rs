@@ -1,37 +1,91 @@
 use std::{collections::HashMap, sync::LazyLock};
 
+#[cfg(feature = "napi")]
 use napi::{Env, Error, Result, bindgen_prelude::Object};
+#[cfg(feature = "napi")]
 use napi_derive::napi;
+#[cfg(feature = "napi")]
 use oxc_span::Span;
 
+/// Errors reported through `on_error`. Compiling goes on after each of them,
+/// so that every error of a file is reported in one pass, and the broken
+/// parts recover as documented on each variant.
 #[cfg_attr(feature = "napi", napi)]
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorCodes {
+  /// The unterminated tag is closed at the end of the template.
+  EofInTag = 9,
+  /// The end tag is ignored.
+  XInvalidEndTag = 23,
+  /// The element is closed at the end of its parent.
+  XMissingEndTag = 24,
+  /// The rest of the template is rendered as text.
+  XMissingInterpolationEnd = 25,
+  /// The condition throws when it's evaluated, the other branches still compile.
   VIfNoExpression = 28,
+  /// The element is replaced with a placeholder that throws when it's rendered.
   VElseNoAdjacentIf = 30,
+  /// The element is replaced with a placeholder that throws when it's rendered.
   VForNoExpression = 31,
+  /// The element is replaced with a placeholder that throws when it's rendered.
   VForMalformedExpression = 32,
+  /// The handler is a no-op.
   VOnNoExpression = 35,
+  /// The component is replaced with a placeholder that throws when it's rendered.
   VSlotMixedSlotUsage = 37,
+  /// The duplicate slot is dropped.
   VSlotDuplicateSlotNames = 38,
+  /// The extraneous children are dropped.
   VSlotExtraneousDefaultSlotChildren = 39,
+  /// The directive is ignored.
   VSlotMisplaced = 40,
+  /// The element is rendered without two-way binding.
   VModelNoExpression = 41,
+  /// The element is rendered without two-way binding.
   VModelMalformedExpression = 42,
+  /// The element content is cleared.
   VHtmlNoExpression = 53,
+  /// The children are dropped, the directive replaces them as in Vue.
   VHtmlWithChildren = 54,
+  /// The element content is cleared.
   VTextNoExpression = 55,
+  /// The children are dropped, the directive replaces them as in Vue.
   VTextWithChildren = 56,
+  /// Warning, the binding still compiles.
   VModelOnInvalidElement = 57,
+  /// Warning, the argument is ignored.
   VModelArgOnElement = 58,
+  /// Warning, the binding still compiles.
   VModelOnFileInputElement = 59,
+  /// Warning, both bindings still compile.
   VModelUnnecessaryValue = 60,
+  /// The directive is ignored.
   VShowNoExpression = 61,
+  /// The directive is ignored.
   VSlotsNoExpression = 62,
+  /// The directive is ignored.
+  VDomUnsupportedDirective = 63,
+  /// The directive is applied without its argument, e.g. to the default slot.
+  XUnsupportedDynamicArgument = 64,
+  /// The root is left out of the IR dump.
+  XIrSerializationFailed = 65,
+  /// The file is transformed as far as it could be parsed.
+  XSyntaxError = 66,
+  /// The source map maps to the source the compiler received.
+  XInvalidInputSourceMap = 67,
+  /// Only the first of the templates is exported from the template module.
+  XTemplateExportCollision = 68,
 }
 
 pub static ERROR_MESSAGES: LazyLock<HashMap<ErrorCodes, &str>> = LazyLock::new(|| {
   HashMap::from([
+    (ErrorCodes::EofInTag, "Unexpected EOF in tag."),
+    (ErrorCodes::XInvalidEndTag, "Invalid end tag."),
+    (ErrorCodes::XMissingEndTag, "Element is missing end tag."),
+    (
+      ErrorCodes::XMissingInterpolationEnd,
+      "Interpolation end sign was not found.",
+    ),
     (
       ErrorCodes::VIfNoExpression,
       "v-if/v-else-if is missing expression.",
@@ -110,19 +164,50 @@ pub static ERROR_MESSAGES: LazyLock<HashMap<ErrorCodes, &str>> = LazyLock::new(|
       ErrorCodes::VShowNoExpression,
       "v-show is missing expression.",
     ),
+    (
+      ErrorCodes::VDomUnsupportedDirective,
+      "v-slot, v-once and v-memo are only supported in Vapor JSX.",
+    ),
+    (
+      ErrorCodes::XUnsupportedDynamicArgument,
+      "Dynamic arguments of v-slot, v-model and custom directives must be an expression.",
+    ),
+    (
+      ErrorCodes::XIrSerializationFailed,
+      "Failed to serialize the IR of this JSX root for `dumpIr`.",
+    ),
+    (ErrorCodes::XSyntaxError, "Invalid JavaScript syntax."),
+    (
+      ErrorCodes::XInvalidInputSourceMap,
+      "`inputSourceMap` is not a valid source map.",
+    ),
+    (
+      ErrorCodes::XTemplateExportCollision,
+      "Different templates have the same export in the template module.",
+    ),
   ])
 });
 
-#[napi(object, js_name = "CompilerError extends SyntaxError")]
+#[cfg_attr(
+  feature = "napi",
+  napi(object, js_name = "CompilerError extends SyntaxError")
+)]
 pub struct CompilerError {
   pub code: i32,
   pub loc: Option<(u32, u32)>,
 }
 
-pub fn create_compiler_error<'a>(env: &'a Env, code: ErrorCodes, loc: Span) -> Result<Object<'a>> {
-  let msg = ERROR_MESSAGES.get(&code).unwrap().to_string();
-  let mut error = env.create_error(Error::from_reason(&msg))?;
+#[cfg(feature = "napi")]
+pub fn create_compiler_error<'a>(
+  env: &'a Env,
+  code: ErrorCodes,
+  loc: Span,
+  filename: &str,
+) -> Result<Object<'a>> {
+  let msg = ERROR_MESSAGES.get(&code).copied().unwrap_or_default();
+  let mut error = env.create_error(Error::from_reason(msg))?;
   error.set("code", code as i32)?;
   error.set("loc", (loc.start, loc.end))?;
+  error.set("filename", filename)?;
   Ok(error)
 }
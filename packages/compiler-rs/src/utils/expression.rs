@@ -1,19 +1,23 @@
-use napi::bindgen_prelude::Either3;
-
-use oxc_ast::ast::{Expression, JSXAttributeValue, JSXChild};
+use oxc_ast::ast::{Expression, JSXAttributeValue, JSXChild, SimpleAssignmentTarget};
 use oxc_span::{GetSpan, SPAN, Span};
 use phf::phf_set;
+use serde::Serialize;
 
+use crate::utils::either::Either3;
 use crate::{
+  ir::index::serialize_span,
   transform::TransformContext,
   utils::text::{get_text_like_value, resolve_jsx_text},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SimpleExpressionNode<'a> {
   pub content: String,
   pub is_static: bool,
+  #[serde(serialize_with = "serialize_span")]
   pub loc: Span,
+  #[serde(skip)]
   pub ast: Option<&'a mut Expression<'a>>,
 }
 
@@ -55,7 +59,17 @@ impl<'a> SimpleExpressionNode<'a> {
       }
       Either3::B(node) => match node {
         JSXChild::ExpressionContainer(node) => {
-          let expression = node.expression.to_expression_mut();
+          // `{}` and `{/* comment */}` have no expression.
+          let span = node.span;
+          let Some(expression) = node.expression.as_expression_mut() else {
+            loc = span;
+            return SimpleExpressionNode {
+              content: String::new(),
+              is_static,
+              ast,
+              loc,
+            };
+          };
           loc = expression.span();
           ast = Some(expression);
           loc.source_text(context.ir.borrow().source).to_string()
@@ -76,7 +90,16 @@ impl<'a> SimpleExpressionNode<'a> {
       },
       Either3::C(node) => match node {
         JSXAttributeValue::ExpressionContainer(node) => {
-          let expression = node.expression.to_expression_mut();
+          let span = node.span;
+          let Some(expression) = node.expression.as_expression_mut() else {
+            loc = span;
+            return SimpleExpressionNode {
+              content: String::new(),
+              is_static,
+              ast,
+              loc,
+            };
+          };
           is_static = matches!(expression, Expression::StringLiteral(_));
           loc = expression.span();
           ast = Some(expression);
@@ -103,6 +126,11 @@ impl<'a> SimpleExpressionNode<'a> {
     }
   }
 
+  /// Whether there's no expression, e.g. `{}` or a missing value.
+  pub fn is_empty(&self) -> bool {
+    !self.is_static && self.ast.is_none() && self.content.trim().is_empty()
+  }
+
   pub fn is_constant_expression(&self) -> bool {
     is_literal_whitelisted(&self.content)
       || is_globally_allowed(&self.content)
@@ -160,3 +188,30 @@ static GLOBALLY_ALLOWED: phf::Set<&'static str> = phf_set! {
 pub fn is_globally_allowed(key: &str) -> bool {
   GLOBALLY_ALLOWED.contains(key)
 }
+
+/// Converts `expression` into the target of an assignment, or gives it back
+/// if it can't be assigned to.
+pub fn into_assignment_target(
+  expression: Expression<'_>,
+) -> Result<SimpleAssignmentTarget<'_>, Expression<'_>> {
+  Ok(match expression {
+    Expression::Identifier(id) => SimpleAssignmentTarget::AssignmentTargetIdentifier(id),
+    Expression::StaticMemberExpression(member) => {
+      SimpleAssignmentTarget::StaticMemberExpression(member)
+    }
+    Expression::ComputedMemberExpression(member) => {
+      SimpleAssignmentTarget::ComputedMemberExpression(member)
+    }
+    Expression::PrivateFieldExpression(member) => {
+      SimpleAssignmentTarget::PrivateFieldExpression(member)
+    }
+    Expression::TSAsExpression(node) => SimpleAssignmentTarget::TSAsExpression(node),
+    Expression::TSSatisfiesExpression(node) => SimpleAssignmentTarget::TSSatisfiesExpression(node),
+    Expression::TSNonNullExpression(node) => SimpleAssignmentTarget::TSNonNullExpression(node),
+    Expression::TSTypeAssertion(node) => SimpleAssignmentTarget::TSTypeAssertion(node),
+    Expression::ParenthesizedExpression(node) => {
+      return into_assignment_target(node.unbox().expression);
+    }
+    expression => return Err(expression),
+  })
+}
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use oxc_ast::ast::{
+  Expression, IdentifierReference, ImportDeclarationSpecifier, Program, Statement,
+};
+use oxc_semantic::Scoping;
+
+use crate::{transform::TransformOptions, utils::pragma::JsxMode};
+
+pub fn default_vapor_factories() -> Vec<String> {
+  vec!["defineVaporComponent".to_string()]
+}
+
+pub fn default_vdom_factories() -> Vec<String> {
+  vec!["defineComponent".to_string()]
+}
+
+pub fn default_factory_modules() -> Vec<String> {
+  vec!["vue".to_string(), "vue-jsx-vapor".to_string()]
+}
+
+/// Local bindings of the component factories of a file, e.g. `dvc` for
+/// `import { defineVaporComponent as dvc } from "vue"`.
+#[derive(Debug, Default)]
+pub struct Factories {
+  /// Factories by their exported name.
+  exported: HashMap<String, JsxMode>,
+  /// Factories by their local name. Factories that aren't imported, e.g.
+  /// auto-imported ones, are found by their exported name.
+  locals: HashMap<String, JsxMode>,
+  /// Namespace imports of the factory modules, e.g. `Vue` for
+  /// `import * as Vue from "vue"`.
+  namespaces: HashSet<String>,
+}
+
+impl Factories {
+  pub fn new(program: &Program, options: &TransformOptions) -> Self {
+    let exported: HashMap<String, JsxMode> = options
+      .vapor_factories
+      .iter()
+      .map(|name| (name.clone(), JsxMode::Vapor))
+      .chain(
+        options
+          .vdom_factories
+          .iter()
+          .map(|name| (name.clone(), JsxMode::VDom)),
+      )
+      .collect();
+    let mut locals = exported.clone();
+    let mut namespaces = HashSet::new();
+    for statement in &program.body {
+      let Statement::ImportDeclaration(decl) = statement else {
+        continue;
+      };
+      let Some(specifiers) = &decl.specifiers else {
+        continue;
+      };
+      let from_factory_module = !decl.import_kind.is_type()
+        && options
+          .factory_modules
+          .iter()
+          .any(|module| decl.source.value == module);
+      for specifier in specifiers {
+        let local = specifier.name().to_string();
+        // Bindings of the same name from other modules aren't factories.
+        locals.remove(&local);
+        if !from_factory_module {
+          continue;
+        }
+        match specifier {
+          ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+            if !specifier.import_kind.is_type()
+              && let Some(factory) = exported.get(specifier.imported.name().as_str())
+            {
+              locals.insert(local, *factory);
+            }
+          }
+          ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => {
+            namespaces.insert(local);
+          }
+          ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => {}
+        }
+      }
+    }
+    Self {
+      exported,
+      locals,
+      namespaces,
+    }
+  }
+
+  /// Returns the mode of the factory `callee` refers to, either by its local name,
+  /// e.g. `dvc(...)`, or through a namespace, e.g. `Vue.defineComponent(...)`.
+  pub fn resolve(&self, callee: &Expression, scoping: &Scoping) -> Option<JsxMode> {
+    match callee.without_parentheses() {
+      Expression::Identifier(id) if is_import_or_global(id, scoping) => {
+        self.locals.get(id.name.as_str()).copied()
+      }
+      Expression::StaticMemberExpression(member)
+        if let Expression::Identifier(object) = &member.object
+          && is_import_or_global(object, scoping)
+          && self.namespaces.contains(object.name.as_str()) =>
+      {
+        self.exported.get(member.property.name.as_str()).copied()
+      }
+      _ => None,
+    }
+  }
+}
+
+/// Whether `id` isn't shadowed by a local binding. Imports are unresolved when
+/// an expression is traversed on its own, so they're found by name.
+fn is_import_or_global(id: &IdentifierReference, scoping: &Scoping) -> bool {
+  id.reference_id
+    .get()
+    .and_then(|reference_id| scoping.get_reference(reference_id).symbol_id())
+    .is_none_or(|symbol_id| scoping.symbol_flags(symbol_id).is_import())
+}
@@ -0,0 +1,11 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a of `bytes`. Unlike `DefaultHasher`, the result is specified,
+/// so it's the same across Rust versions and builds, e.g. for names that are
+/// shared between files compiled by different builds.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+    (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+  })
+}
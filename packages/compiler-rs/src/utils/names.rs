@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet};
+
+use oxc_ast::ast::{BindingIdentifier, Expression, IdentifierReference, JSXIdentifier};
+use oxc_ast_visit::Visit;
+use oxc_semantic::Scoping;
+
+/// Generates names that don't collide with the bindings and global
+/// references of the source, e.g. `_template_1` if `_template` is taken.
+#[derive(Default)]
+pub struct UniqueNames {
+  used: HashSet<String>,
+  names: HashMap<String, String>,
+  prefixes: HashMap<String, String>,
+}
+
+impl UniqueNames {
+  /// Avoids every binding and global reference of the program.
+  pub fn new(scoping: &Scoping) -> Self {
+    Self::from_names(
+      scoping
+        .symbol_names()
+        .chain(scoping.root_unresolved_references().keys().copied())
+        .map(String::from),
+    )
+  }
+
+  pub fn from_names(used: impl IntoIterator<Item = String>) -> Self {
+    UniqueNames {
+      used: used.into_iter().collect(),
+      ..Default::default()
+    }
+  }
+
+  /// Avoids `names` too.
+  pub fn reserve(&mut self, names: impl IntoIterator<Item = String>) {
+    self.used.extend(names);
+  }
+
+  /// Returns a prefix for numbered names like `n0` that no used name starts
+  /// with when followed by a digit, e.g. `_n` if `n1` is taken.
+  pub fn prefix(&mut self, prefix: &str) -> String {
+    if let Some(unique) = self.prefixes.get(prefix) {
+      return unique.clone();
+    }
+    let mut unique = prefix.to_string();
+    while self.used.iter().any(|name| {
+      name
+        .strip_prefix(&unique)
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    }) {
+      unique.insert(0, '_');
+    }
+    self.prefixes.insert(prefix.to_string(), unique.clone());
+    unique
+  }
+
+  /// Returns a collision-free name for `name`, the same one on every call.
+  pub fn get(&mut self, name: &str) -> String {
+    if let Some(unique) = self.names.get(name) {
+      return unique.clone();
+    }
+    let mut unique = name.to_string();
+    let mut index = 0;
+    while self.used.contains(&unique) {
+      index += 1;
+      unique = format!("{name}_{index}");
+    }
+    self.used.insert(unique.clone());
+    self.names.insert(name.to_string(), unique.clone());
+    unique
+  }
+}
+
+/// Collects the names of all identifiers in `expression`, generated locals
+/// must avoid them to not shadow the user's bindings.
+pub fn collect_names(expression: &Expression) -> Vec<String> {
+  let mut collector = NameCollector::default();
+  collector.visit_expression(expression);
+  collector.names
+}
+
+#[derive(Default)]
+struct NameCollector {
+  names: Vec<String>,
+}
+
+impl<'a> Visit<'a> for NameCollector {
+  fn visit_identifier_reference(&mut self, node: &IdentifierReference<'a>) {
+    self.names.push(node.name.to_string());
+  }
+
+  fn visit_binding_identifier(&mut self, node: &BindingIdentifier<'a>) {
+    self.names.push(node.name.to_string());
+  }
+
+  fn visit_jsx_identifier(&mut self, node: &JSXIdentifier<'a>) {
+    self.names.push(node.name.to_string());
+  }
+}
@@ -0,0 +1,146 @@
+use oxc_sourcemap::{SourceMap, SourceMapBuilder};
+use oxc_span::Span;
+
+/// Code generated from another source, with the offsets of the parts that
+/// were copied from it, e.g. the expressions of a template lowered to JSX.
+#[derive(Debug, Default)]
+pub struct MappedCode {
+  pub code: String,
+  pub map: OffsetMap,
+}
+
+impl MappedCode {
+  pub fn push(&mut self, code: &str) {
+    self.code.push_str(code);
+  }
+
+  /// Inserts generated `code` at the start.
+  pub fn prepend(&mut self, code: &str) {
+    self.code.insert_str(0, code);
+    for (generated, ..) in &mut self.map.segments {
+      *generated += code.len() as u32;
+    }
+  }
+
+  /// Pushes `code` that was compiled from the part of the source at
+  /// `original`, with `map` from `code` to that part.
+  pub fn push_with_map(&mut self, code: &str, map: &SourceMap, original: &str, start: u32) {
+    let generated_lines = LineIndex::new(code);
+    let original_lines = LineIndex::new(original);
+    let generated = self.code.len() as u32;
+    for token in map.get_tokens() {
+      let (Some(dst), Some(src)) = (
+        generated_lines.offset(token.get_dst_line(), token.get_dst_col()),
+        original_lines.offset(token.get_src_line(), token.get_src_col()),
+      ) else {
+        continue;
+      };
+      self.map.segments.push((generated + dst, start + src, 0));
+    }
+    self.map.segments.sort_by_key(|(generated, ..)| *generated);
+    self.code.push_str(code);
+  }
+
+  /// Pushes `code` that was copied from `original` in the source.
+  pub fn push_mapped(&mut self, code: &str, original: u32) {
+    if !code.is_empty() {
+      self
+        .map
+        .segments
+        .push((self.code.len() as u32, original, code.len() as u32));
+      self.code.push_str(code);
+    }
+  }
+}
+
+/// Maps offsets of generated code back to the source it was generated from.
+#[derive(Debug, Default, Clone)]
+pub struct OffsetMap {
+  /// Generated offset, original offset and length of each copied part.
+  segments: Vec<(u32, u32, u32)>,
+}
+
+impl OffsetMap {
+  /// Returns the original offset of `offset`. Offsets of generated parts map
+  /// to the end of the copied part before them.
+  pub fn original(&self, offset: u32) -> u32 {
+    let index = self
+      .segments
+      .partition_point(|(generated, ..)| *generated <= offset);
+    let Some((generated, original, len)) = index.checked_sub(1).map(|index| self.segments[index])
+    else {
+      return 0;
+    };
+    original + (offset - generated).min(len)
+  }
+
+  /// Generated offset, original offset and length of each copied part.
+  pub fn segments(&self) -> &[(u32, u32, u32)] {
+    &self.segments
+  }
+
+  pub fn original_span(&self, span: Span) -> Span {
+    let start = self.original(span.start);
+    Span::new(start, self.original(span.end).max(start))
+  }
+
+  /// Creates a source map from the generated code to `source`, to be chained
+  /// after the source map of the generated code.
+  pub fn to_source_map(&self, generated: &str, source: &str, filename: &str) -> SourceMap {
+    let generated_lines = LineIndex::new(generated);
+    let source_lines = LineIndex::new(source);
+    let mut builder = SourceMapBuilder::default();
+    let source_id = builder.set_source_and_content(filename, source);
+    for (start, original, len) in &self.segments {
+      // Copied parts are mapped at their start and at each of their lines.
+      let line_starts = generated[*start as usize..(start + len) as usize]
+        .match_indices('\n')
+        .map(|(index, _)| index as u32 + 1)
+        .filter(|offset| offset < len);
+      for offset in std::iter::once(0).chain(line_starts) {
+        let (dst_line, dst_col) = generated_lines.position(start + offset);
+        let (src_line, src_col) = source_lines.position(original + offset);
+        builder.add_token(dst_line, dst_col, src_line, src_col, Some(source_id), None);
+      }
+    }
+    builder.into_sourcemap()
+  }
+}
+
+/// Converts offsets to zero-based lines and UTF-16 columns.
+struct LineIndex<'s> {
+  source: &'s str,
+  starts: Vec<u32>,
+}
+
+impl<'s> LineIndex<'s> {
+  fn new(source: &'s str) -> Self {
+    let starts = std::iter::once(0)
+      .chain(
+        source
+          .match_indices('\n')
+          .map(|(index, _)| index as u32 + 1),
+      )
+      .collect();
+    Self { source, starts }
+  }
+
+  fn offset(&self, line: u32, column: u32) -> Option<u32> {
+    let start = *self.starts.get(line as usize)?;
+    let mut utf16 = 0;
+    for (index, char) in self.source[start as usize..].char_indices() {
+      if utf16 >= column || char == '\n' {
+        return (utf16 == column).then_some(start + index as u32);
+      }
+      utf16 += char.len_utf16() as u32;
+    }
+    (utf16 == column).then_some(self.source.len() as u32)
+  }
+
+  fn position(&self, offset: u32) -> (u32, u32) {
+    let line = self.starts.partition_point(|start| *start <= offset) - 1;
+    let start = self.starts[line] as usize;
+    let column = self.source[start..offset as usize].encode_utf16().count();
+    (line as u32, column as u32)
+  }
+}
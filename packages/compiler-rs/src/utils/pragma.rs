@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use oxc_ast::ast::Program;
+use oxc_span::GetSpan;
+
+/// How the JSX of a component is compiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsxMode {
+  /// Transformed to Vapor DOM, e.g. within `defineVaporComponent` or after `/* @vapor */`.
+  Vapor,
+  /// Left to the vDOM JSX transform, e.g. within `defineComponent` or after `/* @vdom */`.
+  VDom,
+}
+
+/// `@vapor`, `@vdom` and `@jsxMode vapor|vdom` pragmas of a file.
+#[derive(Debug, Default)]
+pub struct Pragmas {
+  /// Mode of the whole file, set by a pragma before the first statement
+  /// that's separated from it by a blank line or a directive prologue.
+  pub file: Option<JsxMode>,
+  /// Modes of the statements and functions, keyed by the start of the node
+  /// their pragma comment is attached to.
+  attached: HashMap<u32, JsxMode>,
+}
+
+impl Pragmas {
+  pub fn new(program: &Program) -> Self {
+    let mut pragmas = Self::default();
+    let first_statement = program
+      .body
+      .first()
+      .map_or(u32::MAX, |node| node.span().start);
+    for comment in &program.comments {
+      let Some(mode) = parse_pragma(comment.content_span().source_text(program.source_text)) else {
+        continue;
+      };
+      // A comment right before the first statement belongs to that statement,
+      // e.g. `/* @vdom */ export const A = () => <div />`.
+      let is_file_pragma = program
+        .directives
+        .first()
+        .is_some_and(|directive| comment.span.end <= directive.span.start)
+        || comment.span.end <= first_statement
+          && program
+            .source_text
+            .get(comment.span.end as usize..first_statement as usize)
+            .is_some_and(has_blank_line);
+      if is_file_pragma {
+        pragmas.file = Some(mode);
+      } else {
+        pragmas.attached.insert(comment.attached_to, mode);
+      }
+    }
+    pragmas
+  }
+
+  /// Returns the mode of the statement or function starting at `start`.
+  pub fn get(&self, start: u32) -> Option<JsxMode> {
+    self.attached.get(&start).copied()
+  }
+}
+
+/// Whether `text` has a line of its own that's blank, not counting the ends
+/// of the lines it starts and ends on.
+fn has_blank_line(text: &str) -> bool {
+  let lines = text.split('\n').collect::<Vec<_>>();
+  lines.len() > 2
+    && lines[1..lines.len() - 1]
+      .iter()
+      .any(|line| line.trim().is_empty())
+}
+
+fn parse_pragma(comment: &str) -> Option<JsxMode> {
+  let mut words = comment
+    .split(|c: char| c.is_whitespace() || c == '*')
+    .filter(|word| !word.is_empty());
+  while let Some(word) = words.next() {
+    match word {
+      "@vapor" => return Some(JsxMode::Vapor),
+      "@vdom" => return Some(JsxMode::VDom),
+      "@jsxMode" => {
+        return match words.next() {
+          Some("vapor") => Some(JsxMode::Vapor),
+          Some("vdom") => Some(JsxMode::VDom),
+          _ => None,
+        };
+      }
+      _ => {}
+    }
+  }
+  None
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use oxc_sourcemap::{SourceMap, Token};
+use oxc_span::{SPAN, Span};
+
+use crate::utils::error::ErrorCodes;
+
+/// Parses the `input_source_map` option, reporting it through `on_error` if
+/// it isn't a valid source map.
+pub fn parse_input_source_map(
+  input_map: &str,
+  on_error: &dyn Fn(ErrorCodes, Span),
+) -> Option<SourceMap> {
+  SourceMap::from_json_string(input_map)
+    .inspect_err(|_| on_error(ErrorCodes::XInvalidInputSourceMap, SPAN))
+    .ok()
+}
+
+/// Remaps `map`, which points at the text the compiler received, through
+/// `input_map` so it points at the original authored sources instead.
+/// Segments without an original position on their line are dropped.
+pub fn chain_source_map(map: SourceMap, input_map: &SourceMap) -> SourceMap {
+  let lookup_table = input_map.generate_lookup_table();
+  let mut names = input_map.get_names().cloned().collect::<Vec<_>>();
+  let tokens = map
+    .get_source_view_tokens()
+    .filter_map(|token| {
+      let original = input_map
+        .lookup_token(&lookup_table, token.get_src_line(), token.get_src_col())
+        // a segment on another line is unrelated, don't rely on the lookup table for it
+        .filter(|original| original.get_dst_line() == token.get_src_line())?;
+      let name_id = original.get_name_id().or_else(|| {
+        let name = token.get_name()?;
+        Some(match names.iter().position(|n| n == name) {
+          Some(index) => index as u32,
+          None => {
+            names.push(Arc::clone(name));
+            names.len() as u32 - 1
+          }
+        })
+      });
+      Some(Token::new(
+        token.get_dst_line(),
+        token.get_dst_col(),
+        original.get_src_line(),
+        original.get_src_col(),
+        original.get_source_id(),
+        name_id,
+      ))
+    })
+    .collect::<Vec<_>>();
+  SourceMap::new(
+    map.get_file().cloned(),
+    names,
+    input_map.get_source_root().map(String::from),
+    input_map.get_sources().cloned().collect(),
+    input_map
+      .get_source_contents()
+      .map(|c| c.cloned())
+      .collect(),
+    tokens.into_boxed_slice(),
+    None,
+  )
+}
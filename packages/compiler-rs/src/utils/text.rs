@@ -42,7 +42,7 @@ fn ends_with_newline_and_spaces(s: &str) -> bool {
 }
 
 pub fn resolve_jsx_text(node: &JSXText) -> String {
-  if is_all_empty_text(&node.raw.unwrap()) {
+  if is_all_empty_text(node.raw.as_ref().unwrap_or(&node.value)) {
     return String::new();
   }
   let mut value = node.value.to_string();
@@ -57,7 +57,7 @@ pub fn resolve_jsx_text(node: &JSXText) -> String {
 
 pub fn is_empty_text(node: &JSXChild) -> bool {
   match node {
-    JSXChild::Text(node) => is_all_empty_text(&node.raw.unwrap()),
+    JSXChild::Text(node) => is_all_empty_text(node.raw.as_ref().unwrap_or(&node.value)),
     JSXChild::ExpressionContainer(node) => {
       matches!(node.expression, JSXExpression::EmptyExpression(_))
     }
@@ -128,7 +128,8 @@ pub fn get_text_like_value(node: &Expression, exclude_number: Option<bool>) -> O
   } else if let Expression::TemplateLiteral(node) = node {
     let mut result = String::new();
     for i in 0..node.quasis.len() {
-      result += node.quasis[i].value.cooked.unwrap().as_ref();
+      // Invalid escapes like `\x` have no cooked value.
+      result += node.quasis[i].value.cooked?.as_ref();
       if let Some(expression) = node.expressions.get(i) {
         let expression_value = get_text_like_value(expression, None)?;
         result += &expression_value;
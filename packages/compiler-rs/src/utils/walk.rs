@@ -1,20 +1,22 @@
 use oxc_allocator::{CloneIn, TakeIn};
 use oxc_ast::ast::{
-  ArrowFunctionExpression, AssignmentTargetMaybeDefault, AssignmentTargetProperty,
-  BindingIdentifier, BindingPattern, BindingPatternKind, BlockStatement, CatchClause, Expression,
-  ForInStatement, ForOfStatement, ForStatement, ForStatementInit, ForStatementLeft, Function,
-  FunctionBody, Program, Statement, VariableDeclarationKind,
+  ArrowFunctionExpression, AssignmentTarget, AssignmentTargetMaybeDefault,
+  AssignmentTargetProperty, BindingIdentifier, BindingPattern, BindingPatternKind, BlockStatement,
+  CatchClause, Expression, ForInStatement, ForOfStatement, ForStatement, ForStatementInit,
+  ForStatementLeft, Function, FunctionBody, Program, Statement, VariableDeclarationKind,
 };
 use oxc_semantic::SemanticBuilder;
 use oxc_traverse::{Ancestor, Traverse, TraverseAncestry, TraverseCtx, traverse_mut};
 use std::collections::{HashMap, HashSet};
 
-use napi::bindgen_prelude::Either3;
 use oxc_ast::{AstKind, ast::IdentifierReference};
 use oxc_span::{GetSpan, SPAN, Span};
 
+use crate::utils::either::Either3;
 use crate::{
-  generate::CodegenContext, transform::TransformContext, utils::check::is_referenced_identifier,
+  generate::CodegenContext,
+  transform::TransformContext,
+  utils::{check::is_referenced_identifier, expression::into_assignment_target, pragma::JsxMode},
 };
 
 type OnIdentifier<'a> = Box<
@@ -136,17 +138,19 @@ impl<'a> Traverse<'a, ()> for WalkIdentifiers<'a, '_> {
       // ;({ baz } = bar)
       AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(id) => {
         if let Some(replacer) = self.on_identifier_reference(&mut id.binding, ctx) {
+          let Ok(target) = into_assignment_target(replacer) else {
+            return;
+          };
+          let target = AssignmentTarget::from(target);
           *node = ast.assignment_target_property_assignment_target_property_property(
             SPAN,
             ast.property_key_static_identifier(id.binding.span, id.binding.name),
-            match replacer {
-              Expression::Identifier(replacer) => {
-                AssignmentTargetMaybeDefault::AssignmentTargetIdentifier(replacer)
-              }
-              Expression::StaticMemberExpression(replacer) => {
-                AssignmentTargetMaybeDefault::StaticMemberExpression(replacer)
-              }
-              _ => unimplemented!(),
+            match id.init.take() {
+              // ;({ baz = 1 } = bar)
+              Some(init) => AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(
+                ast.alloc_assignment_target_with_default(SPAN, target, init),
+              ),
+              None => target.into(),
             },
             false,
           );
@@ -154,22 +158,21 @@ impl<'a> Traverse<'a, ()> for WalkIdentifiers<'a, '_> {
       }
       // ;({ baz: baz } = bar)
       AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) => {
-        match &mut property.binding {
-          AssignmentTargetMaybeDefault::AssignmentTargetIdentifier(id) => {
-            if let Some(replacer) = self.on_identifier_reference(id, ctx) {
-              property.binding = match replacer {
-                Expression::Identifier(replacer) => {
-                  AssignmentTargetMaybeDefault::AssignmentTargetIdentifier(replacer)
-                }
-                Expression::StaticMemberExpression(replacer) => {
-                  AssignmentTargetMaybeDefault::StaticMemberExpression(replacer)
-                }
-                _ => unimplemented!(),
-              };
-            }
+        let target = match &mut property.binding {
+          AssignmentTargetMaybeDefault::AssignmentTargetIdentifier(_) => {
+            property.binding.to_assignment_target_mut()
           }
-          _ => unreachable!(),
+          // ;({ baz: baz = 1 } = bar)
+          AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(node) => &mut node.binding,
+          // Nested patterns and member expressions are visited on their own.
+          _ => return,
         };
+        if let AssignmentTarget::AssignmentTargetIdentifier(id) = target
+          && let Some(replacer) = self.on_identifier_reference(id, ctx)
+          && let Ok(replacer) = into_assignment_target(replacer)
+        {
+          *target = replacer.into();
+        }
       }
     }
   }
@@ -180,13 +183,13 @@ impl<'a> Traverse<'a, ()> for WalkIdentifiers<'a, '_> {
       }
     } else if matches!(node, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
       if self.context.options.interop {
+        let factories = self.context.options.factories.borrow();
         for node in ctx.ancestors() {
           if let Ancestor::CallExpressionArguments(node) = node {
-            let name = node.callee().span().source_text(self.context.ir.source);
-            if name == "defineVaporComponent" {
-              break;
-            } else if name == "defineComponent" {
-              return;
+            match factories.resolve(node.callee(), ctx.scoping()) {
+              Some(JsxMode::Vapor) => break,
+              Some(JsxMode::VDom) => return,
+              None => {}
             }
           }
         }
@@ -374,7 +377,7 @@ pub fn walk_function_params<'a>(
   let params = match node {
     AstKind::Function(node) => &node.params.items,
     AstKind::ArrowFunctionExpression(node) => &node.params.items,
-    _ => panic!(""),
+    _ => return,
   };
   for p in params {
     for id in extract_identifiers(&p.pattern, Vec::new()) {
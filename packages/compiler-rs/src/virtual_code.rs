@@ -0,0 +1,485 @@
+#[cfg(feature = "napi")]
+use napi::Env;
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+  JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement,
+  JSXElementName, JSXFragment,
+};
+use oxc_ast_visit::{VisitMut, walk_mut};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use std::collections::HashSet;
+
+#[cfg(feature = "napi")]
+use crate::compile::CompilerOptions;
+#[cfg(feature = "napi")]
+use crate::transform::{TransformConfig, napi_transform_options};
+use crate::{
+  ir::index::RootIRNode,
+  transform::{TransformContext, TransformOptions, v_for::get_for_parse_result},
+  utils::{
+    check::{is_jsx_component, is_template},
+    directive::resolve_directive,
+    offset_map::MappedCode,
+    text::camelize,
+  },
+};
+
+/// Declarations the virtual code relies on, appended after the source. A file
+/// without imports or exports gets `export {}` as well, so that they're
+/// declared in its module scope instead of colliding with those of other files.
+const PRELUDE: &str = "
+type __ForItem<T> = T extends number ? [number, number, number] : T extends string ? [string, number, number] : T extends Iterable<infer V> ? [V, number, number] : T extends object ? { [K in keyof T]-?: [T[K], K, number] }[keyof T] : [any, any, number];
+declare function __iter<T>(source: T): Iterable<__ForItem<T>>;
+type __Slots<T> = T extends new (...args: any) => { $slots: infer S } ? S : T extends (props: any, ctx: { slots: infer S }) => any ? S : Record<string, ((props: any) => any) | undefined>;
+type __SlotProps<T, K extends PropertyKey> = K extends keyof __Slots<T> ? NonNullable<__Slots<T>[K]> extends (props: infer P, ...args: any) => any ? P : any : any;
+declare function __vSlots<T>(slots: Partial<__Slots<T>>): {};
+declare function __directive(...values: unknown[]): {};
+";
+
+/// Directives that are lowered to TypeScript constructs instead of `__directive`.
+const BUILT_IN_DIRECTIVES: [&str; 12] = [
+  "if", "else-if", "else", "for", "slot", "slots", "model", "show", "html", "text", "once", "memo",
+];
+
+#[cfg_attr(feature = "napi", napi(object))]
+pub struct VirtualCode {
+  pub code: String,
+  /// Parts of `code` copied from the source, e.g. for a language server to
+  /// map diagnostics and completions back to the source.
+  pub mappings: Vec<CodeMapping>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+pub struct CodeMapping {
+  pub source_offset: u32,
+  pub generated_offset: u32,
+  pub length: u32,
+}
+
+#[cfg(feature = "napi")]
+#[napi]
+pub fn _generate_virtual_code(
+  env: Env,
+  source: String,
+  options: Option<CompilerOptions>,
+) -> VirtualCode {
+  let mut options = options.unwrap_or_default();
+  let filename = options.filename.take().unwrap_or("index.tsx".to_string());
+  let config = TransformConfig::from(&mut options);
+  generate_virtual_code(
+    &source,
+    Some(napi_transform_options(env, &config, options, &filename)),
+  )
+}
+
+/// Generates TypeScript that type-checks the directives of the JSX in `source`:
+/// `v-for` is lowered to a `for...of` loop over `__iter(source)`, `v-if` to a
+/// conditional, the props of `v-slot` are typed from the component's slots,
+/// `v-model` of inputs, selects and textareas is checked as their `value` or
+/// `checked`, and the values of the other directives are checked as
+/// expressions. The rest of the source is copied as is. The filename
+/// defaults to `index.tsx`.
+pub fn generate_virtual_code(source: &str, options: Option<TransformOptions>) -> VirtualCode {
+  let options = options.unwrap_or_else(|| TransformOptions {
+    filename: "index.tsx",
+    source_type: SourceType::tsx(),
+    ..Default::default()
+  });
+  let allocator = Allocator::default();
+  let mut program = Parser::new(&allocator, source, options.source_type)
+    .parse()
+    .program;
+  let context = TransformContext::new(&allocator, &options);
+  *context.ir.borrow_mut() = RootIRNode::new(source);
+  let mut generator = VirtualCodeGenerator {
+    context: &context,
+    source,
+    edits: vec![],
+    children: HashSet::new(),
+    components: vec![],
+  };
+  generator.visit_program(&mut program);
+
+  let mut edits = generator.edits;
+  // Edits at the same offset: closing parts of inner elements before those of
+  // outer ones, and opening parts of outer elements before those of inner ones.
+  edits.sort_by_key(|edit| {
+    (
+      edit.start,
+      !edit.closing,
+      if edit.closing { -edit.seq } else { edit.seq },
+    )
+  });
+  let mut out = MappedCode::default();
+  let mut last = 0;
+  for edit in edits {
+    if edit.start < last {
+      continue;
+    }
+    out.push_mapped(&source[last as usize..edit.start as usize], last);
+    for piece in edit.pieces {
+      match piece {
+        Piece::Text(text) => out.push(&text),
+        Piece::Source(span) => out.push_mapped(span.source_text(source), span.start),
+      }
+    }
+    last = edit.end;
+  }
+  out.push_mapped(&source[last as usize..], last);
+  if !program
+    .body
+    .iter()
+    .any(|statement| statement.is_module_declaration())
+  {
+    out.push("\nexport {};");
+  }
+  out.push(PRELUDE);
+  VirtualCode {
+    mappings: out
+      .map
+      .segments()
+      .iter()
+      .map(|(generated_offset, source_offset, length)| CodeMapping {
+        source_offset: *source_offset,
+        generated_offset: *generated_offset,
+        length: *length,
+      })
+      .collect(),
+    code: out.code,
+  }
+}
+
+enum Piece {
+  Text(String),
+  Source(Span),
+}
+
+fn text(text: &str) -> Piece {
+  Piece::Text(text.to_string())
+}
+
+/// Replaces `start..end` of the source with `pieces`.
+struct Edit {
+  start: u32,
+  end: u32,
+  /// Whether the edit closes an element, e.g. the end of a `v-for` loop.
+  closing: bool,
+  /// Order of the element in the traversal.
+  seq: i32,
+  pieces: Vec<Piece>,
+}
+
+struct VirtualCodeGenerator<'a, 'ctx> {
+  context: &'a TransformContext<'a>,
+  source: &'ctx str,
+  edits: Vec<Edit>,
+  /// Starts of the elements that are children of another element or fragment.
+  children: HashSet<u32>,
+  /// Tags of the enclosing components, `None` for other elements.
+  components: Vec<Option<String>>,
+}
+
+impl<'a> VirtualCodeGenerator<'a, '_> {
+  fn edit(&mut self, span: Span, closing: bool, seq: i32, pieces: Vec<Piece>) {
+    self.edits.push(Edit {
+      start: span.start,
+      end: span.end,
+      closing,
+      seq,
+      pieces,
+    });
+  }
+}
+
+impl<'a> VisitMut<'a> for VirtualCodeGenerator<'a, '_> {
+  fn visit_jsx_fragment(&mut self, node: &mut JSXFragment<'a>) {
+    self.components.push(None);
+    for child in &node.children {
+      if let JSXChild::Element(child) = child {
+        self.children.insert(child.span.start);
+      }
+    }
+    walk_mut::walk_jsx_fragment(self, node);
+    self.components.pop();
+  }
+
+  fn visit_jsx_element(&mut self, node: &mut JSXElement<'a>) {
+    let seq = self.edits.len() as i32;
+    let node = unsafe { &mut *(node as *mut JSXElement<'a>) };
+    let is_component = is_jsx_component(node);
+    let tag = match &node.opening_element.name {
+      JSXElementName::Identifier(_)
+      | JSXElementName::IdentifierReference(_)
+      | JSXElementName::MemberExpression(_) => Some(
+        node
+          .opening_element
+          .name
+          .span()
+          .source_text(self.source)
+          .to_string(),
+      ),
+      _ => None,
+    }
+    .filter(|_| is_component);
+    let model_prop = if is_component { None } else { model_prop(node) };
+    let parent = self.components.last().cloned().flatten();
+    let is_child = self.children.contains(&node.span.start);
+    let is_slot_template = is_template(node) && parent.is_some();
+    for child in &node.children {
+      if let JSXChild::Element(child) = child {
+        self.children.insert(child.span.start);
+      }
+    }
+
+    let mut if_pieces = None;
+    let mut for_pieces = None;
+    let mut slot_function = None;
+    let mut children_slot_function = None;
+    let opening_end = node.opening_element.span.end;
+    for attr in node.opening_element.attributes.iter_mut() {
+      let JSXAttributeItem::Attribute(attr) = attr else {
+        continue;
+      };
+      let span = attr.span;
+      let attr = unsafe { &mut *(attr.as_mut() as *mut JSXAttribute<'a>) };
+      let name = directive_name(attr);
+      let Some(directive) = name.strip_prefix("v-") else {
+        // Modifiers aren't part of the prop, e.g. `onClick` of `onClick_stop`.
+        if let JSXAttributeName::Identifier(name) = &attr.name
+          && let Some((prop, _)) = name.name.split_once('_')
+        {
+          self.edit(name.span, false, seq, vec![text(prop)]);
+        }
+        continue;
+      };
+      match directive {
+        "for" => {
+          let exp = expression_span(attr);
+          let source = get_for_parse_result(attr, self.context).and_then(|for_| {
+            let source = for_.source?;
+            let aliases = [for_.value, for_.key, for_.index]
+              .into_iter()
+              .flatten()
+              .map(|alias| alias.loc)
+              .collect::<Vec<_>>();
+            Some((aliases, source.loc))
+          });
+          match source {
+            Some((aliases, source)) => {
+              let mut pieces = vec![text("(() => { for (const [")];
+              for (index, alias) in aliases.into_iter().enumerate() {
+                if index > 0 {
+                  pieces.push(text(", "));
+                }
+                pieces.push(Piece::Source(alias));
+              }
+              pieces.extend([
+                text("] of __iter("),
+                Piece::Source(source),
+                text(")) { return "),
+              ]);
+              for_pieces = Some(pieces);
+              self.edit(span, false, seq, vec![]);
+            }
+            None => self.edit(span, false, seq, directive_check(None, exp)),
+          }
+        }
+        "slot" => {
+          if is_component {
+            children_slot_function = Some(slot_params(attr, tag.as_deref()));
+          } else if is_slot_template {
+            slot_function = Some(slot_params(attr, parent.as_deref()));
+          }
+          self.edit(span, false, seq, vec![]);
+        }
+        _ => {
+          let dir = resolve_directive(attr, self.context);
+          let exp = dir.exp.map(|exp| exp.loc);
+          let pieces = match (dir.name.as_str(), exp) {
+            ("if" | "else-if", Some(exp)) => {
+              if_pieces = Some(vec![text("("), Piece::Source(exp), text(") ? ")]);
+              vec![]
+            }
+            ("model", Some(exp)) if is_component => match dir.arg {
+              Some(arg) if !arg.is_static => vec![
+                Piece::Text(format!("{{...{{[{}]: ", arg.content)),
+                Piece::Source(exp),
+                text("}}"),
+              ],
+              arg => vec![
+                Piece::Text(format!(
+                  "{}={{",
+                  arg.map_or("modelValue".to_string(), |arg| arg.content)
+                )),
+                Piece::Source(exp),
+                text("}"),
+              ],
+            },
+            ("model", Some(exp)) if let Some(prop) = model_prop => vec![
+              Piece::Text(format!("{prop}={{")),
+              Piece::Source(exp),
+              text("}"),
+            ],
+            ("slots", Some(exp)) if let Some(tag) = &tag => vec![
+              Piece::Text(format!("{{...__vSlots<typeof {tag}>(")),
+              Piece::Source(exp),
+              text(")}"),
+            ],
+            (name, exp) if BUILT_IN_DIRECTIVES.contains(&name) => directive_check(None, exp),
+            (name, exp) => directive_check(Some(format!("v{}", capitalize(&camelize(name)))), exp),
+          };
+          self.edit(span, false, seq, pieces);
+        }
+      }
+    }
+
+    if if_pieces.is_some() || for_pieces.is_some() || slot_function.is_some() {
+      let mut opening = vec![text(if is_child { "{" } else { "(" })];
+      let mut closing = vec![];
+      if let Some(slot_function) = slot_function {
+        opening.extend(slot_function);
+        opening.push(text("("));
+        closing.push(text(")"));
+      }
+      if let Some(pieces) = if_pieces {
+        opening.extend(pieces);
+        closing.push(text(" : null"));
+      }
+      if let Some(pieces) = for_pieces {
+        opening.extend(pieces);
+        closing.push(text("; } })()"));
+      }
+      closing.reverse();
+      closing.push(text(if is_child { "}" } else { ")" }));
+      self.edit(Span::empty(node.span.start), false, seq, opening);
+      self.edit(Span::empty(node.span.end), true, seq, closing);
+    }
+
+    if let Some(slot_function) = children_slot_function
+      && let Some(closing) = &node.closing_element
+    {
+      let mut opening = vec![text("{")];
+      opening.extend(slot_function);
+      opening.push(text("<>"));
+      self.edit(Span::empty(opening_end), false, seq, opening);
+      self.edit(
+        Span::empty(closing.span.start),
+        true,
+        seq,
+        vec![text("</>}")],
+      );
+    }
+
+    self.components.push(tag);
+    walk_mut::walk_jsx_element(self, node);
+    self.components.pop();
+  }
+}
+
+/// `{...__directive(vName, value)}`, which checks that the directive and its
+/// value exist.
+fn directive_check(directive: Option<String>, exp: Option<Span>) -> Vec<Piece> {
+  let mut pieces = vec![text("{...__directive(")];
+  if let Some(directive) = &directive {
+    pieces.push(Piece::Text(directive.clone()));
+  }
+  match exp {
+    Some(exp) => {
+      if directive.is_some() {
+        pieces.push(text(", "));
+      }
+      pieces.push(Piece::Source(exp));
+    }
+    None if directive.is_none() => return vec![],
+    None => {}
+  }
+  pieces.push(text(")}"));
+  pieces
+}
+
+/// The prop that `v-model` binds on a native element, `None` for radios, whose
+/// model is compared to their `value`, and elements that don't support it.
+fn model_prop(node: &JSXElement) -> Option<&'static str> {
+  let JSXElementName::Identifier(tag) = &node.opening_element.name else {
+    return None;
+  };
+  match tag.name.as_str() {
+    "select" | "textarea" => Some("value"),
+    "input" => {
+      let input_type = node.opening_element.attributes.iter().find_map(|attr| {
+        if let JSXAttributeItem::Attribute(attr) = attr
+          && let JSXAttributeName::Identifier(name) = &attr.name
+          && name.name == "type"
+          && let Some(JSXAttributeValue::StringLiteral(value)) = &attr.value
+        {
+          Some(value.value.as_str())
+        } else {
+          None
+        }
+      });
+      match input_type {
+        Some("checkbox") => Some("checked"),
+        Some("radio") => None,
+        _ => Some("value"),
+      }
+    }
+    _ => None,
+  }
+}
+
+fn capitalize(name: &str) -> String {
+  let mut chars = name.chars();
+  chars
+    .next()
+    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+    .unwrap_or_default()
+}
+
+/// Span of the expression of `attr`, without parentheses.
+fn expression_span(attr: &JSXAttribute) -> Option<Span> {
+  match attr.value.as_ref()? {
+    JSXAttributeValue::ExpressionContainer(value) => value
+      .expression
+      .as_expression()
+      .map(|expression| expression.without_parentheses().span()),
+    _ => None,
+  }
+}
+
+fn directive_name(attr: &JSXAttribute) -> String {
+  let name = match &attr.name {
+    JSXAttributeName::Identifier(name) => name.name.as_str(),
+    JSXAttributeName::NamespacedName(name) => name.namespace.name.as_str(),
+  };
+  name.split('_').next().unwrap_or(name).to_string()
+}
+
+/// `(props: Type) => ` of a slot function, the props are typed from the slots
+/// of the component `tag`, e.g. `__SlotProps<typeof Comp, "default">`.
+fn slot_params(dir: &JSXAttribute, tag: Option<&str>) -> Vec<Piece> {
+  let name = match &dir.name {
+    JSXAttributeName::NamespacedName(name) => {
+      let arg = name.name.name.as_str();
+      (!arg.starts_with('$')).then(|| arg.split('_').next().unwrap_or(arg))
+    }
+    JSXAttributeName::Identifier(_) => Some("default"),
+  };
+  let Some(params) = expression_span(dir) else {
+    return vec![text("() => ")];
+  };
+  let props_type = match (tag, name) {
+    (Some(tag), Some(name)) => format!(
+      "__SlotProps<typeof {tag}, {}>",
+      serde_json::to_string(name).unwrap()
+    ),
+    _ => "any".to_string(),
+  };
+  vec![
+    text("("),
+    Piece::Source(params),
+    Piece::Text(format!(": {props_type}) => ")),
+  ]
+}
@@ -0,0 +1,127 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use insta::assert_snapshot;
+
+fn temp_dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("compiler-rs-cli-{name}"));
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(dir.join("src/nested")).unwrap();
+  dir
+}
+
+fn cli() -> Command {
+  Command::new(env!("CARGO_BIN_EXE_compiler-rs"))
+}
+
+#[test]
+fn compile_file_to_stdout() {
+  let dir = temp_dir("stdout");
+  let file = dir.join("src/index.jsx");
+  fs::write(&file, "export default () => <div>{msg}</div>").unwrap();
+  let output = cli().arg(&file).output().unwrap();
+  assert!(output.status.success());
+  assert_snapshot!(String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn compile_directory() {
+  let dir = temp_dir("directory");
+  fs::write(dir.join("src/a.jsx"), "<div>{a}</div>").unwrap();
+  fs::write(dir.join("src/nested/b.tsx"), "<span>{b as string}</span>").unwrap();
+  fs::write(dir.join("src/c.css"), "div {}").unwrap();
+  let out = dir.join("out");
+  let output = cli()
+    .arg(dir.join("src"))
+    .arg("--out-dir")
+    .arg(&out)
+    .arg("--source-map")
+    .output()
+    .unwrap();
+  assert!(output.status.success());
+  assert!(out.join("a.js").exists());
+  assert!(out.join("a.js.map").exists());
+  assert!(out.join("nested/b.js").exists());
+  assert!(!out.join("c.js").exists());
+  assert!(
+    fs::read_to_string(out.join("a.js"))
+      .unwrap()
+      .ends_with("//# sourceMappingURL=a.js.map\n")
+  );
+}
+
+#[test]
+fn emit_ir() {
+  let dir = temp_dir("ir");
+  let file = dir.join("src/index.jsx");
+  fs::write(&file, "<div>{msg}</div>").unwrap();
+  let output = cli().arg(&file).args(["--emit", "ir"]).output().unwrap();
+  assert!(output.status.success());
+  assert!(String::from_utf8(output.stdout).unwrap().starts_with("[{"));
+}
+
+#[test]
+fn diagnostics() {
+  let dir = temp_dir("diagnostics");
+  fs::write(dir.join("src/a.jsx"), "<div v-if />").unwrap();
+  fs::write(dir.join("src/b.jsx"), "<div").unwrap();
+  let output = cli()
+    .arg(dir.join("src"))
+    .args(["--emit", "diagnostics"])
+    .output()
+    .unwrap();
+  assert_eq!(output.status.code(), Some(1));
+  assert!(output.stdout.is_empty());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(stderr.contains("a.jsx:1:1: error: v-if/v-else-if is missing expression."));
+  assert!(stderr.contains("b.jsx:1:5: error: Invalid JavaScript syntax."));
+}
+
+#[test]
+fn diagnostics_columns() {
+  let dir = temp_dir("columns");
+  let file = dir.join("src/index.jsx");
+  fs::write(&file, "const a = \"é😀\"; <div").unwrap();
+  let output = cli()
+    .arg(&file)
+    .args(["--emit", "diagnostics"])
+    .output()
+    .unwrap();
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(stderr.contains("index.jsx:1:22: error: Invalid JavaScript syntax."));
+}
+
+#[test]
+fn duplicate_outputs() {
+  let dir = temp_dir("duplicates");
+  fs::write(dir.join("src/a.jsx"), "<div>{a}</div>").unwrap();
+  fs::write(dir.join("src/a.tsx"), "<div>{a}</div>").unwrap();
+  fs::write(dir.join("src/nested/b.jsx"), "<div>{b}</div>").unwrap();
+  fs::write(dir.join("b.jsx"), "<div>{b}</div>").unwrap();
+  let out = dir.join("out");
+  for inputs in [
+    vec![dir.join("src")],
+    vec![dir.join("src/nested"), dir.join("b.jsx")],
+  ] {
+    let output = cli()
+      .args(&inputs)
+      .arg("--out-dir")
+      .arg(&out)
+      .output()
+      .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+      String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("would both be written to")
+    );
+  }
+  assert!(!out.exists());
+}
+
+#[test]
+fn invalid_arguments() {
+  let output = cli().arg("--unknown").output().unwrap();
+  assert_eq!(output.status.code(), Some(2));
+  let output = cli().output().unwrap();
+  assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,119 @@
+use compiler_rs::transform::{TransformOptions, transform};
+use insta::assert_snapshot;
+
+#[test]
+fn element_and_text_locals() {
+  let code = transform(
+    "const n0 = 1
+    const x0 = 2
+    const a = <div>{n0}<span>{x0}</span></div>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn shadowed_template() {
+  let code = transform(
+    "function Comp(t0, _template) {
+      return <div>{t0}{_template}</div>
+    }",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn template_ref() {
+  let code = transform("const a = <div ref={r0}>{_setTemplateRef}</div>", None).code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_for() {
+  let code = transform(
+    "<div v-for={(item, index) in _.range(n1)} key={index}>
+      {_for_item0}{_for_key0}{_for_index0}{index}
+    </div>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_for_selector() {
+  let code = transform(
+    "<tr
+      v-for={row in rows}
+      key={row.id}
+      class={selected === row.id ? createSelector : _selector0_0}
+    ></tr>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_model() {
+  let code = transform("<input v-model={_value} />", None).code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn slot_props() {
+  let code = transform("<Comp v-slot={{ foo }}>{_slotProps0}{foo}{p0}</Comp>", None).code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn custom_directive() {
+  let code = transform("<div v-example={[_dir, _binding, _mounted]}></div>", None).code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn hmr() {
+  let code = transform(
+    "const __default__ = 1
+    const mod = 2
+    export default () => <div>{__default__}{mod}</div>",
+    Some(TransformOptions {
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn ssr() {
+  let code = transform(
+    "const __moduleId = 1
+    export const Comp = () => <div>{__moduleId}</div>",
+    Some(TransformOptions {
+      ssr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn shadowed_helper_import() {
+  let code = transform(
+    r#"import { child } from "vue"
+    function f(child) {
+      return <div>{child}</div>
+    }"#,
+    None,
+  )
+  .code;
+  assert!(code.contains("_child(n0)"), "{code}");
+  assert_snapshot!(code);
+}
@@ -1,6 +1,17 @@
-use compiler_rs::transform::transform;
+use std::collections::HashMap;
+
+use compiler_rs::transform::{
+  TransformOptions,
+  custom_directive::{
+    CustomDirective, CustomDirectiveEvent, CustomDirectiveProp, CustomDirectiveResult,
+    DirectiveTransform,
+  },
+  transform,
+};
 use insta::assert_snapshot;
 
+mod runtime;
+
 #[test]
 fn basic() {
   let code = transform("<div v-example></div>", None).code;
@@ -56,3 +67,110 @@ fn component() {
   .code;
   assert_snapshot!(code);
 }
+
+#[test]
+fn directive_transforms() {
+  let code = transform(
+    "<>
+      <input v-focus_stop={visible} />
+      <Comp v-focus={visible} />
+    </>",
+    Some(TransformOptions {
+      directive_transforms: HashMap::from([(
+        String::from("focus"),
+        Box::new(|dir: CustomDirective| {
+          assert_eq!(dir.exp.as_deref(), Some("visible"));
+          Some(CustomDirectiveResult {
+            props: Some(vec![CustomDirectiveProp {
+              key: String::from("autofocus"),
+              ..Default::default()
+            }]),
+            events: Some(vec![CustomDirectiveEvent {
+              name: String::from("blur"),
+              value: Some(String::from("() => (visible = false)")),
+              modifiers: Some(dir.modifiers),
+            }]),
+            ..Default::default()
+          })
+        }) as DirectiveTransform,
+      )]),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn directive_transforms_need_runtime() {
+  let code = transform(
+    "<div v-tooltip:top={msg} />",
+    Some(TransformOptions {
+      directive_transforms: HashMap::from([(
+        String::from("tooltip"),
+        Box::new(|dir: CustomDirective| {
+          Some(CustomDirectiveResult {
+            props: Some(vec![CustomDirectiveProp {
+              key: String::from("data-placement"),
+              value: dir.arg,
+              is_static: Some(true),
+            }]),
+            need_runtime: Some(true),
+            ..Default::default()
+          })
+        }) as DirectiveTransform,
+      )]),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn directive_lifecycle() {
+  let Some(log) = runtime::run(
+    r#"const show = Vue.ref(true)
+const msg = Vue.ref("a")
+const log = []
+const vLog = (el, value, arg, modifiers) => {
+  log.push(`created ${el.tag} ${value()} ${arg} ${Object.keys(modifiers ?? {})} ${el.isConnected}`)
+  Vue.watch(value, (value, oldValue) => log.push(`updated ${el.tag} ${value} ${oldValue}`))
+  return () => log.push(`unmounted ${el.tag}`)
+}
+const Comp = () => <p>comp</p>
+const App = () => (
+  <main>
+    <span v-if={show.value} v-log:foo_bar={msg.value} />
+    <Comp v-log={msg.value} />
+  </main>
+)"#,
+    TransformOptions::default(),
+    r#"const unmount = Vue.mount(App)
+await Vue.nextTick()
+for (const [name, update] of [
+  ["value", () => (msg.value = "b")],
+  ["v-if", () => (show.value = false)],
+  ["unmount", unmount],
+]) {
+  log.push(`-- ${name}`)
+  update()
+  await Vue.nextTick()
+}
+console.log(log.join("\n"))"#,
+  ) else {
+    return;
+  };
+  assert_eq!(
+    log.trim(),
+    "created span a foo bar false
+created p a undefined  false
+-- value
+updated span b a
+updated p b a
+-- v-if
+unmounted span
+-- unmount
+unmounted p"
+  );
+}
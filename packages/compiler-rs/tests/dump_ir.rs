@@ -0,0 +1,42 @@
+use compiler_rs::transform::{TransformOptions, transform_file};
+use insta::assert_snapshot;
+
+#[test]
+fn dump_ir() {
+  let ir = transform_file(
+    "<>
+      <div id={id} onClick={handleClick}>{msg}</div>
+      <Comp v-if={ok} foo={bar} />
+      <span v-else />
+    </>",
+    Some(TransformOptions {
+      dump_ir: true,
+      ..Default::default()
+    }),
+  )
+  .ir
+  .unwrap();
+  let ir: serde_json::Value = serde_json::from_str(&ir).unwrap();
+  assert_snapshot!(serde_json::to_string_pretty(&ir).unwrap());
+}
+
+#[test]
+fn dump_ir_per_root() {
+  let ir = transform_file(
+    "const a = <div>{a}</div>; const b = <span>{b}</span>",
+    Some(TransformOptions {
+      dump_ir: true,
+      ..Default::default()
+    }),
+  )
+  .ir
+  .unwrap();
+  let ir: serde_json::Value = serde_json::from_str(&ir).unwrap();
+  assert_eq!(ir.as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn without_dump_ir() {
+  let ir = transform_file("<div>{msg}</div>", None).ir;
+  assert!(ir.is_none());
+}
@@ -0,0 +1,70 @@
+use std::cell::RefCell;
+
+use compiler_rs::{
+  transform::{TransformOptions, transform},
+  utils::error::ErrorCodes,
+};
+use insta::assert_snapshot;
+
+fn transform_with_errors(source: &str) -> (String, Vec<ErrorCodes>) {
+  let errors = RefCell::new(vec![]);
+  let code = transform(
+    source,
+    Some(TransformOptions {
+      on_error: Box::new(|e, _| errors.borrow_mut().push(e)),
+      ..Default::default()
+    }),
+  )
+  .code;
+  (code, errors.into_inner())
+}
+
+#[test]
+fn v_if_no_expression() {
+  let (code, errors) = transform_with_errors(
+    "<div><span v-if={}>foo</span><span v-else-if={bar}>bar</span><span v-else>baz</span></div>",
+  );
+  assert_eq!(errors, vec![ErrorCodes::VIfNoExpression]);
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_else_no_adjacent_if() {
+  let (code, errors) =
+    transform_with_errors("<div><span>foo</span><span v-else>bar</span><span>{baz}</span></div>");
+  assert_eq!(errors, vec![ErrorCodes::VElseNoAdjacentIf]);
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_for_malformed_expression() {
+  let (code, errors) =
+    transform_with_errors("<div><span v-for={items}>{item}</span><span>{foo}</span></div>");
+  assert_eq!(errors, vec![ErrorCodes::VForMalformedExpression]);
+  assert_snapshot!(code);
+}
+
+#[test]
+fn all_errors_in_one_pass() {
+  let (code, errors) = transform_with_errors(
+    r#"const Foo = () => <div>
+      <span v-for />
+      <span v-else />
+      <input v-model={} />
+      <div v-html={foo}>bar</div>
+    </div>
+    const Bar = () => <Comp v-slot={foo}><template v-slot:default /></Comp>
+    "#,
+  );
+  assert_eq!(
+    errors,
+    vec![
+      ErrorCodes::VForNoExpression,
+      ErrorCodes::VElseNoAdjacentIf,
+      ErrorCodes::VModelNoExpression,
+      ErrorCodes::VHtmlWithChildren,
+      ErrorCodes::VSlotMixedSlotUsage,
+    ]
+  );
+  assert_snapshot!(code);
+}
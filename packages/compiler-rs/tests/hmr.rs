@@ -92,3 +92,136 @@ pub fn exports_with_define_component() {
   .code;
   assert_snapshot!(code);
 }
+
+/// Returns the setup and render hashes of the only component of `source`.
+fn hmr_hashes(source: &str) -> (String, String) {
+  let code = transform(
+    source,
+    Some(TransformOptions {
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  let hash = |key: &str| {
+    let start = code.find(&format!("{key} = \"")).unwrap() + key.len() + 4;
+    code[start..start + code[start..].find('"').unwrap()].to_string()
+  };
+  (hash("__hmrSetupHash"), hash("__hmrRenderHash"))
+}
+
+#[test]
+pub fn signature() {
+  let code = transform(
+    "
+    export const Comp = () => {
+      const count = ref(0)
+      return <div onClick={() => count.value++}>{count.value}</div>
+    }
+  ",
+    Some(TransformOptions {
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn setup_and_render_hashes() {
+  let (setup, render) = hmr_hashes(
+    "export default () => {
+      const count = ref(0)
+      return <div>{count.value}</div>
+    }",
+  );
+  // Only the JSX changed, so the component is rerendered.
+  let jsx_changed = hmr_hashes(
+    "export default () => {
+      const count = ref(0)
+      return <p class=\"count\">{count.value * 2}</p>
+    }",
+  );
+  assert_eq!(jsx_changed.0, setup);
+  assert_ne!(jsx_changed.1, render);
+  // The setup changed, so the component is reloaded.
+  let setup_changed = hmr_hashes(
+    "export default () => {
+      const count = ref(1)
+      return <div>{count.value}</div>
+    }",
+  );
+  assert_ne!(setup_changed.0, setup);
+  assert_eq!(setup_changed.1, render);
+  // Only whitespace changed, so the component is left alone.
+  assert_eq!(
+    hmr_hashes(
+      "export default () => {
+        const count   = ref(0)
+
+        return <div>{count.value}</div>
+      }",
+    ),
+    (setup, render)
+  );
+}
+
+#[test]
+pub fn local_components() {
+  let code = transform(
+    "
+    const Item = () => <li />
+    function List() {
+      return <ul><Item /></ul>
+    }
+    const format = () => {}
+    const Store = () => ({})
+    const renderItem = () => <Item />
+    export default () => <List />
+  ",
+    Some(TransformOptions {
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn component_wrappers() {
+  let code = transform(
+    "
+    export const Comp = memoComponent(defineVaporComponent(() => <div />))
+    export const Store = createStore(() => {})
+  ",
+    Some(TransformOptions {
+      hmr: true,
+      component_wrappers: vec!["memoComponent".to_string()],
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn imported_factories() {
+  let code = transform(
+    "
+    import { defineVaporComponent as dvc } from 'vue'
+    import * as Vue from 'vue'
+    import { defineComponent } from 'other'
+    export const A = dvc({})
+    export const B = Vue.defineComponent({})
+    export const C = defineComponent({})
+  ",
+    Some(TransformOptions {
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
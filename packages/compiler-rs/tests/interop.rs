@@ -21,3 +21,42 @@ fn basic() {
   .code;
   assert_snapshot!(code);
 }
+
+#[test]
+fn imported_factories() {
+  let code = transform(
+    "import { defineVaporComponent as dvc } from 'vue'
+    import * as Vue from 'vue'
+    import { defineComponent } from 'other'
+    const A = dvc(() => <div />)
+    const B = Vue.defineComponent(() => () => <div />)
+    const C = defineComponent(() => <div />)
+    const D = Vue.defineVaporComponent(() => {
+      const dvc = defineComponent
+      return dvc(() => () => <span />)
+    })",
+    Some(TransformOptions {
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn custom_factories() {
+  let code = transform(
+    "import { defineVapor, defineComponent } from '@acme/vue'
+    const A = defineVapor(() => <div />)
+    const B = defineComponent(() => () => <div />)",
+    Some(TransformOptions {
+      interop: true,
+      vapor_factories: vec!["defineVapor".to_string()],
+      factory_modules: vec!["@acme/vue".to_string()],
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
@@ -0,0 +1,85 @@
+use compiler_rs::{
+  sfc::transform_sfc,
+  template::transform_template,
+  transform::{ComponentUsage, TransformMetadata, TransformOptions, transform_file},
+};
+
+#[test]
+fn metadata() {
+  let source = "export const Foo = () => (
+  <>
+    <Comp ref={comp} v-focus />
+    <div onClick={onClick} v-focus:top={pos} v-tooltip>{msg}</div>
+    <Foo.Bar />
+  </>
+)";
+  let metadata = transform_file(
+    source,
+    Some(TransformOptions {
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .metadata;
+  let loc = |text: &str| {
+    let start = source.find(text).unwrap() as u32;
+    (start, start + text.len() as u32)
+  };
+  assert_eq!(
+    metadata,
+    TransformMetadata {
+      components: vec![
+        ComponentUsage {
+          name: "Comp".to_string(),
+          loc: loc("Comp"),
+        },
+        ComponentUsage {
+          name: "Foo.Bar".to_string(),
+          loc: loc("Foo.Bar"),
+        },
+      ],
+      directives: vec!["focus".to_string(), "tooltip".to_string()],
+      helpers: [
+        "child",
+        "createComponent",
+        "createTemplateRefSetter",
+        "delegateEvents",
+        "renderEffect",
+        "setNodes",
+        "template",
+        "withVaporDirectives",
+      ]
+      .map(String::from)
+      .to_vec(),
+      delegates: vec!["click".to_string()],
+      template_count: 1,
+      has_template_ref: true,
+      hmr_ids: metadata.hmr_ids.clone(),
+    }
+  );
+  assert_eq!(metadata.hmr_ids.len(), 1);
+}
+
+#[test]
+fn template_and_sfc() {
+  let source = "<div><my-comp /></div>";
+  let metadata = transform_template(source, None).metadata;
+  assert_eq!(
+    metadata.components,
+    [ComponentUsage {
+      name: "my-comp".to_string(),
+      loc: (6, 13),
+    }]
+  );
+
+  let source = "<script setup lang=\"tsx\">\nconst a = <Comp />\n</script>";
+  let metadata = transform_sfc(source, None).metadata;
+  let start = source.find("<Comp").unwrap() as u32;
+  assert_eq!(
+    metadata.components,
+    [ComponentUsage {
+      name: "Comp".to_string(),
+      loc: (start + 1, start + 5),
+    }]
+  );
+}
@@ -0,0 +1,106 @@
+use std::{
+  cell::RefCell,
+  panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use compiler_rs::{
+  compile::compile,
+  transform::{TransformOptions, transform},
+  utils::error::ErrorCodes,
+};
+use oxc_span::SourceType;
+
+const CORPUS: &[&str] = &[
+  "const A = () => <input v-model={(a)} />",
+  "const A = () => <input v-model={a as any} v-model:b={c!} />",
+  "const A = () => <Comp v-model={(a satisfies B)} v-model:foo={b.c} />",
+  "const A = () => <div onClick={() => ({ a = 1, b: d = 2 } = x)} />",
+  "const A = () => <div onClick={() => ({ a: [b], c: { d } } = x)} />",
+  "const A = () => <div v-for={(item) in list} onClick={() => ({ item = 1, x: item } = y)} />",
+  "const A = () => <div v-for={([a]) in list} onClick={() => ({ a } = x)}><input v-model={a} /></div>",
+  "const A = () => <div v-for={({ [k]: a, 'b-c': b, 1: c, ...rest }) in list}>{a}{b}{c}{rest}</div>",
+  "const A = () => <div v-for={({ a = 1, b: [c = 2, ...d] }) in list}>{a}{c}{d}</div>",
+  "const A = () => <Comp v-slot={({ [k]: a, ...rest })}>{a}{rest}</Comp>",
+  "const A = () => <div class={`${a}\\x`}>{`${1}\\u{zz}`}</div>",
+  "const A = () => <div v-if={} v-for={} v-html={} v-text={} v-show={} v-on={} v-slots={} />",
+  "const A = () => <Comp v-slots={} v-model={} v-slot={} />",
+  "const A = () => <div v-if v-for v-slot v-model v-show v-on v-bind v-once v-memo />",
+  "const A = () => <input v-model v-html=\"a\" v-text=\"b\" v-show=\"c\" />",
+  "const A = () => <div v-=\"a\" v-:b={c} v-on:={d} v-bind:={e} on={f} onClick_={g} />",
+  "const A = () => <><div v-else /><div v-else-if={a} /></>",
+  "const A = () => <Comp><template v-else v-slot:a /><template v-slot /><template v-slot /></Comp>",
+  "const A = () => <Comp v-slot:a={x}><template v-slot:b />text</Comp>",
+  "const A = () => <input type=\"file\" v-model={a} value={b} />",
+  "const A = () => <div>{}{/* */}{...a}<>{<></>}</></div>",
+  "const A = () => <this.a>{this.b}</this.a>",
+  "const A = () => <a:b c:d=\"1\" {...x} />",
+  "export default interface A {}",
+  "export default defineComponent((props) => <div />, { name: 'a' })",
+  "export { A as default }; const A = () => <div />",
+  "const A = <T,>(p: T) => <div>{p as any}{<T>p}{p!}</div>",
+  "<div />",
+  "",
+];
+
+fn assert_no_panic(source: &str) {
+  for (ssr, hmr, interop) in [
+    (false, false, false),
+    (true, false, false),
+    (false, true, true),
+  ] {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+      transform(
+        source,
+        Some(TransformOptions {
+          source_type: SourceType::tsx(),
+          ssr,
+          hmr,
+          interop,
+          ..Default::default()
+        }),
+      );
+      compile(source, None);
+    }));
+    assert!(result.is_ok(), "panicked on {source:?}");
+  }
+}
+
+#[test]
+fn corpus() {
+  for source in CORPUS {
+    assert_no_panic(source);
+  }
+}
+
+#[test]
+fn truncated_corpus() {
+  for source in CORPUS {
+    for (index, _) in source.char_indices() {
+      assert_no_panic(&source[..index]);
+    }
+  }
+}
+
+#[test]
+fn error_on_empty_expression() {
+  let errors = RefCell::new(vec![]);
+  let code = transform(
+    "<><div v-if={} /><div v-html={} /><div v-show={} /></>",
+    Some(TransformOptions {
+      on_error: Box::new(|e, _| errors.borrow_mut().push(e)),
+      ..Default::default()
+    }),
+  )
+  .code;
+  // `undefined` could be shadowed, `void 0` can't.
+  assert!(!code.contains("undefined"), "{code}");
+  assert!(code.contains("_setHtml(n3, void 0)"), "{code}");
+  assert_eq!(
+    *errors.borrow(),
+    [
+      ErrorCodes::VIfNoExpression,
+      ErrorCodes::VHtmlNoExpression,
+      ErrorCodes::VShowNoExpression
+    ]
+  );
+}
@@ -0,0 +1,74 @@
+use compiler_rs::transform::{TransformOptions, transform};
+use insta::assert_snapshot;
+
+mod runtime;
+
+#[test]
+fn templates_before_components() {
+  let code = transform(
+    r#"import { ref } from 'vue'
+    const count = ref(0)
+    export const Foo = () => <div onClick={() => count.value++}>{count.value}</div>
+    export const Bar = () => <span><div>{count.value}</div></span>
+    "#,
+    Some(TransformOptions {
+      per_component_templates: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn templates_not_shared_between_components() {
+  let code = transform(
+    r#"const Foo = () => <div>{foo}</div>
+    const Bar = () => [<div>{bar}</div>, <div>{baz}</div>]
+    "#,
+    Some(TransformOptions {
+      per_component_templates: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn templates_with_hmr() {
+  let code = transform(
+    "export default () => <div>{foo}</div>",
+    Some(TransformOptions {
+      per_component_templates: true,
+      hmr: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn templates_before_hoisted_components() {
+  let source = r#"const log = []
+    log.push(App().tag, Comp().tag)
+    function App() {
+      return <div id="app" />
+    }
+    export default function Comp() {
+      return <main />
+    }
+    const render = () => Late()
+    const Late = () => <span />
+    log.push(render().tag)"#;
+  let options = || TransformOptions {
+    per_component_templates: true,
+    ..Default::default()
+  };
+  assert_snapshot!(transform(source, Some(options())).code);
+  let Some(log) = runtime::run(source, options(), "console.log(log.join())") else {
+    return;
+  };
+  assert_eq!(log.trim(), "div,main,span");
+}
@@ -0,0 +1,75 @@
+use compiler_rs::transform::{TransformOptions, transform};
+use insta::assert_snapshot;
+
+#[test]
+fn file_pragma() {
+  let code = transform(
+    "/* @vdom */
+
+    const A = () => <div />
+    /* @vapor */
+    const B = () => <span />",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn component_pragmas() {
+  let code = transform(
+    "const A = () => <div />
+    /** @jsxMode vdom */
+    function B() {
+      return <div />
+    }
+    export const C = /* @vdom */ () => <div />
+    /* @vdom */
+    export default () => {
+      const D = /* @vapor */ () => <span />
+      return <div />
+    }",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn file_pragma_after_directive() {
+  let code = transform(
+    "/* @vdom */
+    'use strict'
+    const A = () => <div />",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn leading_component_pragma() {
+  let code = transform(
+    "/* @vdom */ export const A = () => <div />
+    export const B = () => <span />",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn pragmas_with_interop() {
+  let code = transform(
+    "/* @jsxMode vapor */
+    const A = () => <div />
+    const B = defineComponent(() => () => <div />)
+    const C = defineComponent(/* @vapor */ () => <div />)",
+    Some(TransformOptions {
+      interop: true,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
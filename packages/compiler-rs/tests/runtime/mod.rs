@@ -0,0 +1,44 @@
+use std::{
+  io::Write,
+  process::{Command, Stdio},
+};
+
+use compiler_rs::transform::{ImportStyle, TransformOptions, transform};
+
+/// Compiles `source` with `options` and runs it in Node with the stand-in
+/// runtime of `vue.js`, followed by `script`. Returns what the script
+/// printed, or `None` when Node isn't installed.
+pub fn run(source: &str, options: TransformOptions, script: &str) -> Option<String> {
+  let code = transform(
+    source,
+    Some(TransformOptions {
+      import_style: ImportStyle::Global,
+      ..options
+    }),
+  )
+  .code;
+  let Ok(mut node) = Command::new("node")
+    .arg("--input-type=module")
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+  else {
+    eprintln!("skipped, `node` is not installed");
+    return None;
+  };
+  let program = format!("{}\n{code}\n{script}", include_str!("vue.js"));
+  node
+    .stdin
+    .take()
+    .unwrap()
+    .write_all(program.as_bytes())
+    .unwrap();
+  let output = node.wait_with_output().unwrap();
+  assert!(
+    output.status.success(),
+    "{}\n{program}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+  Some(String::from_utf8(output.stdout).unwrap())
+}
@@ -0,0 +1,215 @@
+// A minimal stand-in for the Vapor runtime, enough to run compiled output in
+// Node and observe when effects, watchers and post-flush callbacks run.
+
+class Node {
+  constructor(tag) {
+    this.tag = tag
+    this.parentNode = null
+    this.childNodes = []
+    this.isRoot = false
+  }
+  get isConnected() {
+    let node = this
+    while (node.parentNode) node = node.parentNode
+    return node.isRoot
+  }
+  append(child) {
+    child.parentNode = this
+    this.childNodes.push(child)
+  }
+  remove() {
+    const { childNodes } = this.parentNode
+    childNodes.splice(childNodes.indexOf(this), 1)
+    this.parentNode = null
+  }
+}
+globalThis.Node = Node
+
+let activeEffect = null
+let currentScope = null
+let insertionParent = null
+
+function untracked(fn) {
+  const prev = activeEffect
+  activeEffect = null
+  try {
+    return fn()
+  } finally {
+    activeEffect = prev
+  }
+}
+
+function effect(fn, scheduler) {
+  const runner = () => {
+    const prev = activeEffect
+    activeEffect = runner
+    try {
+      return fn()
+    } finally {
+      activeEffect = prev
+    }
+  }
+  runner.scheduler = scheduler
+  runner.active = true
+  onScopeDispose(() => (runner.active = false))
+  return runner
+}
+
+function ref(value) {
+  const subscribers = new Set()
+  return {
+    get value() {
+      if (activeEffect) subscribers.add(activeEffect)
+      return value
+    },
+    set value(newValue) {
+      value = newValue
+      for (const subscriber of [...subscribers]) {
+        if (subscriber.active) subscriber.scheduler()
+      }
+    },
+  }
+}
+
+const jobs = []
+const postFlushCbs = []
+let flushing = null
+
+function queueFlush() {
+  flushing ||= Promise.resolve().then(() => {
+    while (jobs.length) jobs.shift()()
+    while (postFlushCbs.length) postFlushCbs.shift()()
+    flushing = null
+  })
+}
+function queueJob(job) {
+  if (!jobs.includes(job)) jobs.push(job)
+  queueFlush()
+}
+function queuePostFlushCb(cb) {
+  postFlushCbs.push(cb)
+  queueFlush()
+}
+function nextTick() {
+  return flushing || Promise.resolve()
+}
+
+function onScopeDispose(fn) {
+  if (currentScope) currentScope.cleanups.push(fn)
+}
+function runInScope(fn) {
+  const scope = { cleanups: [], active: true }
+  onScopeDispose(() => stopScope(scope))
+  const prev = currentScope
+  currentScope = scope
+  try {
+    return [untracked(fn), scope]
+  } finally {
+    currentScope = prev
+  }
+}
+function stopScope(scope) {
+  if (!scope.active) return
+  scope.active = false
+  for (const cleanup of scope.cleanups) cleanup()
+}
+
+function renderEffect(fn) {
+  const runner = effect(fn, () => queueJob(runner))
+  runner()
+}
+
+function watch(getter, cb) {
+  let oldValue
+  const runner = effect(getter, () =>
+    queueJob(() => {
+      const value = runner()
+      if (value !== oldValue) {
+        const prev = oldValue
+        oldValue = value
+        untracked(() => cb(value, prev))
+      }
+    }),
+  )
+  oldValue = runner()
+}
+
+function template(html) {
+  const tag = /^<([\w-]+)/.exec(html)[1]
+  return () => new Node(tag)
+}
+
+function setInsertionState(parent) {
+  insertionParent = parent
+}
+function takeInsertionParent() {
+  const parent = insertionParent
+  insertionParent = null
+  return parent
+}
+
+function createComponent(component, props) {
+  const parent = takeInsertionParent()
+  const [block] = runInScope(() => component(props))
+  const instance = { block }
+  if (parent) parent.append(block)
+  return instance
+}
+
+function createIf(condition, render, renderElse) {
+  const parent = takeInsertionParent()
+  const fragment = { nodes: null }
+  let scope = null
+  let current
+  renderEffect(() => {
+    const value = !!condition()
+    if (value === current) return
+    current = value
+    if (scope) {
+      stopScope(scope)
+      fragment.nodes.remove()
+      fragment.nodes = null
+    }
+    const branch = value ? render : renderElse
+    if (branch) {
+      ;[fragment.nodes, scope] = runInScope(branch)
+      parent.append(fragment.nodes)
+    }
+  })
+  return fragment
+}
+
+function withVaporDirectives(node, dirs) {
+  while (node && !(node instanceof Node)) node = node.block || node.nodes
+  for (const [dir, value, argument, modifiers] of dirs) {
+    const cleanup = dir(node, value, argument, modifiers)
+    if (cleanup) onScopeDispose(cleanup)
+  }
+}
+
+function mount(component) {
+  const root = new Node('#root')
+  root.isRoot = true
+  const [block, scope] = runInScope(component)
+  root.append(block)
+  return () => {
+    stopScope(scope)
+    block.remove()
+  }
+}
+
+globalThis.Vue = {
+  createComponent,
+  createIf,
+  mount,
+  nextTick,
+  onScopeDispose,
+  queuePostFlushCb,
+  ref,
+  renderEffect,
+  setInsertionState,
+  template,
+  watch,
+  withVaporDirectives,
+}
+globalThis.VueJsxVapor = globalThis.Vue
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use compiler_rs::transform::{ImportStyle, TransformOptions, transform};
+use insta::assert_snapshot;
+use oxc_span::SourceType;
+
+const SOURCE: &str = "const a = <div onClick={onClick}>{msg}<Comp /></div>";
+
+#[test]
+pub fn runtime_modules() {
+  let code = transform(
+    SOURCE,
+    Some(TransformOptions {
+      runtime_modules: HashMap::from([
+        ("template".to_string(), "@vue/runtime-vapor".to_string()),
+        ("setNodes".to_string(), "vue".to_string()),
+      ]),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn namespace_import() {
+  let code = transform(
+    SOURCE,
+    Some(TransformOptions {
+      import_style: ImportStyle::Namespace,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn global_import() {
+  let code = transform(
+    SOURCE,
+    Some(TransformOptions {
+      import_style: ImportStyle::Global,
+      runtime_globals: HashMap::from([("vue-jsx-vapor".to_string(), "VueJsx".to_string())]),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn script_require() {
+  let code = transform(
+    SOURCE,
+    Some(TransformOptions {
+      source_type: SourceType::cjs().with_jsx(true),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn script_namespace_require() {
+  let code = transform(
+    SOURCE,
+    Some(TransformOptions {
+      source_type: SourceType::cjs().with_jsx(true),
+      import_style: ImportStyle::Namespace,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn ssr_register_helper_module() {
+  let code = transform(
+    "export const foo = () => {}",
+    Some(TransformOptions {
+      ssr: true,
+      runtime_modules: HashMap::from([(
+        "ssrRegisterHelper".to_string(),
+        "vue-jsx-vapor/ssr".to_string(),
+      )]),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn merge_existing_imports() {
+  let code = transform(
+    "import { ref } from 'vue'
+    import Foo from 'vue-jsx-vapor'
+    const a = <div>{msg}<Comp /></div>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn reuse_imported_helpers() {
+  let code = transform(
+    "import { template as _template, child as getChild } from 'vue'
+    const a = <div>{msg}</div>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn skip_type_and_namespace_imports() {
+  let code = transform(
+    "import type { Ref } from 'vue'
+    import * as Vue from 'vue'
+    const a = <div>{msg}</div>",
+    Some(TransformOptions {
+      source_type: SourceType::tsx(),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn collision_free_names() {
+  let code = transform(
+    "const _template = 1
+    const t0 = 2
+    function Comp(_child, _setTemplateRef) {
+      return <div ref={r} onClick={_delegateEvents}>{_child}</div>
+    }",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+pub fn collision_free_namespace() {
+  let code = transform(
+    "const _vue = 1
+    const a = <div>{_vue}</div>",
+    Some(TransformOptions {
+      import_style: ImportStyle::Namespace,
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+
+use compiler_rs::{sfc::transform_sfc, transform::TransformOptions, utils::error::ErrorCodes};
+use insta::assert_snapshot;
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_sourcemap::SourceMap;
+use oxc_span::{SourceType, Span};
+
+const SFC: &str = r#"<script lang="tsx">
+export const Foo = () => <div>foo</div>
+</script>
+
+<script setup lang="tsx">
+import { ref } from 'vue'
+const count = ref<number>(0)
+const Comp = () => <button onClick={() => count.value++}>{count.value}</button>
+</script>
+
+<template>
+  <template v-if="count"><Comp /></template>
+</template>
+
+<style>
+.a { color: red }
+</style>
+"#;
+
+#[test]
+fn script_blocks() {
+  let code = transform_sfc(SFC, None).code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn merged_blocks() {
+  // `compileScript` merges the blocks into one module.
+  let code = transform_sfc(SFC, None).code;
+  let merged = code
+    .split("</script>")
+    .filter_map(|block| {
+      let block = &block[block.rfind("<script")?..];
+      Some(&block[block.find('>')? + 1..])
+    })
+    .collect::<String>();
+  let allocator = Allocator::default();
+  let ret = Parser::new(&allocator, &merged, SourceType::tsx()).parse();
+  assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+  let errors = SemanticBuilder::new()
+    .with_check_syntax_error(true)
+    .build(&ret.program)
+    .errors;
+  assert!(errors.is_empty(), "{errors:?}\n{merged}");
+}
+
+#[test]
+fn quoted_attributes() {
+  let source = r#"<template>
+  <div :title="a > b"><template v-if="ok">{{ a }}</template></div>
+</template>
+<script setup lang="tsx" generic="T extends Record<string, any>">
+const a = <div>{a}</div>
+</script>"#;
+  let code = transform_sfc(source, None).code;
+  assert!(code.contains(r#"<script setup lang="tsx" generic="T extends Record<string, any>">"#));
+  assert!(code.contains("_template(\"<div> </div>\""), "{code}");
+}
+
+#[test]
+fn skip_other_blocks() {
+  let source = r#"<!-- <script lang="tsx">const a = <div /></script> -->
+<template><div /></template>
+<script setup lang="ts">
+const msg = '<script lang="tsx">'
+</script>"#;
+  assert_eq!(transform_sfc(source, None).code, source);
+}
+
+#[test]
+fn errors() {
+  let errors = RefCell::new(vec![]);
+  let source = "<template><Comp /></template>\n<script setup lang=\"jsx\">\nconst a = <input v-model={1} />\n</script>";
+  transform_sfc(
+    source,
+    Some(TransformOptions {
+      on_error: Box::new(|code, span| errors.borrow_mut().push((code, span))),
+      ..Default::default()
+    }),
+  );
+  let start = source.find("1}").unwrap() as u32;
+  assert_eq!(
+    errors.into_inner(),
+    vec![(
+      ErrorCodes::VModelMalformedExpression,
+      Span::new(start, start + 1)
+    )]
+  );
+}
+
+#[test]
+fn source_map() {
+  let result = transform_sfc(
+    SFC,
+    Some(TransformOptions {
+      filename: "App.vue",
+      source_map: true,
+      ..Default::default()
+    }),
+  );
+  let map = SourceMap::from_json_string(&result.map.unwrap()).unwrap();
+  assert_eq!(
+    map
+      .get_sources()
+      .map(|source| source.as_ref())
+      .collect::<Vec<_>>(),
+    ["App.vue"]
+  );
+  let lookup_table = map.generate_lookup_table();
+  let position = |code: &str, text: &str| {
+    let offset = code.find(text).unwrap();
+    let line = code[..offset].matches('\n').count() as u32;
+    let column = (offset - code[..offset].rfind('\n').map_or(0, |index| index + 1)) as u32;
+    (line, column)
+  };
+  // `count.value++` of the compiled `<script setup>`, and the style after it.
+  for text in ["count.value++", "<style>"] {
+    let (line, column) = position(&result.code, text);
+    let token = map.lookup_token(&lookup_table, line, column).unwrap();
+    assert_eq!(
+      (token.get_src_line(), token.get_src_col()),
+      position(SFC, text),
+      "{text}"
+    );
+  }
+}
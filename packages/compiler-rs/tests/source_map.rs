@@ -0,0 +1,118 @@
+use std::{cell::RefCell, path::PathBuf};
+
+use compiler_rs::{
+  transform::{TransformOptions, transform},
+  utils::error::ErrorCodes,
+};
+use oxc_allocator::Allocator;
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+const ORIGINAL: &str = "// generated header
+
+
+const msg = 'hello'
+export default <div>{msg}</div>
+";
+
+/// Simulates a pre-processing step by re-printing `ORIGINAL` with a source map.
+fn preprocess() -> (String, String) {
+  let allocator = Allocator::default();
+  let program = Parser::new(&allocator, ORIGINAL, SourceType::jsx())
+    .parse()
+    .program;
+  let result = Codegen::new()
+    .with_options(CodegenOptions {
+      comments: oxc_codegen::CommentOptions::disabled(),
+      source_map_path: Some(PathBuf::from("original.jsx")),
+      ..CodegenOptions::default()
+    })
+    .build(&program);
+  (result.code, result.map.unwrap().to_json_string())
+}
+
+/// Returns the `(line, column)` of `needle` in `source`, zero-based.
+fn position(source: &str, needle: &str) -> (u32, u32) {
+  let offset = source.find(needle).unwrap();
+  let before = &source[..offset];
+  let line = before.matches('\n').count();
+  let column = offset - before.rfind('\n').map_or(0, |i| i + 1);
+  (line as u32, column as u32)
+}
+
+#[test]
+fn input_source_map() {
+  let (intermediate, input_map) = preprocess();
+  let result = transform(
+    &intermediate,
+    Some(TransformOptions {
+      source_map: true,
+      input_source_map: Some(&input_map),
+      ..Default::default()
+    }),
+  );
+  let map = result.map.unwrap();
+  assert_eq!(
+    map.get_sources().map(|s| s.as_ref()).collect::<Vec<_>>(),
+    ["original.jsx"]
+  );
+  assert_eq!(map.get_source_content(0).unwrap().as_ref(), ORIGINAL);
+
+  let (dst_line, dst_col) = position(&result.code, "msg =");
+  let token = map
+    .get_tokens()
+    .find(|token| token.get_dst_line() == dst_line && token.get_dst_col() == dst_col)
+    .unwrap();
+  assert_eq!(
+    (token.get_src_line(), token.get_src_col()),
+    position(ORIGINAL, "msg =")
+  );
+}
+
+#[test]
+fn invalid_input_source_map() {
+  let source = "export default <div>{msg}</div>";
+  let errors = RefCell::new(vec![]);
+  let options = |input_source_map| TransformOptions {
+    source_map: true,
+    input_source_map,
+    on_error: Box::new(|code, _| errors.borrow_mut().push(code)),
+    ..Default::default()
+  };
+  assert_eq!(
+    transform(source, Some(options(Some("not a source map"))))
+      .map
+      .map(|map| map.to_json_string()),
+    transform(source, Some(options(None)))
+      .map
+      .map(|map| map.to_json_string())
+  );
+  assert_eq!(errors.take(), [ErrorCodes::XInvalidInputSourceMap]);
+}
+
+#[test]
+fn unmapped_input_lines() {
+  // The JSX on the second line has no mapping, unlike its neighbours.
+  let source = "const a = 1\nexport default <div>{msg}</div>\nconst b = 2";
+  let input_map = r#"{"version":3,"sources":["original.jsx"],"names":[],"mappings":"AAAA;;AACA"}"#;
+  let result = transform(
+    source,
+    Some(TransformOptions {
+      source_map: true,
+      input_source_map: Some(input_map),
+      ..Default::default()
+    }),
+  );
+  let mapped_lines = [
+    position(&result.code, "const a").0,
+    position(&result.code, "const b").0,
+  ];
+  let map = result.map.unwrap();
+  assert!(map.get_tokens().count() > 0);
+  assert!(
+    map
+      .get_tokens()
+      .all(|token| mapped_lines.contains(&token.get_dst_line()))
+  );
+}
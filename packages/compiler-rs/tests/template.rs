@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+
+use compiler_rs::{
+  template::transform_template, transform::TransformOptions, utils::error::ErrorCodes,
+};
+use insta::assert_snapshot;
+use oxc_sourcemap::SourceMap;
+use oxc_span::Span;
+
+fn template(source: &str) -> String {
+  transform_template(source, None).code
+}
+
+#[test]
+fn interpolation() {
+  let code = template("<div>{{ msg }} and {{ count + 1 }}</div>");
+  assert_snapshot!(code);
+}
+
+#[test]
+fn attributes() {
+  let code = template(
+    r#"<div id="app" :class="cls" v-bind:title.camel="title" :[key]="value" v-bind="attrs" :id />"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn events() {
+  let code = template(
+    r#"<div>
+  <button @click="increment">+</button>
+  <button @click.stop="count++">+</button>
+  <button @click="() => count--">-</button>
+  <input @keyup.enter="submit($event); reset()" v-on:update:model-value="update" @[event]="handler">
+</div>"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn directives() {
+  let code = template(
+    r#"<div v-if="ok" v-show="visible">{{ a }}</div>
+<p v-else-if="other" v-html="html"></p>
+<input v-else v-model.trim="text" v-focus:top.lazy="pos">"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_for() {
+  let code = template(
+    r#"<ul>
+  <li v-for="(item, index) in items" :key="item.id" @click="select(item, index)">
+    {{ index }}: {{ item.name }} of {{ items.length }} {{ Math.max(index, max) }}
+  </li>
+</ul>"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_slot() {
+  let code = template(
+    r#"<Comp>
+  <template #header="{ title }">{{ title }} {{ { title, subtitle } }}</template>
+  <template v-for="name in names" #[name]>{{ name }}</template>
+  <template v-slot:default>{{ title }}</template>
+</Comp>"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn assets() {
+  let code = template(
+    r#"<div>
+  <Comp v-focus />
+  <my-comp />
+  <component :is="view" />
+</div>"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn dynamic_arguments() {
+  let code = template(
+    r#"<Comp v-model:[prop]="value" v-focus:[arg]="pos">
+  <template #[slot_name]>{{ a }}</template>
+  <template v-for="item in items" #[item.name]>{{ item }}</template>
+  <template v-for="(item, i) in items" #[`item-${i}`]>{{ item }}</template>
+</Comp>
+<div v-focus:[dir_arg]="pos" v-focus:[a?'x':'y'].lazy />"#,
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn text() {
+  let code = template("<span>a &lt; b</span><br/><!-- comment --><span>{ c }</span>");
+  assert_snapshot!(code);
+}
+
+#[test]
+fn errors() {
+  let errors = RefCell::new(vec![]);
+  let source = "<div>{{ msg </span>";
+  transform_template(
+    source,
+    Some(TransformOptions {
+      on_error: Box::new(|code, span| errors.borrow_mut().push((code, span))),
+      ..Default::default()
+    }),
+  );
+  assert_eq!(
+    errors.into_inner(),
+    vec![
+      (ErrorCodes::XMissingInterpolationEnd, Span::new(5, 7)),
+      (ErrorCodes::XMissingEndTag, Span::new(1, 4)),
+    ],
+  );
+  let errors = RefCell::new(vec![]);
+  let source = "<div></span><Comp #[+a+]=\"{ a }\"><input v-model=\"1\"></Comp>";
+  transform_template(
+    source,
+    Some(TransformOptions {
+      on_error: Box::new(|code, span| errors.borrow_mut().push((code, span))),
+      ..Default::default()
+    }),
+  );
+  assert_eq!(
+    errors.into_inner(),
+    vec![
+      (ErrorCodes::XInvalidEndTag, Span::new(5, 12)),
+      (ErrorCodes::XMissingEndTag, Span::new(1, 4)),
+      (ErrorCodes::XUnsupportedDynamicArgument, Span::new(18, 24)),
+      (ErrorCodes::VModelMalformedExpression, Span::new(49, 50)),
+    ],
+  );
+}
+
+#[test]
+fn source_map() {
+  let source = "<div>\n  {{ msg }}\n</div>";
+  let map = transform_template(
+    source,
+    Some(TransformOptions {
+      filename: "App.vue",
+      source_map: true,
+      ..Default::default()
+    }),
+  )
+  .map
+  .unwrap();
+  let map = SourceMap::from_json_string(&map).unwrap();
+  assert_eq!(
+    map
+      .get_sources()
+      .map(|source| source.as_ref())
+      .collect::<Vec<_>>(),
+    ["App.vue"]
+  );
+  assert_eq!(
+    map
+      .get_source_contents()
+      .flatten()
+      .map(|content| content.as_ref())
+      .collect::<Vec<_>>(),
+    [source]
+  );
+  // `msg` of the interpolation.
+  assert!(
+    map
+      .get_source_view_tokens()
+      .any(|token| (token.get_src_line(), token.get_src_col()) == (1, 5))
+  );
+}
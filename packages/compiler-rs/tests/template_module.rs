@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+
+use compiler_rs::{
+  transform::{ImportStyle, TransformOptions, transform},
+  traverse::template_module::{generate_template_module, template_export},
+};
+use insta::assert_snapshot;
+
+#[test]
+fn import_templates_from_module() {
+  let code = transform(
+    r#"const Foo = () => <div onClick={onClick}>{foo}</div>
+    const Bar = () => <span><div class="row" /></span>
+    "#,
+    Some(TransformOptions {
+      template_module: Some("virtual:vapor-templates"),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn shared_template_exports() {
+  let options = || TransformOptions {
+    template_module: Some("virtual:vapor-templates"),
+    ..Default::default()
+  };
+  let foo = transform("const Foo = () => <div class=\"row\" />", Some(options())).code;
+  let bar = transform(
+    "const Baz = () => <span />\nconst Bar = () => <div class=\"row\" />",
+    Some(options()),
+  )
+  .code;
+  let export = template_export(&("<div class=\"row\"></div>".to_string(), true));
+  assert!(foo.contains(&format!("{export} as t0")));
+  assert!(bar.contains(&format!("{export} as t1")));
+}
+
+#[test]
+fn stable_template_exports() {
+  assert_eq!(
+    template_export(&("<div class=\"row\"></div>".to_string(), true)),
+    "t_508524cc117b1f52"
+  );
+}
+
+#[test]
+fn generate_module() {
+  let templates = [
+    ("<div class=\"row\"></div>".to_string(), true),
+    ("<span> </span>".to_string(), false),
+    ("<div class=\"row\"></div>".to_string(), true),
+  ];
+  let errors = RefCell::new(vec![]);
+  let code = generate_template_module(
+    &templates,
+    &TransformOptions {
+      on_error: Box::new(|code, _| errors.borrow_mut().push(code)),
+      ..Default::default()
+    },
+  );
+  assert_eq!(code.matches("export const").count(), 2);
+  // The same template from many files is exported once, not reported as a collision.
+  assert!(errors.take().is_empty());
+  assert_snapshot!(code);
+}
+
+#[test]
+fn generate_module_with_namespace_import() {
+  let templates = [("<div></div>".to_string(), true)];
+  let code = generate_template_module(
+    &templates,
+    &TransformOptions {
+      import_style: ImportStyle::Namespace,
+      ..Default::default()
+    },
+  );
+  assert_snapshot!(code);
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "napi")]
+
+use std::{
+  env::consts::{DLL_PREFIX, DLL_SUFFIX},
+  fs,
+  path::PathBuf,
+  process::Command,
+};
+
+/// Loads the built addon in Node, runs `script` with it bound to `compiler`
+/// and returns what the script printed, or `None` when Node isn't installed.
+/// Every test loads its own copy of the addon, named after `test`.
+fn run(test: &str, script: &str) -> Option<String> {
+  // `target/debug/deps/transform_async-*` -> `target/debug`
+  let exe = std::env::current_exe().unwrap();
+  let library = exe
+    .parent()
+    .and_then(|deps| deps.parent())
+    .unwrap()
+    .join(format!("{DLL_PREFIX}compiler_rs{DLL_SUFFIX}"));
+  let addon = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("compiler-rs-{test}.node"));
+  fs::copy(&library, &addon).unwrap();
+  let Ok(output) = Command::new("node")
+    .arg("-e")
+    .arg(format!(
+      "const compiler = require({:?});\n{script}",
+      addon.to_string_lossy()
+    ))
+    .env("RUST_BACKTRACE", "0")
+    .output()
+  else {
+    eprintln!("skipped, `node` is not installed");
+    return None;
+  };
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+  Some(String::from_utf8(output.stdout).unwrap())
+}
+
+// `v-model:$a` panics while resolving the directive, which stands in for any
+// bug in the compiler here.
+const PANICS: &str = "<input v-model:$a={b} />";
+
+#[test]
+fn transform_async() {
+  let Some(output) = run(
+    "transform_async",
+    &format!(
+      r#"(async () => {{
+  const errors = []
+  const {{ code }} = await compiler.transformAsync("<div v-if />", {{
+    onError: (error) => errors.push(error.code),
+  }})
+  console.log(code.includes("_createIf"), errors.join())
+  await compiler
+    .transformAsync({PANICS:?}, {{ filename: "panics.jsx" }})
+    .catch((error) => console.log(error.message))
+}})()"#
+    ),
+  ) else {
+    return;
+  };
+  let lines = output.lines().collect::<Vec<_>>();
+  assert_eq!(lines[0], "true 28");
+  assert!(
+    lines[1].starts_with("Failed to transform `panics.jsx`: "),
+    "{output}"
+  );
+}
+
+#[test]
+fn transform_many() {
+  let Some(output) = run(
+    "transform_many",
+    &format!(
+      r#"(async () => {{
+  const files = Array.from({{ length: 50 }}, (_, i) => ({{
+    source: i === 7 ? "<div v-if />" : `<div id="${{i}}" />`,
+    filename: `${{i}}.jsx`,
+  }}))
+  const errors = []
+  const results = await compiler.transformMany(files, {{
+    onError: (error) => errors.push(`${{error.filename}} ${{error.code}}`),
+  }})
+  console.log(results.every(({{ filename, result }}, i) => filename === `${{i}}.jsx` && (i === 7 || result.code.includes(`id=\\"${{i}}\\"`))))
+  console.log(errors.join())
+  files[3] = {{ source: {PANICS:?}, filename: "panics.jsx" }}
+  const [, , , panics, ...rest] = await compiler.transformMany(files)
+  console.log(panics.filename, panics.result === undefined, rest.every(({{ result }}) => result.code))
+  console.log(panics.error)
+}})()"#
+    ),
+  ) else {
+    return;
+  };
+  let lines = output.lines().collect::<Vec<_>>();
+  assert_eq!(lines[..3], ["true", "7.jsx 28", "panics.jsx true true"]);
+  assert!(
+    lines[3].starts_with("Failed to transform `panics.jsx`: "),
+    "{output}"
+  );
+}
@@ -50,6 +50,32 @@ fn component_v_for_should_not_mark_as_single_root() {
   assert_snapshot!(code);
 }
 
+#[test]
+fn component_dynamic_component() {
+  let code = transform(
+    "<component is={foo ? Foo : Bar} foo={bar}><div>{baz}</div></component>",
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn component_dynamic_component_string() {
+  let code = transform(
+    r#"<div><component is="my-el" foo="bar" /><component is={"my-el"} /></div>"#,
+    None,
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn component_v_is_on_native_tag() {
+  let code = transform("<table><tr v-is={Row} id={id}>{foo}</tr></table>", None).code;
+  assert_snapshot!(code);
+}
+
 #[test]
 fn component_static_props() {
   let code = transform("<Foo id=\"foo\" class=\"bar\" />", None).code;
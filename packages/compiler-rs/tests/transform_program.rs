@@ -0,0 +1,48 @@
+use compiler_rs::transform::{TransformContext, TransformOptions, transform_program};
+use insta::assert_snapshot;
+use oxc_allocator::Allocator;
+use oxc_codegen::Codegen;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::SourceType;
+
+#[test]
+fn transform_program_in_place() {
+  let allocator = Allocator::default();
+  let source = "const a = <div onClick={onClick}>{msg}</div>";
+  let mut program = Parser::new(&allocator, source, SourceType::jsx())
+    .parse()
+    .program;
+  let options = TransformOptions::default();
+  let context = TransformContext::new(&allocator, &options);
+  let result = transform_program(&mut program, &context, None);
+  assert_eq!(
+    result.helpers.into_iter().collect::<Vec<_>>(),
+    ["child", "delegateEvents", "setNodes", "template"]
+  );
+  assert_eq!(result.templates, [("<div> </div>".to_string(), true)]);
+  assert_eq!(result.delegates.into_iter().collect::<Vec<_>>(), ["click"]);
+  assert!(result.ir.is_none());
+  assert_snapshot!(Codegen::new().build(&program).code);
+}
+
+#[test]
+fn transform_program_with_scoping() {
+  let allocator = Allocator::default();
+  let source = "const a = <div>{msg}</div>";
+  let mut program = Parser::new(&allocator, source, SourceType::jsx())
+    .parse()
+    .program;
+  let scoping = SemanticBuilder::new()
+    .build(&program)
+    .semantic
+    .into_scoping();
+  let options = TransformOptions::default();
+  let context = TransformContext::new(&allocator, &options);
+  let result = transform_program(&mut program, &context, Some(scoping));
+  for name in ["a", "t0", "_template", "_setNodes"] {
+    assert!(result.scoping.get_root_binding(name).is_some(), "{name}");
+  }
+  assert_eq!(result.templates.len(), 1);
+  assert_snapshot!(Codegen::new().build(&program).code);
+}
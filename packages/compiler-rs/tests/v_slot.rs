@@ -183,7 +183,7 @@ fn error_on_duplicated_slot_names() {
 #[test]
 fn error_on_invalid_mixed_slot_usage() {
   let error = RefCell::new(None);
-  transform(
+  let code = transform(
     "<Comp v-slot={foo}>
       <template v-slot:foo></template>
     </Comp>",
@@ -193,8 +193,10 @@ fn error_on_invalid_mixed_slot_usage() {
       }),
       ..Default::default()
     }),
-  );
+  )
+  .code;
   assert_eq!(*error.borrow(), Some(ErrorCodes::VSlotMixedSlotUsage));
+  assert_snapshot!(code);
 }
 
 #[test]
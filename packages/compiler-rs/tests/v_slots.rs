@@ -53,7 +53,7 @@ fn should_raise_error_if_not_component() {
 #[test]
 fn should_raise_error_if_has_children() {
   let error = RefCell::new(None);
-  transform(
+  let code = transform(
     "<Comp v-slots={obj}> </Comp>",
     Some(TransformOptions {
       on_error: Box::new(|e, _| {
@@ -61,8 +61,10 @@ fn should_raise_error_if_has_children() {
       }),
       ..Default::default()
     }),
-  );
+  )
+  .code;
   assert_eq!(*error.borrow(), Some(ErrorCodes::VSlotMixedSlotUsage));
+  assert_snapshot!(code);
 }
 
 #[test]
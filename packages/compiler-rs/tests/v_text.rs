@@ -15,7 +15,7 @@ fn should_convert_v_text_to_set_text() {
 #[test]
 fn should_raise_error_and_ignore_children_when_v_text_is_present() {
   let error = RefCell::new(None);
-  transform(
+  let code = transform(
     "<div v-text={test}>hello</div>",
     Some(TransformOptions {
       on_error: Box::new(|e, _| {
@@ -23,8 +23,10 @@ fn should_raise_error_and_ignore_children_when_v_text_is_present() {
       }),
       ..Default::default()
     }),
-  );
+  )
+  .code;
   assert_eq!(*error.borrow(), Some(ErrorCodes::VTextWithChildren));
+  assert_snapshot!(code);
 }
 
 #[test]
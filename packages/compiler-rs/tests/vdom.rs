@@ -0,0 +1,192 @@
+use std::cell::RefCell;
+
+use compiler_rs::{
+  transform::{TransformOptions, transform},
+  utils::error::ErrorCodes,
+};
+use insta::assert_snapshot;
+
+fn vdom(source: &str) -> String {
+  transform(
+    source,
+    Some(TransformOptions {
+      interop: true,
+      vdom: true,
+      ..Default::default()
+    }),
+  )
+  .code
+}
+
+#[test]
+fn mixed() {
+  let code = vdom(
+    "const A = defineComponent(() => () => <div class=\"a\">{foo}</div>)
+    const B = defineVaporComponent(() => <div class=\"a\">{foo}</div>)",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn patch_flags() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <>
+        <div class={cls} style={style} id={id} onClick={onClick} title=\"static\" />
+        <div {...attrs} id={id} />
+        <input ref={input} />
+        <Comp foo={foo} class={cls} />
+      </>
+    ))",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn static_hoisting() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <div>
+        <p class=\"title\">Hello <b>world</b></p>
+        <span>{msg}</span>
+      </div>
+    ))
+    const B = defineComponent(() => () => <img src=\"logo.png\" />)",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn slots() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <>
+        <Comp>default {foo}</Comp>
+        <Comp>{{ default: () => foo, title: () => <span /> }}</Comp>
+        <Comp v-slots={slots}>default</Comp>
+        <Comp>static <b>text</b></Comp>
+        <resolved-comp />
+        <component is={Comp} />
+        <Foo.Bar />
+      </>
+    ))",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn blocks() {
+  let code = vdom(
+    "const A = defineComponent(() => () => <div class={cls}><p>{msg}</p><Comp /></div>)
+    const B = defineComponent(() => () => <Comp foo={foo} />)
+    const C = defineComponent(() => () => <>{list.map((item) => <li>{item}</li>)}</>)
+    const D = defineComponent(() => () => <Comp icon={<i>{icon}</i>} />)
+    const E = defineComponent(() => () => <input v-show={visible} />)",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn text_children() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <div>
+        <p>{msg}</p>
+        <p>Hello {name}!</p>
+        <p>Hi <b>{name}</b> {count}</p>
+        <Comp>{msg}</Comp>
+      </div>
+    ))",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_if() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <div>
+        <p v-if={a}>a</p>
+        <Comp v-else-if={b} />
+        <p v-else key=\"c\">c</p>
+        <span v-if={d} />
+      </div>
+    ))
+    const B = defineComponent(() => () => <div v-if={visible}>{msg}</div>)",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_for() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <ul>
+        <li v-for={item in list} key={item.id}>{item.name}</li>
+        <li v-for={({ id }, index) in list}>{index}</li>
+        <li v-if={show} v-for={item in list} key={item}>{item}</li>
+      </ul>
+    ))",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn directives() {
+  let code = vdom(
+    "const A = defineComponent(() => () => (
+      <>
+        <div v-show={visible} v-html={html} />
+        <input v-model_trim={text} />
+        <Comp v-model={value} v-model:title={title} />
+        <div v-focus:top_lazy={value} />
+      </>
+    ))",
+  );
+  assert_snapshot!(code);
+}
+
+#[test]
+fn unsupported_directive() {
+  let errors = RefCell::new(vec![]);
+  transform(
+    "const A = defineComponent(() => () => <div v-once />)",
+    Some(TransformOptions {
+      interop: true,
+      vdom: true,
+      on_error: Box::new(|code, _| errors.borrow_mut().push(code)),
+      ..Default::default()
+    }),
+  );
+  assert_eq!(errors.take(), vec![ErrorCodes::VDomUnsupportedDirective]);
+}
+
+#[test]
+fn structural_directive_errors() {
+  let errors = RefCell::new(vec![]);
+  let code = transform(
+    "const A = defineComponent(() => () => (
+      <div>
+        <p v-else />
+        <p v-for />
+        <p v-for={list} />
+      </div>
+    ))",
+    Some(TransformOptions {
+      interop: true,
+      vdom: true,
+      on_error: Box::new(|code, _| errors.borrow_mut().push(code)),
+      ..Default::default()
+    }),
+  )
+  .code;
+  assert_eq!(
+    errors.take(),
+    vec![
+      ErrorCodes::VElseNoAdjacentIf,
+      ErrorCodes::VForNoExpression,
+      ErrorCodes::VForMalformedExpression
+    ]
+  );
+  assert_snapshot!(code);
+}
@@ -0,0 +1,107 @@
+use compiler_rs::{
+  transform::TransformOptions,
+  virtual_code::{VirtualCode, generate_virtual_code},
+};
+use insta::assert_snapshot;
+use oxc_span::SourceType;
+
+fn virtual_code(source: &str) -> VirtualCode {
+  generate_virtual_code(
+    source,
+    Some(TransformOptions {
+      source_type: SourceType::tsx(),
+      ..Default::default()
+    }),
+  )
+}
+
+#[test]
+fn v_for() {
+  let code = virtual_code(
+    "const list = [{ id: 1 }]
+export default () => (
+  <ul>
+    <li v-for={({ id }, index) in list} key={id}>{index}</li>
+    <li v-for={item in list} v-if={item.id}>{item.id}</li>
+  </ul>
+)",
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_if() {
+  let code = virtual_code("export default () => <div v-if={foo}>{foo.bar}</div>").code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn v_slot() {
+  let code = virtual_code(
+    "export default () => (
+  <>
+    <Comp v-slot={{ foo }}>{foo}</Comp>
+    <Comp>
+      <template v-slot:header={({ title })}>{title}</template>
+      <template v-for={name in names} v-slot:$name$={props}>{props}</template>
+    </Comp>
+    <Comp v-slots={{ default: ({ foo }) => foo }} />
+  </>
+)",
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn directives() {
+  let code = virtual_code(
+    "export default () => (
+  <>
+    <input v-model={text} v-show={visible} onClick_stop={onClick} />
+    <input type=\"checkbox\" v-model={checked} />
+    <input type=\"radio\" value=\"a\" v-model={picked} />
+    <select v-model={selected} />
+    <Comp v-model={value} v-model:title={title} v-model:$name$_trim={other} />
+    <div v-focus:top_lazy={pos} v-else v-once />
+  </>
+)",
+  )
+  .code;
+  assert_snapshot!(code);
+}
+
+#[test]
+fn prelude() {
+  let module = virtual_code("export default () => <div />").code;
+  assert!(!module.contains("export {};"));
+  let script = virtual_code("const a = <div />").code;
+  assert!(script.contains("\nexport {};\n"));
+}
+
+#[test]
+fn default_options() {
+  let code = generate_virtual_code("const a: number = 1;\n<div v-if={a} />", None).code;
+  assert!(code.starts_with("const a: number = 1;\n((a) ? <div  /> : null)"));
+}
+
+#[test]
+fn mappings() {
+  let source = "<li v-for={item in list}>{item.id}</li>";
+  let VirtualCode { code, mappings } = virtual_code(source);
+  for text in ["list", "item.id"] {
+    let source_offset = source.find(text).unwrap() as u32;
+    let mapping = mappings
+      .iter()
+      .find(|mapping| {
+        (mapping.source_offset..mapping.source_offset + mapping.length).contains(&source_offset)
+      })
+      .unwrap();
+    let generated_offset = mapping.generated_offset + source_offset - mapping.source_offset;
+    assert_eq!(
+      &code[generated_offset as usize..generated_offset as usize + text.len()],
+      text
+    );
+  }
+}